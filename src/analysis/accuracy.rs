@@ -0,0 +1,233 @@
+use std::fmt::Display;
+
+use crate::types::cgats::CgatsTable;
+use crate::types::profile::Profile;
+use crate::utils::color::{
+    bradford_adaptation, delta_e_2000, lab_to_xyz, mat_vec, xyz_to_lab, D50_XYZ,
+};
+
+pub struct PatchError {
+    pub id: String,
+    pub device: Vec<f64>,    // Device values (0..100)
+    pub measured: [f64; 3],  // Measured Lab, relative to the media white
+    pub predicted: [f64; 3], // Lab computed by the profile
+    pub delta_e: f64,        // CIEDE2000
+}
+
+pub struct AccuracyReport {
+    pub source: String,    // Tags the measurements come from
+    pub transform: String, // Profile table used for the device values
+    pub white: [f64; 3],   // Measured media white XYZ
+    pub patches: Vec<PatchError>,
+}
+
+// Measurements come from the targ tag, or from the DevD (device) and CIED (measured) tags
+fn measurement_tables(profile: &Profile) -> Result<(String, CgatsTable, CgatsTable), String> {
+    let measured = |table: &CgatsTable| table.xyz().is_some() || table.lab().is_some();
+
+    if let Ok(targ) = profile.cgats("targ") {
+        if let Some(table) = targ
+            .tables
+            .iter()
+            .find(|table| !table.device_fields().is_empty() && measured(table))
+        {
+            return Ok(("targ".to_string(), table.clone(), table.clone()));
+        }
+    }
+
+    let (device, cied) = match (profile.cgats("DevD"), profile.cgats("CIED")) {
+        (Ok(device), Ok(cied)) => (device, cied),
+        (Err(err), _) | (_, Err(err)) => {
+            return Err(format!("No targ or DevD/CIED measurement data ({})", err))
+        }
+    };
+    let device_table = device
+        .tables
+        .iter()
+        .find(|table| !table.device_fields().is_empty())
+        .ok_or("No device values in the DevD tag")?;
+    let measured_table = cied
+        .tables
+        .iter()
+        .find(|table| measured(table))
+        .ok_or("No XYZ or Lab values in the CIED tag")?;
+
+    Ok((
+        "DevD + CIED".to_string(),
+        device_table.clone(),
+        measured_table.clone(),
+    ))
+}
+
+// Measured values normalized to the media white (brightest patch) and adapted to D50,
+// as the relative colorimetric PCS values of the profile.
+// XYZ measurements (displays) are adapted with Bradford, Lab measurements (D50, printers)
+// are scaled to the media white like the ICC absolute colorimetric intent does.
+fn relative_lab(table: &CgatsTable) -> Result<(Vec<[f64; 3]>, [f64; 3]), String> {
+    let (xyz, bradford) = match (table.xyz(), table.lab()) {
+        (Some(xyz), _) => (xyz, true),
+        (None, Some(lab)) => (
+            lab.iter()
+                .map(|lab| lab_to_xyz(lab, &D50_XYZ).map(|val| val * 100.0))
+                .collect(),
+            false,
+        ),
+        _ => return Err("No XYZ or Lab measurements".to_string()),
+    };
+
+    let white = xyz
+        .iter()
+        .copied()
+        .max_by(|xyz, other| xyz[1].total_cmp(&other[1]))
+        .ok_or("No measurements")?;
+    if white[1] <= 0.0 {
+        return Err("Media white luminance is zero".to_string());
+    }
+    let white_norm = white.map(|val| val / white[1]);
+    let adaptation = bradford_adaptation(&white_norm, &D50_XYZ)?;
+
+    let labs = xyz
+        .iter()
+        .map(|xyz| {
+            let norm = xyz.map(|val| val / white[1]);
+            let relative = if bradford {
+                mat_vec(&adaptation, &norm)
+            } else {
+                [
+                    norm[0] * D50_XYZ[0] / white_norm[0],
+                    norm[1],
+                    norm[2] * D50_XYZ[2] / white_norm[2],
+                ]
+            };
+            xyz_to_lab(&relative, &D50_XYZ)
+        })
+        .collect();
+
+    Ok((labs, white))
+}
+
+// Runs the measured device values through the relative colorimetric table of the profile
+pub fn verify_profile(profile: &Profile) -> Result<AccuracyReport, String> {
+    let (source, device_table, measured_table) = measurement_tables(profile)?;
+    let transform = profile.device_to_pcs(1)?;
+
+    let devices = device_table
+        .device_values()
+        .ok_or("Device values are not numbers")?;
+    let (measured, white) = relative_lab(&measured_table)?;
+    if devices.len() != measured.len() {
+        return Err(format!(
+            "{} device values for {} measurements",
+            devices.len(),
+            measured.len()
+        ));
+    }
+
+    let ids: Vec<String> = match device_table.field_index("SAMPLE_ID") {
+        Some(id_idx) => device_table
+            .sets
+            .iter()
+            .map(|set| set[id_idx].clone())
+            .collect(),
+        None => (1..=devices.len()).map(|id| id.to_string()).collect(),
+    };
+
+    let mut patches: Vec<PatchError> = Vec::with_capacity(devices.len());
+    for ((id, device), measured) in ids.into_iter().zip(devices).zip(measured) {
+        let normalized: Vec<f64> = device.iter().map(|val| val / 100.0).collect();
+        let predicted = xyz_to_lab(&transform.eval(&normalized)?, &D50_XYZ);
+        patches.push(PatchError {
+            id,
+            device,
+            measured,
+            predicted,
+            delta_e: delta_e_2000(&measured, &predicted),
+        });
+    }
+    if patches.is_empty() {
+        return Err("No patches in the measurement data".to_string());
+    }
+
+    Ok(AccuracyReport {
+        source,
+        transform: transform.name(),
+        white,
+        patches,
+    })
+}
+
+impl AccuracyReport {
+    pub fn average(&self) -> f64 {
+        self.patches.iter().map(|patch| patch.delta_e).sum::<f64>() / self.patches.len() as f64
+    }
+
+    pub fn max(&self) -> f64 {
+        self.patches
+            .iter()
+            .map(|patch| patch.delta_e)
+            .fold(0.0, f64::max)
+    }
+
+    // Nearest rank percentile
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let mut delta_es: Vec<f64> = self.patches.iter().map(|patch| patch.delta_e).collect();
+        delta_es.sort_by(f64::total_cmp);
+        let rank = (percent / 100.0 * delta_es.len() as f64).ceil() as usize;
+        delta_es[rank.clamp(1, delta_es.len()) - 1]
+    }
+
+    pub fn worst(&self, count: usize) -> Vec<&PatchError> {
+        let mut patches: Vec<&PatchError> = self.patches.iter().collect();
+        patches.sort_by(|patch, other| other.delta_e.total_cmp(&patch.delta_e));
+        patches.truncate(count);
+        patches
+    }
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let device: Vec<String> = self
+            .device
+            .iter()
+            .map(|val| format!("{:.1}", val))
+            .collect();
+        write!(
+            f,
+            "      {:>6}  [{}]  measured {:.2} {:.2} {:.2}  profile {:.2} {:.2} {:.2}  ΔE2000 {:.2}",
+            self.id,
+            device.join(" "),
+            self.measured[0],
+            self.measured[1],
+            self.measured[2],
+            self.predicted[0],
+            self.predicted[1],
+            self.predicted[2],
+            self.delta_e
+        )
+    }
+}
+
+impl Display for AccuracyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "    Measurements : {} ({} patches)",
+            self.source,
+            self.patches.len()
+        )?;
+        writeln!(f, "    Profile table : {}", self.transform)?;
+        writeln!(
+            f,
+            "    Measured white : XYZ {:.2} {:.2} {:.2}",
+            self.white[0], self.white[1], self.white[2]
+        )?;
+        writeln!(
+            f,
+            "    ΔE2000 average : {:.2}, maximum : {:.2}, 95th percentile : {:.2}",
+            self.average(),
+            self.max(),
+            self.percentile(95.0)
+        )?;
+        write!(f, "    Worst patches :")
+    }
+}
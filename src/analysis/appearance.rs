@@ -0,0 +1,178 @@
+use std::fmt::Display;
+
+use crate::types::icc::{Measurement, ViewingConditions};
+use crate::types::profile::{Profile, TagData};
+use crate::utils::color::{
+    bradford_adaptation, mat_inv, mat_mul, mat_vec, xy_to_xyz, xyz_to_xy, Matrix3, D50_XY, D50_XYZ,
+};
+
+const CAT02: Matrix3 = [
+    [0.7328, 0.4296, -0.1624],
+    [-0.7036, 1.6975, 0.0061],
+    [0.0030, 0.0136, 0.9834],
+];
+
+const HUNT_POINTER_ESTEVEZ: Matrix3 = [
+    [0.38971, 0.68898, -0.07868],
+    [-0.22981, 1.18340, 0.04641],
+    [0.0, 0.0, 1.0],
+];
+
+// ICC PCS reference viewing : 500 lx, i.e. a white of 500 / π cd/m²
+const PCS_WHITE_LUMINANCE: f64 = 500.0 / std::f64::consts::PI;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Surround {
+    Average,
+    Dim,
+    Dark,
+}
+
+impl Surround {
+    // Surround ratio of the surround to the adopted white luminance (CIE 159)
+    pub fn from_ratio(ratio: f64) -> Self {
+        if ratio >= 0.2 {
+            Surround::Average
+        } else if ratio > 0.0 {
+            Surround::Dim
+        } else {
+            Surround::Dark
+        }
+    }
+
+    // F, c and Nc
+    fn factors(&self) -> (f64, f64, f64) {
+        match self {
+            Surround::Average => (1.0, 0.69, 1.0),
+            Surround::Dim => (0.9, 0.59, 0.9),
+            Surround::Dark => (0.8, 0.525, 0.8),
+        }
+    }
+}
+
+// CIECAM02 viewing parameters
+pub struct AppearanceConditions {
+    pub white: [f64; 3],         // Adopted white XYZ, Y = 100
+    pub adapting_luminance: f64, // La in cd/m²
+    pub background: f64,         // Yb relative to the white
+    pub surround: Surround,
+}
+
+impl AppearanceConditions {
+    // Viewing conditions of the view tag, the measurement illuminant or the ICC reference
+    // viewing (D50, 500 lx, average surround), with a 20 % gray background
+    pub fn new(view: Option<&ViewingConditions>, meas: Option<&Measurement>) -> Self {
+        let illuminant_type = view
+            .and_then(|view| view.illuminant_type.white_xy())
+            .or_else(|| meas.and_then(|meas| meas.illuminant.white_xy()));
+
+        let (white_xy, luminance, surround) = match view {
+            Some(view) if view.illuminant[1] > 0.0 => (
+                xyz_to_xy(&view.illuminant),
+                view.illuminant[1],
+                Surround::from_ratio(view.surround[1] / view.illuminant[1]),
+            ),
+            _ => (
+                illuminant_type.unwrap_or(D50_XY),
+                PCS_WHITE_LUMINANCE,
+                Surround::Average,
+            ),
+        };
+
+        AppearanceConditions {
+            white: xy_to_xyz(white_xy).map(|val| val * 100.0),
+            adapting_luminance: luminance * 0.2,
+            background: 20.0,
+            surround,
+        }
+    }
+
+    // Lightness J, chroma C and hue angle h of XYZ values relative to the adopted white (Y = 100)
+    pub fn jch(&self, xyz: &[f64; 3]) -> [f64; 3] {
+        let (f, c, nc) = self.surround.factors();
+        let la = self.adapting_luminance;
+        let white_y = self.white[1];
+
+        let degree = (f * (1.0 - (1.0 / 3.6) * ((-la - 42.0) / 92.0).exp())).clamp(0.0, 1.0);
+        let k = 1.0 / (5.0 * la + 1.0);
+        let fl = 0.2 * k.powi(4) * 5.0 * la + 0.1 * (1.0 - k.powi(4)).powi(2) * (5.0 * la).cbrt();
+        let n = self.background / white_y;
+        let nbb = 0.725 * n.powf(-0.2);
+        let z = 1.48 + n.sqrt();
+
+        let cat02_white = mat_vec(&CAT02, &self.white);
+        let hpe = match mat_inv(&CAT02) {
+            Ok(cat02_inv) => mat_mul(&HUNT_POINTER_ESTEVEZ, &cat02_inv),
+            Err(_) => return [0.0; 3],
+        };
+        let adapted = |xyz: &[f64; 3]| {
+            let cone = mat_vec(&CAT02, xyz);
+            let cone: [f64; 3] = std::array::from_fn(|chan| {
+                (white_y * degree / cat02_white[chan] + 1.0 - degree) * cone[chan]
+            });
+            mat_vec(&hpe, &cone).map(|val| {
+                let scaled = (fl * val.abs() / 100.0).powf(0.42);
+                val.signum() * 400.0 * scaled / (27.13 + scaled) + 0.1
+            })
+        };
+        let achromatic = |rgb: &[f64; 3]| (2.0 * rgb[0] + rgb[1] + rgb[2] / 20.0 - 0.305) * nbb;
+
+        let rgb = adapted(xyz);
+        let a = rgb[0] - 12.0 * rgb[1] / 11.0 + rgb[2] / 11.0;
+        let b = (rgb[0] + rgb[1] - 2.0 * rgb[2]) / 9.0;
+        let hue = b.atan2(a).to_degrees().rem_euclid(360.0);
+
+        let lightness = 100.0
+            * (achromatic(&rgb) / achromatic(&adapted(&self.white)))
+                .max(0.0)
+                .powf(c * z);
+        let eccentricity = 0.25 * ((hue.to_radians() + 2.0).cos() + 3.8);
+        let t = (50000.0 / 13.0 * nc * nbb * eccentricity * a.hypot(b))
+            / (rgb[0] + rgb[1] + 21.0 / 20.0 * rgb[2]);
+        let chroma = t.powf(0.9) * (lightness / 100.0).sqrt() * (1.64 - 0.29f64.powf(n)).powf(0.73);
+
+        [lightness, chroma, hue]
+    }
+}
+
+impl Profile {
+    pub fn measurement(&self) -> Option<&Measurement> {
+        match self.tag("meas") {
+            Some(TagData::Meas(meas)) => Some(meas),
+            _ => None,
+        }
+    }
+
+    pub fn viewing_conditions(&self) -> Option<&ViewingConditions> {
+        match self.tag("view") {
+            Some(TagData::View(view)) => Some(view),
+            _ => None,
+        }
+    }
+
+    pub fn appearance_conditions(&self) -> AppearanceConditions {
+        AppearanceConditions::new(self.viewing_conditions(), self.measurement())
+    }
+
+    // CIECAM02 JCh of a media-relative PCS XYZ (D50, Y = 1), seen in the profile
+    // viewing conditions : made ICC-absolute, then adapted from D50 to the adopted white
+    pub fn pcs_to_jch(&self, relative: &[f64; 3]) -> Result<[f64; 3], String> {
+        let conditions = self.appearance_conditions();
+        let white = conditions.white.map(|val| val / 100.0);
+        let adaptation = bradford_adaptation(&D50_XYZ, &white)?;
+        let absolute = mat_vec(&adaptation, &self.to_absolute(relative));
+
+        Ok(conditions.jch(&absolute.map(|val| val * 100.0)))
+    }
+}
+
+impl Display for AppearanceConditions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (x, y) = xyz_to_xy(&self.white);
+        write!(
+            f,
+            "      CIECAM02 : adopted white xy {:.4} {:.4}, La {:.1} cd/m², Yb {:.0}, {:?} surround",
+            x, y, self.adapting_luminance, self.background, self.surround
+        )
+    }
+}
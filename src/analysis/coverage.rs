@@ -0,0 +1,137 @@
+use std::fmt::Display;
+
+use crate::types::profile::Profile;
+use crate::utils::color::xy_to_uv_prime;
+use crate::utils::geometry::{clip_polygon, polygon_area, Point2};
+use crate::utils::icc::{ChrmPrimaries, ADOBE_RGB_PRIMARIES, ICC_CHRM_TYPE};
+
+// Color spaces the display gamut is compared to
+pub const COVERAGE_REFERENCES: [ChrmPrimaries; 4] = [
+    (
+        "sRGB",
+        ICC_CHRM_TYPE[0].1,
+        ICC_CHRM_TYPE[0].2,
+        ICC_CHRM_TYPE[0].3,
+    ),
+    (
+        "Adobe RGB",
+        ADOBE_RGB_PRIMARIES.1,
+        ADOBE_RGB_PRIMARIES.2,
+        ADOBE_RGB_PRIMARIES.3,
+    ),
+    (
+        "DCI-P3",
+        ICC_CHRM_TYPE[4].1,
+        ICC_CHRM_TYPE[4].2,
+        ICC_CHRM_TYPE[4].3,
+    ),
+    (
+        "Rec.2020",
+        ICC_CHRM_TYPE[5].1,
+        ICC_CHRM_TYPE[5].2,
+        ICC_CHRM_TYPE[5].3,
+    ),
+];
+
+// Gamut triangle against a reference one, in a chromaticity diagram
+#[derive(Clone, Copy, Debug)]
+pub struct AreaComparison {
+    pub area: f64,     // Gamut area relative to the reference area
+    pub coverage: f64, // Part of the reference area inside the gamut
+}
+
+impl AreaComparison {
+    pub fn compare(gamut: &[Point2], reference: &[Point2]) -> Self {
+        let reference_area = polygon_area(reference);
+        AreaComparison {
+            area: polygon_area(gamut) / reference_area,
+            coverage: polygon_area(&clip_polygon(reference, gamut)) / reference_area,
+        }
+    }
+}
+
+pub struct GamutCoverage {
+    pub reference: &'static str,
+    pub xy: AreaComparison,
+    pub uv: AreaComparison,
+}
+
+// Native primaries and white point of a matrix/TRC display profile, with its gamut coverage
+pub struct PrimariesReport {
+    pub primaries: [(f64, f64); 3],
+    pub white: (f64, f64),
+    pub coverages: Vec<GamutCoverage>,
+}
+
+pub fn gamut_coverage(primaries: &[(f64, f64); 3]) -> Vec<GamutCoverage> {
+    let gamut_uv = primaries.map(xy_to_uv_prime);
+    COVERAGE_REFERENCES
+        .iter()
+        .map(|(reference, red, green, blue)| {
+            let reference_xy = [*red, *green, *blue];
+            GamutCoverage {
+                reference,
+                xy: AreaComparison::compare(primaries, &reference_xy),
+                uv: AreaComparison::compare(&gamut_uv, &reference_xy.map(xy_to_uv_prime)),
+            }
+        })
+        .collect()
+}
+
+impl Profile {
+    pub fn primaries_report(&self) -> Result<PrimariesReport, String> {
+        let primaries = self
+            .colorant_primaries()
+            .ok_or("No rXYZ, gXYZ and bXYZ colorants in the profile")?;
+        Ok(PrimariesReport {
+            primaries,
+            white: self.native_white(),
+            coverages: gamut_coverage(&primaries),
+        })
+    }
+}
+
+impl Display for PrimariesReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let uv = self.primaries.map(xy_to_uv_prime);
+        let white_uv = xy_to_uv_prime(self.white);
+        for (name, (xy, uv)) in ["Red", "Green", "Blue"]
+            .iter()
+            .zip(self.primaries.iter().zip(uv))
+        {
+            writeln!(
+                f,
+                "    {} : xy {:.4} {:.4}, u′v′ {:.4} {:.4}",
+                name, xy.0, xy.1, uv.0, uv.1
+            )?;
+        }
+        writeln!(
+            f,
+            "    White point : xy {:.4} {:.4}, u′v′ {:.4} {:.4}",
+            self.white.0, self.white.1, white_uv.0, white_uv.1
+        )?;
+        writeln!(
+            f,
+            "    Gamut area : xy {:.4}, u′v′ {:.4}",
+            polygon_area(&self.primaries),
+            polygon_area(&uv)
+        )?;
+        write!(
+            f,
+            "    {:<10}  {:>8}  {:>8}  {:>8}  {:>8}",
+            "Reference", "xy area", "coverage", "u′v′ area", "coverage"
+        )?;
+        for coverage in &self.coverages {
+            write!(
+                f,
+                "\n    {:<10}  {:>6.1} %  {:>6.1} %  {:>6.1} %  {:>6.1} %",
+                coverage.reference,
+                coverage.xy.area * 100.0,
+                coverage.xy.coverage * 100.0,
+                coverage.uv.area * 100.0,
+                coverage.uv.coverage * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
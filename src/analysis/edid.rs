@@ -0,0 +1,184 @@
+use std::fmt::Display;
+
+use crate::analysis::metadata::Metadata;
+use crate::analysis::primaries::delta_uv;
+use crate::parsers::edid::find_edid;
+use crate::types::edid::Edid;
+use crate::types::profile::{Profile, TagData};
+
+// Largest Δu′v′ between an EDID primary and the profile one for a profile of the same display
+pub const EDID_PRIMARIES_TOLERANCE: f64 = 0.03;
+
+const PRIMARY_NAMES: [&str; 3] = ["red", "green", "blue"];
+
+// Δu′v′ of the red, green and blue primaries of the profile against the EDID ones
+pub struct PrimariesComparison {
+    pub source: &'static str,
+    pub deltas: [f64; 3],
+}
+
+impl PrimariesComparison {
+    pub fn compare(
+        source: &'static str,
+        edid: &[(f64, f64); 3],
+        profile: &[(f64, f64); 3],
+    ) -> Self {
+        PrimariesComparison {
+            source,
+            deltas: std::array::from_fn(|chan| delta_uv(edid[chan], profile[chan])),
+        }
+    }
+
+    // Index and Δu′v′ of the farthest primary
+    pub fn max(&self) -> (usize, f64) {
+        self.deltas
+            .iter()
+            .copied()
+            .enumerate()
+            .fold(
+                (0, 0.0),
+                |max, (chan, delta)| if delta > max.1 { (chan, delta) } else { max },
+            )
+    }
+
+    pub fn mismatch(&self) -> bool {
+        self.max().1 > EDID_PRIMARIES_TOLERANCE
+    }
+
+    pub fn warning(&self) -> Option<String> {
+        let (chan, delta) = self.max();
+        self.mismatch().then(|| {
+            format!(
+                "! {} primaries differ from the EDID ones (Δu′v′ {:.4} for {}) : the profile was likely made for a different display",
+                self.source, delta, PRIMARY_NAMES[chan]
+            )
+        })
+    }
+}
+
+impl Display for PrimariesComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "      Δu′v′ against {} : R {:.4}, G {:.4}, B {:.4}",
+            self.source, self.deltas[0], self.deltas[1], self.deltas[2]
+        )?;
+        if let Some(warning) = self.warning() {
+            write!(f, "\n      {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+impl Metadata {
+    fn get_xy(&self, color: &str) -> Option<(f64, f64)> {
+        let x = self.get(&format!("EDID_{}_x", color))?.parse().ok()?;
+        let y = self.get(&format!("EDID_{}_y", color))?.parse().ok()?;
+        Some((x, y))
+    }
+
+    // OpenICC EDID_ keys : EDID_mnft, EDID_model_id, EDID_date ("2019-T40"), EDID_red_x...
+    pub fn edid(&self) -> Option<Edid> {
+        if self.namespace("EDID_").is_empty() {
+            return None;
+        }
+
+        let product_code =
+            self.get("EDID_model_id")
+                .and_then(|code| match code.strip_prefix("0x") {
+                    Some(hex) => u16::from_str_radix(hex, 16).ok(),
+                    None => code.parse().ok(),
+                });
+        let (year, week) = match self.get("EDID_date") {
+            Some(date) => {
+                let mut parts = date.splitn(2, '-');
+                let year = parts.next().and_then(|year| year.trim().parse().ok());
+                let week = parts
+                    .next()
+                    .map(|week| week.trim_start_matches(['T', 'W']))
+                    .and_then(|week| week.parse().ok());
+                (year, week)
+            }
+            None => (None, None),
+        };
+        let primaries = match (
+            self.get_xy("red"),
+            self.get_xy("green"),
+            self.get_xy("blue"),
+        ) {
+            (Some(red), Some(green), Some(blue)) => Some([red, green, blue]),
+            _ => None,
+        };
+
+        Some(Edid {
+            manufacturer_id: self
+                .get("EDID_mnft")
+                .or_else(|| self.edid_manufacturer())
+                .unwrap_or("Unknown")
+                .to_string(),
+            product_code,
+            serial_number: None,
+            serial: self.edid_serial().map(|serial| serial.to_string()),
+            model: self.edid_model().map(|model| model.to_string()),
+            week,
+            year,
+            gamma: self.get("EDID_gamma").and_then(|gamma| gamma.parse().ok()),
+            primaries,
+            white: self.get_xy("white"),
+            checksum_ok: true,
+        })
+    }
+}
+
+impl Profile {
+    // EDID block stored in the meta dict or a private tag, the EDID_ keys of the meta dict otherwise
+    pub fn edid(&self) -> Option<Edid> {
+        let metadata = self.metadata();
+
+        let in_meta = metadata.as_ref().and_then(|metadata| {
+            metadata
+                .records
+                .iter()
+                .filter_map(|record| record.value.as_deref())
+                .find_map(|value| find_edid(value.as_bytes()))
+        });
+        let in_tags = || {
+            self.tags.iter().find_map(|(_, data)| match data {
+                TagData::Data(data) => find_edid(&data.data),
+                TagData::Text(text) => find_edid(text.as_bytes()),
+                TagData::Unknown(raw) if raw.len() > 8 => find_edid(&raw[8..]),
+                _ => None,
+            })
+        };
+
+        in_meta
+            .or_else(in_tags)
+            .or_else(|| metadata.and_then(|metadata| metadata.edid()))
+    }
+
+    // EDID primaries against the colorants and the chrm tag of the profile
+    pub fn edid_comparisons(&self, edid: &Edid) -> Vec<PrimariesComparison> {
+        let Some(edid_primaries) = edid.primaries else {
+            return vec![];
+        };
+
+        let mut comparisons = vec![];
+        if let Some(colorants) = self.colorant_primaries() {
+            comparisons.push(PrimariesComparison::compare(
+                "rXYZ/gXYZ/bXYZ",
+                &edid_primaries,
+                &colorants,
+            ));
+        }
+        if let Some(TagData::Chrm(chrm)) = self.tag("chrm") {
+            if let Some(chrm_primaries) = chrm.rgb_primaries() {
+                comparisons.push(PrimariesComparison::compare(
+                    "chrm",
+                    &edid_primaries,
+                    &chrm_primaries,
+                ));
+            }
+        }
+        comparisons
+    }
+}
@@ -0,0 +1,28 @@
+use crate::analysis::transform::{encode_pcs, eval_lut};
+use crate::types::profile::{Profile, TagData};
+use crate::utils::bytes::sig_to_str;
+use crate::utils::color::{lab_to_xyz, D50_XYZ};
+
+impl Profile {
+    // Output of the gamt tag for a PCS XYZ (D50, Y = 1) : 0 in gamut, up to 1 out of gamut
+    pub fn gamut_value(&self, xyz: &[f64; 3]) -> Result<f64, String> {
+        let gamt = match self.tag("gamt") {
+            Some(gamt @ (TagData::Lut8(..) | TagData::Lut16(..) | TagData::LutAB(..))) => gamt,
+            Some(..) => return Err("Tag gamt is not a lut8, lut16 or lutBtoA".to_string()),
+            None => return Err("No gamt tag in the profile".to_string()),
+        };
+        let pcs_lab = sig_to_str(self.header.pcs) == "Lab ";
+        let output = eval_lut(gamt, &encode_pcs(gamt, xyz, pcs_lab))?;
+
+        match output.first() {
+            Some(value) => Ok(value.clamp(0.0, 1.0)),
+            None => Err("gamt table has no output channel".to_string()),
+        }
+    }
+
+    // Whether a D50 L*a*b* color is printable. Interpolated outputs count as in gamut
+    // while closer to 0 than to 1.
+    pub fn in_gamut(&self, lab: &[f64; 3]) -> Result<bool, String> {
+        Ok(self.gamut_value(&lab_to_xyz(lab, &D50_XYZ))? < 0.5)
+    }
+}
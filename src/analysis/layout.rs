@@ -0,0 +1,276 @@
+use std::fmt::Display;
+
+use crate::parsers::tags::read_tag_table;
+use crate::utils::bytes::{bytes_u32_usize, sig_to_str};
+
+const MAP_WIDTH: usize = 64;
+const MAP_MAX_ROWS: usize = 32;
+const MAP_SYMBOLS: &[u8] = b"ABCDEFGIJKLMNOPQRSUVWYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+pub struct DataBlock {
+    pub offset: usize,
+    pub size: usize,
+    pub tags: Vec<u32>, // Tags sharing this data block
+}
+
+pub enum GapKind {
+    Padding, // Less than 4 zero bytes before an aligned block
+    Unused,
+}
+
+pub struct Gap {
+    pub offset: usize,
+    pub size: usize,
+    pub kind: GapKind,
+}
+
+pub struct TagLayout {
+    pub file_size: usize,
+    pub pf_size: usize,
+    pub tag_table_end: usize,
+    pub blocks: Vec<DataBlock>,           // Sorted by offset
+    pub overlaps: Vec<(u32, u32, usize)>, // (tag, other tag, overlapped bytes)
+    pub gaps: Vec<Gap>,
+    pub beyond_pf_size: Vec<u32>, // Tags ending after the header profile size
+    pub beyond_file: Vec<u32>,    // Tags ending after the end of file
+}
+
+pub fn tags_str(tags: &[u32]) -> String {
+    tags.iter()
+        .map(|tag| sig_to_str(*tag))
+        .collect::<Vec<String>>()
+        .join(" = ")
+}
+
+pub fn analyze_layout(icc_raw_data: &[u8]) -> Result<TagLayout, String> {
+    let tag_table = read_tag_table(icc_raw_data)?;
+    let pf_size = bytes_u32_usize(&icc_raw_data[0..=3])?;
+    let tag_table_end = 132 + 12 * tag_table.len();
+
+    let mut blocks: Vec<DataBlock> = vec![];
+    let mut beyond_pf_size: Vec<u32> = vec![];
+    let mut beyond_file: Vec<u32> = vec![];
+
+    for (tag_sig, offset, size) in tag_table.iter() {
+        if offset + size > icc_raw_data.len() {
+            beyond_file.push(*tag_sig);
+        }
+        if offset + size > pf_size {
+            beyond_pf_size.push(*tag_sig);
+        }
+
+        match blocks
+            .iter_mut()
+            .find(|block| block.offset == *offset && block.size == *size)
+        {
+            Some(block) => block.tags.push(*tag_sig),
+            None => blocks.push(DataBlock {
+                offset: *offset,
+                size: *size,
+                tags: vec![*tag_sig],
+            }),
+        }
+    }
+
+    blocks.sort_by_key(|block| (block.offset, block.size));
+
+    let mut overlaps: Vec<(u32, u32, usize)> = vec![];
+    for (block_idx, block) in blocks.iter().enumerate() {
+        for other in blocks[(block_idx + 1)..].iter() {
+            let start = block.offset.max(other.offset);
+            let end = (block.offset + block.size).min(other.offset + other.size);
+            if start < end {
+                overlaps.push((block.tags[0], other.tags[0], end - start));
+            }
+        }
+    }
+
+    // Bytes covered neither by the header, the tag table nor any tag data
+    let mut gaps: Vec<Gap> = vec![];
+    let mut covered_end = tag_table_end;
+    let data_end = pf_size.min(icc_raw_data.len());
+    for block in blocks.iter() {
+        if block.offset > covered_end {
+            gaps.push(Gap {
+                offset: covered_end,
+                size: block.offset - covered_end,
+                kind: GapKind::Unused,
+            });
+        }
+        covered_end = covered_end.max(block.offset + block.size);
+    }
+    if data_end > covered_end {
+        gaps.push(Gap {
+            offset: covered_end,
+            size: data_end - covered_end,
+            kind: GapKind::Unused,
+        });
+    }
+    for gap in gaps.iter_mut() {
+        let gap_end = gap.offset + gap.size;
+        // Gaps between tags pointing beyond the end of file have no bytes to check
+        let zeroed = icc_raw_data
+            .get(gap.offset..gap_end.min(icc_raw_data.len()))
+            .is_some_and(|bytes| bytes.iter().all(|byte| *byte == 0));
+        if gap.size < 4 && gap_end.is_multiple_of(4) && zeroed {
+            gap.kind = GapKind::Padding;
+        }
+    }
+
+    Ok(TagLayout {
+        file_size: icc_raw_data.len(),
+        pf_size,
+        tag_table_end,
+        blocks,
+        overlaps,
+        gaps,
+        beyond_pf_size,
+        beyond_file,
+    })
+}
+
+impl TagLayout {
+    pub fn shared_blocks(&self) -> impl Iterator<Item = &DataBlock> {
+        self.blocks.iter().filter(|block| block.tags.len() > 1)
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = vec![];
+        for (tag, other, size) in self.overlaps.iter() {
+            warnings.push(format!(
+                "Tag {} overlaps tag {} on {} Bytes",
+                sig_to_str(*tag),
+                sig_to_str(*other),
+                size
+            ));
+        }
+        for tag in self.beyond_pf_size.iter() {
+            warnings.push(format!(
+                "Tag {} extends beyond the header profile size",
+                sig_to_str(*tag)
+            ));
+        }
+        for tag in self.beyond_file.iter() {
+            warnings.push(format!(
+                "Tag {} extends beyond the end of file",
+                sig_to_str(*tag)
+            ));
+        }
+        warnings
+    }
+
+    pub fn unused_bytes(&self) -> usize {
+        self.gaps
+            .iter()
+            .filter(|gap| matches!(gap.kind, GapKind::Unused))
+            .map(|gap| gap.size)
+            .sum()
+    }
+
+    // One symbol per data block, 'H' header, 'T' tag table, '.' unused, 'X' illegal overlap
+    pub fn byte_map(&self) -> String {
+        let map_size = self.file_size.max(self.pf_size);
+        let cells_num = MAP_WIDTH * MAP_MAX_ROWS;
+        let cell_size = map_size.div_ceil(cells_num).max(1);
+        let mut map = String::new();
+
+        for cell in 0..map_size.div_ceil(cell_size) {
+            let start = cell * cell_size;
+            let end = start + cell_size;
+
+            let mut symbols: Vec<char> = vec![];
+            if start < 128 {
+                symbols.push('H');
+            }
+            if start < self.tag_table_end && end > 128 {
+                symbols.push('T');
+            }
+            for (block_idx, block) in self.blocks.iter().enumerate() {
+                if start < block.offset + block.size && block.offset < end {
+                    symbols.push(MAP_SYMBOLS[block_idx % MAP_SYMBOLS.len()] as char);
+                }
+            }
+
+            let overlapped = self.blocks.iter().enumerate().any(|(block_idx, block)| {
+                self.blocks[(block_idx + 1)..].iter().any(|other| {
+                    let ov_start = block.offset.max(other.offset).max(start);
+                    let ov_end = (block.offset + block.size)
+                        .min(other.offset + other.size)
+                        .min(end);
+                    ov_start < ov_end
+                })
+            });
+
+            let symbol = match symbols.first() {
+                _ if overlapped => 'X',
+                Some(symbol) => *symbol,
+                None if start >= self.file_size => '!',
+                None => '.',
+            };
+            map.push(symbol);
+
+            if cell % MAP_WIDTH == MAP_WIDTH - 1 {
+                map.push('\n');
+            }
+        }
+
+        format!(
+            "      [1 symbol = {} Bytes]\n{}",
+            cell_size,
+            map.trim_end()
+                .lines()
+                .map(|line| format!("      {}", line))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+}
+
+impl Display for TagLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "    File size : {} Bytes, header profile size : {} Bytes",
+            self.file_size, self.pf_size
+        )?;
+        writeln!(f, "    Tag data blocks :")?;
+        for (block_idx, block) in self.blocks.iter().enumerate() {
+            let shared = if block.tags.len() > 1 {
+                " (shared)"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "      {} {:>8} .. {:<8} {:>7} Bytes  {}{}",
+                MAP_SYMBOLS[block_idx % MAP_SYMBOLS.len()] as char,
+                block.offset,
+                block.offset + block.size,
+                block.size,
+                tags_str(&block.tags),
+                shared
+            )?;
+        }
+
+        for warning in self.warnings() {
+            writeln!(f, "    ! {}", warning)?;
+        }
+        for gap in self.gaps.iter() {
+            let kind = match gap.kind {
+                GapKind::Padding => "padding",
+                GapKind::Unused => "unused",
+            };
+            writeln!(
+                f,
+                "    Gap {:>8} .. {:<8} {:>7} Bytes  {}",
+                gap.offset,
+                gap.offset + gap.size,
+                gap.size,
+                kind
+            )?;
+        }
+        writeln!(f, "    Unused bytes : {}", self.unused_bytes())?;
+        writeln!(f, "    Byte map :")?;
+        write!(f, "{}", self.byte_map())
+    }
+}
@@ -0,0 +1,146 @@
+use crate::parsers::icc_types::decode_dict_entries;
+use crate::types::icc::DictRecord;
+use crate::types::profile::{Profile, TagData};
+
+// Key namespaces written by DisplayCAL and ArgyllCMS in the meta dict
+pub const META_NAMESPACES: [(&str, &str); 7] = [
+    ("CMF_", "Color management framework which made the profile"),
+    ("MEASUREMENT_", "Measurement instrument"),
+    ("EDID_", "Display identification (EDID)"),
+    ("OPENICC_", "OpenICC"),
+    ("ACCURACY_", "Profile self check"),
+    ("GAMUT_", "Gamut volume and coverage"),
+    ("ARGYLL_", "ArgyllCMS settings"),
+];
+
+// Typed view over the entries of the meta dict
+pub struct Metadata {
+    pub records: Vec<DictRecord>,
+}
+
+impl Metadata {
+    pub fn new(records: Vec<DictRecord>) -> Self {
+        Metadata { records }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.records
+            .iter()
+            .find(|record| record.name == key)
+            .and_then(|record| record.value.as_deref())
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(|value| value.parse::<f64>().ok())
+    }
+
+    // Keys of a namespace without their prefix, with their values
+    pub fn namespace(&self, prefix: &str) -> Vec<(&str, &str)> {
+        self.records
+            .iter()
+            .filter_map(|record| {
+                let key = record.name.strip_prefix(prefix)?;
+                Some((key, record.value.as_deref().unwrap_or_default()))
+            })
+            .collect()
+    }
+
+    // Namespaces announced by the OpenICC "prefix" key, the known ones found otherwise
+    pub fn prefixes(&self) -> Vec<String> {
+        match self.get("prefix") {
+            Some(prefixes) => prefixes
+                .split(',')
+                .map(|prefix| prefix.trim().to_string())
+                .filter(|prefix| !prefix.is_empty())
+                .collect(),
+            None => META_NAMESPACES
+                .iter()
+                .filter(|(prefix, _)| !self.namespace(prefix).is_empty())
+                .map(|(prefix, _)| prefix.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn product(&self) -> Option<&str> {
+        self.get("CMF_product")
+    }
+
+    pub fn product_version(&self) -> Option<&str> {
+        self.get("CMF_version")
+    }
+
+    pub fn measurement_device(&self) -> Option<&str> {
+        self.get("MEASUREMENT_device")
+    }
+
+    pub fn automatic_generated(&self) -> Option<bool> {
+        self.get("OPENICC_automatic_generated")
+            .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+    }
+
+    pub fn edid_manufacturer(&self) -> Option<&str> {
+        self.get("EDID_manufacturer")
+            .or_else(|| self.get("EDID_mnft"))
+    }
+
+    pub fn edid_model(&self) -> Option<&str> {
+        self.get("EDID_model")
+    }
+
+    pub fn edid_serial(&self) -> Option<&str> {
+        self.get("EDID_serial")
+    }
+
+    pub fn accuracy_de76_avg(&self) -> Option<f64> {
+        self.get_f64("ACCURACY_dE76_avg")
+    }
+
+    pub fn accuracy_de76_max(&self) -> Option<f64> {
+        self.get_f64("ACCURACY_dE76_max")
+    }
+
+    pub fn accuracy_de76_rms(&self) -> Option<f64> {
+        self.get_f64("ACCURACY_dE76_rms")
+    }
+
+    // "Made with DisplayCAL 3.8, measured with i1 DisplayPro, EDID serial …, avg ΔE 0.31"
+    pub fn summary(&self) -> Option<String> {
+        let mut parts: Vec<String> = vec![];
+
+        if let Some(product) = self.product() {
+            match self.product_version() {
+                Some(version) => parts.push(format!("made with {} {}", product, version)),
+                None => parts.push(format!("made with {}", product)),
+            }
+        }
+        if self.automatic_generated() == Some(true) {
+            parts.push("automatically generated".to_string());
+        }
+        if let Some(device) = self.measurement_device() {
+            parts.push(format!("measured with {}", device));
+        }
+        if let Some(serial) = self.edid_serial() {
+            parts.push(format!("EDID serial {}", serial));
+        }
+        if let Some(avg) = self.accuracy_de76_avg() {
+            parts.push(format!("avg ΔE {:.2}", avg));
+        }
+
+        let summary = parts.join(", ");
+        let mut chars = summary.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+    }
+}
+
+impl Profile {
+    pub fn metadata(&self) -> Option<Metadata> {
+        match self.tag("meta") {
+            Some(TagData::Dict(dict)) => Some(Metadata::new(decode_dict_entries(dict))),
+            _ => None,
+        }
+    }
+}
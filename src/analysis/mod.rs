@@ -0,0 +1,11 @@
+pub mod accuracy;
+pub mod appearance;
+pub mod coverage;
+pub mod edid;
+pub mod gamut;
+pub mod layout;
+pub mod metadata;
+pub mod primaries;
+pub mod transform;
+pub mod validate;
+pub mod volume;
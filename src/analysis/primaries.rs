@@ -0,0 +1,88 @@
+use crate::types::profile::{Profile, TagData};
+use crate::utils::color::{
+    bradford_adaptation, mat_inv, mat_vec, xy_to_uv_prime, xyz_number_to_f64, xyz_to_xy, Matrix3,
+    D50_XYZ,
+};
+use crate::utils::icc::{ICC_CHRM_TYPE, RGB_PRIMARIES};
+
+// Distance between two chromaticities in the CIE 1976 u′v′ diagram
+pub fn delta_uv(xy1: (f64, f64), xy2: (f64, f64)) -> f64 {
+    let (u1, v1) = xy_to_uv_prime(xy1);
+    let (u2, v2) = xy_to_uv_prime(xy2);
+    ((u1 - u2).powi(2) + (v1 - v2).powi(2)).sqrt()
+}
+
+fn is_d50(xyz: &[f64; 3]) -> bool {
+    xyz.iter()
+        .zip(D50_XYZ)
+        .all(|(value, d50)| (value - d50).abs() < 0.001)
+}
+
+fn largest(deltas: &[f64; 3]) -> f64 {
+    deltas.iter().fold(0.0, |max, delta| f64::max(max, *delta))
+}
+
+// Standard primaries with the smallest largest Δu′v′ to the given ones, and the Δu′v′ of each
+pub fn nearest_standard(primaries: &[(f64, f64); 3]) -> (&'static str, [f64; 3]) {
+    ICC_CHRM_TYPE
+        .iter()
+        .chain(RGB_PRIMARIES.iter())
+        .map(|(name, red, green, blue)| {
+            let standard = [*red, *green, *blue];
+            let deltas = std::array::from_fn(|chan| delta_uv(primaries[chan], standard[chan]));
+            (*name, deltas)
+        })
+        .min_by(|(_, a), (_, b)| largest(a).total_cmp(&largest(b)))
+        .unwrap_or(("Unknown", [0.0; 3]))
+}
+
+impl Profile {
+    // Adaptation of the device colors to the D50 PCS : the chad tag, or for v2 profiles
+    // without one, Bradford from a media white point which is not D50
+    pub fn chromatic_adaptation(&self) -> Option<Matrix3> {
+        if let Some(TagData::Sf32(values)) = self.tag("chad") {
+            if values.len() == 9 {
+                return Some(std::array::from_fn(|row| {
+                    std::array::from_fn(|col| values[3 * row + col].to_num::<f64>())
+                }));
+            }
+        }
+
+        let white = self.media_white();
+        if is_d50(&white) {
+            return None;
+        }
+        bradford_adaptation(&white, &D50_XYZ).ok()
+    }
+
+    // Native white point : a D50 wtpt is the adapted white of v4 profiles, chad undone,
+    // any other wtpt is the measured white of v2 profiles
+    pub fn native_white(&self) -> (f64, f64) {
+        let white = self.media_white();
+        match self.chromatic_adaptation().map(|chad| mat_inv(&chad)) {
+            Some(Ok(undo)) if is_d50(&white) => xyz_to_xy(&mat_vec(&undo, &white)),
+            _ => xyz_to_xy(&white),
+        }
+    }
+
+    // Native xy of the rXYZ, gXYZ and bXYZ colorants, chromatic adaptation undone
+    pub fn colorant_primaries(&self) -> Option<[(f64, f64); 3]> {
+        let undo = match self.chromatic_adaptation() {
+            Some(chad) => Some(mat_inv(&chad).ok()?),
+            None => None,
+        };
+
+        let mut primaries = [(0.0, 0.0); 3];
+        for (primary, sig) in primaries.iter_mut().zip(["rXYZ", "gXYZ", "bXYZ"]) {
+            let Some(TagData::XYZ(xyz)) = self.tag(sig) else {
+                return None;
+            };
+            let xyz = xyz_number_to_f64(xyz);
+            *primary = match &undo {
+                Some(undo) => xyz_to_xy(&mat_vec(undo, &xyz)),
+                None => xyz_to_xy(&xyz),
+            };
+        }
+        Some(primaries)
+    }
+}
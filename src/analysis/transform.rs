@@ -0,0 +1,542 @@
+use crate::types::icc::{Curve, Lut16, Lut8, LutAB, LutCurve, ParametricCurve, S15Fixed16Number};
+use crate::types::profile::{Profile, TagData};
+use crate::utils::bytes::{sig_to_str, str_to_sig};
+use crate::utils::color::{
+    lab_to_xyz, mat_inv, mat_vec, xyz_number_to_f64, xyz_to_lab, Matrix3, D50_XYZ,
+};
+
+// Device values (0..1) to PCS XYZ relative to D50 (Y = 1 for the media white)
+pub enum PcsTransform<'a> {
+    MatrixTrc {
+        matrix: Matrix3, // Columns are rXYZ, gXYZ and bXYZ
+        curves: [&'a TagData; 3],
+    },
+    GrayTrc(&'a TagData),
+    Lut {
+        tag: u32,
+        lut: &'a TagData,
+        pcs_lab: bool,
+    },
+}
+
+// Linear interpolation in a table of normalized values
+fn interpolate(table: &[f64], x: f64) -> f64 {
+    match table.len() {
+        0 => x,
+        1 => table[0],
+        len => {
+            let pos = x.clamp(0.0, 1.0) * (len - 1) as f64;
+            let idx = (pos.floor() as usize).min(len - 2);
+            let frac = pos - idx as f64;
+            table[idx] * (1.0 - frac) + table[idx + 1] * frac
+        }
+    }
+}
+
+fn eval_u16_table(table: &[u16], x: f64) -> f64 {
+    let table: Vec<f64> = table.iter().map(|val| *val as f64 / 65535.0).collect();
+    interpolate(&table, x)
+}
+
+pub fn eval_curve(curve: &Curve, x: f64) -> f64 {
+    let x = x.clamp(0.0, 1.0);
+    if let Some(gamma) = curve.gamma {
+        return x.powf(gamma.to_num::<f64>());
+    }
+    match &curve.curve {
+        Some(table) => eval_u16_table(table, x),
+        None => x,
+    }
+}
+
+pub fn eval_para(para: &ParametricCurve, x: f64) -> f64 {
+    let p: Vec<f64> = para.params.iter().map(|p| p.to_num::<f64>()).collect();
+    let param = |idx: usize| p.get(idx).copied().unwrap_or(0.0);
+    let (g, a, b, c, d, e, f) = (
+        param(0),
+        param(1),
+        param(2),
+        param(3),
+        param(4),
+        param(5),
+        param(6),
+    );
+    let pow = |base: f64| if base > 0.0 { base.powf(g) } else { 0.0 };
+
+    let y = match para.function_type {
+        0 => pow(x),
+        1 if x >= -b / a => pow(a * x + b),
+        1 => 0.0,
+        2 if x >= -b / a => pow(a * x + b) + c,
+        2 => c,
+        3 if x >= d => pow(a * x + b),
+        3 => c * x,
+        4 if x >= d => pow(a * x + b) + e,
+        4 => c * x + f,
+        _ => x,
+    };
+    y.clamp(0.0, 1.0)
+}
+
+pub fn eval_lut_curve(curve: &LutCurve, x: f64) -> f64 {
+    match curve {
+        LutCurve::Curv(curve) => eval_curve(curve, x),
+        LutCurve::Para(para) => eval_para(para, x),
+    }
+}
+
+fn eval_tag_curve(tag: &TagData, x: f64) -> Result<f64, String> {
+    match tag {
+        TagData::Curve(curve) => Ok(eval_curve(curve, x)),
+        TagData::Para(para) => Ok(eval_para(para, x)),
+        _ => Err("TRC tag is not a curv or para curve".to_string()),
+    }
+}
+
+// Multilinear interpolation in a CLUT whose first input channel varies slowest
+pub fn eval_clut(
+    grid_pts: &[usize],
+    table: &[u16],
+    max_val: f64,
+    out_chans: usize,
+    input: &[f64],
+) -> Result<Vec<f64>, String> {
+    let in_chans = grid_pts.len();
+    let nodes_num: usize = grid_pts.iter().product();
+    if input.len() < in_chans || table.len() < nodes_num * out_chans {
+        return Err("CLUT smaller than its grid".to_string());
+    }
+
+    let mut strides = vec![out_chans; in_chans];
+    for chan in (0..in_chans.saturating_sub(1)).rev() {
+        strides[chan] = strides[chan + 1] * grid_pts[chan + 1];
+    }
+
+    let mut base = 0;
+    let mut fracs: Vec<f64> = Vec::with_capacity(in_chans);
+    for chan in 0..in_chans {
+        if grid_pts[chan] < 2 {
+            fracs.push(0.0);
+            continue;
+        }
+        let pos = input[chan].clamp(0.0, 1.0) * (grid_pts[chan] - 1) as f64;
+        let idx = (pos.floor() as usize).min(grid_pts[chan] - 2);
+        base += idx * strides[chan];
+        fracs.push(pos - idx as f64);
+    }
+
+    let mut output = vec![0.0; out_chans];
+    for corner in 0..(1usize << in_chans) {
+        let mut weight = 1.0;
+        let mut offset = base;
+        for chan in 0..in_chans {
+            if corner & (1 << chan) != 0 {
+                if grid_pts[chan] < 2 {
+                    weight = 0.0;
+                    break;
+                }
+                weight *= fracs[chan];
+                offset += strides[chan];
+            } else {
+                weight *= 1.0 - fracs[chan];
+            }
+        }
+        if weight == 0.0 {
+            continue;
+        }
+        for (chan, out) in output.iter_mut().enumerate() {
+            *out += weight * table[offset + chan] as f64 / max_val;
+        }
+    }
+
+    Ok(output)
+}
+
+fn eval_lut_curves(curves: &[LutCurve], values: &mut [f64]) {
+    for (value, curve) in values.iter_mut().zip(curves.iter()) {
+        *value = eval_lut_curve(curve, *value);
+    }
+}
+
+// 3x3 matrix followed by the 3 offsets
+fn eval_lutab_matrix(matrix: &[S15Fixed16Number], values: &[f64]) -> Vec<f64> {
+    let e: Vec<f64> = matrix.iter().map(|e| e.to_num::<f64>()).collect();
+    (0..3)
+        .map(|row| {
+            e[row * 3] * values[0]
+                + e[row * 3 + 1] * values[1]
+                + e[row * 3 + 2] * values[2]
+                + e[9 + row]
+        })
+        .collect()
+}
+
+fn eval_lutab_clut(lut: &LutAB, values: &[f64]) -> Result<Vec<f64>, String> {
+    match &lut.clut {
+        Some(clut) => {
+            let grid_pts: Vec<usize> = clut.grid_pts.iter().map(|pts| usize::from(*pts)).collect();
+            let max_val = if clut.precision == 1 { 255.0 } else { 65535.0 };
+            eval_clut(
+                &grid_pts,
+                &clut.table,
+                max_val,
+                usize::from(lut.out_chans_num),
+                values,
+            )
+        }
+        None => Ok(values.to_vec()),
+    }
+}
+
+// mAB : A curves, CLUT, M curves, matrix and B curves.
+// mBA : the same elements in the reverse order.
+fn eval_lutab(lut: &LutAB, input: &[f64]) -> Result<Vec<f64>, String> {
+    let mut values: Vec<f64> = input.to_vec();
+
+    if lut.lut_sig == 0x6D424120 {
+        eval_lut_curves(&lut.b_curves, &mut values);
+        if let (Some(matrix), 3) = (&lut.matrix, values.len()) {
+            values = eval_lutab_matrix(matrix, &values);
+        }
+        if let Some(m_curves) = &lut.m_curves {
+            eval_lut_curves(m_curves, &mut values);
+        }
+        values = eval_lutab_clut(lut, &values)?;
+        if let Some(a_curves) = &lut.a_curves {
+            eval_lut_curves(a_curves, &mut values);
+        }
+        return Ok(values);
+    }
+
+    if let Some(a_curves) = &lut.a_curves {
+        eval_lut_curves(a_curves, &mut values);
+    }
+    values = eval_lutab_clut(lut, &values)?;
+    if let Some(m_curves) = &lut.m_curves {
+        eval_lut_curves(m_curves, &mut values);
+    }
+    if let (Some(matrix), 3) = (&lut.matrix, values.len()) {
+        values = eval_lutab_matrix(matrix, &values);
+    }
+    eval_lut_curves(&lut.b_curves, &mut values);
+
+    Ok(values)
+}
+
+// PCS XYZ (D50, Y = 1) to the normalized input of a PCS to device LUT (B2A, gamt, pre0...)
+pub fn encode_pcs(lut: &TagData, xyz: &[f64; 3], pcs_lab: bool) -> Vec<f64> {
+    if pcs_lab {
+        let lab = xyz_to_lab(xyz, &D50_XYZ);
+        // lut16 keeps the legacy 16 bits Lab encoding (0xFF00 = 100)
+        let scale = match lut {
+            TagData::Lut16(..) => 65280.0 / 65535.0,
+            _ => 1.0,
+        };
+        return vec![
+            lab[0] / 100.0 * scale,
+            (lab[1] + 128.0) / 255.0 * scale,
+            (lab[2] + 128.0) / 255.0 * scale,
+        ];
+    }
+
+    // u1Fixed15Number : 0x8000 = 1.0, lut8 and lut16 apply their matrix to XYZ inputs
+    let encoded: Vec<f64> = xyz.iter().map(|val| val * 32768.0 / 65535.0).collect();
+    match lut {
+        TagData::Lut8(Lut8 { e_params, .. }) | TagData::Lut16(Lut16 { e_params, .. })
+            if e_params.len() == 9 =>
+        {
+            let mut matrix = e_params.clone();
+            matrix.extend([S15Fixed16Number::from_num(0); 3]);
+            eval_lutab_matrix(&matrix, &encoded)
+        }
+        _ => encoded,
+    }
+}
+
+// Normalized output of a LUT (0..1 for each channel)
+pub fn eval_lut(lut: &TagData, input: &[f64]) -> Result<Vec<f64>, String> {
+    match lut {
+        TagData::Lut8(lut) => {
+            let in_chans = usize::from(lut.in_chans_num);
+            let out_chans = usize::from(lut.out_chans_num);
+            let to_f64 = |table: &[u8]| -> Vec<f64> {
+                table.iter().map(|val| *val as f64 / 255.0).collect()
+            };
+
+            let values: Vec<f64> = (0..in_chans)
+                .map(|chan| {
+                    interpolate(
+                        &to_f64(&lut.in_table[chan * 256..(chan + 1) * 256]),
+                        input.get(chan).copied().unwrap_or(0.0),
+                    )
+                })
+                .collect();
+            let clut: Vec<u16> = lut.clut_table.iter().map(|val| u16::from(*val)).collect();
+            let grid_pts = vec![usize::from(lut.grid_pts_num); in_chans];
+            let values = eval_clut(&grid_pts, &clut, 255.0, out_chans, &values)?;
+
+            Ok((0..out_chans)
+                .map(|chan| {
+                    interpolate(
+                        &to_f64(&lut.out_table[chan * 256..(chan + 1) * 256]),
+                        values[chan],
+                    )
+                })
+                .collect())
+        }
+        TagData::Lut16(lut) => {
+            let in_chans = usize::from(lut.in_chans_num);
+            let out_chans = usize::from(lut.out_chans_num);
+            let in_entries = usize::from(lut.in_table_entries_num);
+            let out_entries = usize::from(lut.out_table_entries_num);
+
+            let values: Vec<f64> = (0..in_chans)
+                .map(|chan| {
+                    eval_u16_table(
+                        &lut.in_table[chan * in_entries..(chan + 1) * in_entries],
+                        input.get(chan).copied().unwrap_or(0.0),
+                    )
+                })
+                .collect();
+            let grid_pts = vec![usize::from(lut.grid_pts_num); in_chans];
+            let values = eval_clut(&grid_pts, &lut.clut_table, 65535.0, out_chans, &values)?;
+
+            Ok((0..out_chans)
+                .map(|chan| {
+                    eval_u16_table(
+                        &lut.out_table[chan * out_entries..(chan + 1) * out_entries],
+                        values[chan],
+                    )
+                })
+                .collect())
+        }
+        TagData::LutAB(lut) => eval_lutab(lut, input),
+        _ => Err("Tag is not a lut8, lut16 or lutAtoB".to_string()),
+    }
+}
+
+impl PcsTransform<'_> {
+    pub fn name(&self) -> String {
+        match self {
+            PcsTransform::MatrixTrc { .. } => {
+                "Matrix/TRC (rXYZ, gXYZ, bXYZ, rTRC, gTRC, bTRC)".to_string()
+            }
+            PcsTransform::GrayTrc(..) => "Gray TRC (kTRC)".to_string(),
+            PcsTransform::Lut { tag, lut, .. } => {
+                let lut_type = match lut {
+                    TagData::Lut8(..) => "mft1",
+                    TagData::Lut16(..) => "mft2",
+                    _ => "mAB ",
+                };
+                format!("{} ({})", sig_to_str(*tag), lut_type.trim_end())
+            }
+        }
+    }
+
+    pub fn eval(&self, device: &[f64]) -> Result<[f64; 3], String> {
+        match self {
+            PcsTransform::MatrixTrc { matrix, curves } => {
+                let mut linear = [0.0; 3];
+                for (chan, curve) in curves.iter().enumerate() {
+                    linear[chan] = eval_tag_curve(curve, device.get(chan).copied().unwrap_or(0.0))?;
+                }
+                Ok(mat_vec(matrix, &linear))
+            }
+            PcsTransform::GrayTrc(curve) => {
+                let y = eval_tag_curve(curve, device.first().copied().unwrap_or(0.0))?;
+                Ok([D50_XYZ[0] * y, y, D50_XYZ[2] * y])
+            }
+            PcsTransform::Lut { lut, pcs_lab, .. } => {
+                let pcs = eval_lut(lut, device)?;
+                if pcs.len() < 3 {
+                    return Err("LUT with less than 3 output channels".to_string());
+                }
+                if *pcs_lab {
+                    // lut16 keeps the legacy 16 bits Lab encoding (0xFF00 = 100)
+                    let scale = match lut {
+                        TagData::Lut16(..) => 65535.0 / 65280.0,
+                        _ => 1.0,
+                    };
+                    let lab = [
+                        pcs[0] * scale * 100.0,
+                        pcs[1] * scale * 255.0 - 128.0,
+                        pcs[2] * scale * 255.0 - 128.0,
+                    ];
+                    Ok(lab_to_xyz(&lab, &D50_XYZ))
+                } else {
+                    // u1Fixed15Number : 0x8000 = 1.0
+                    let scale = 65535.0 / 32768.0;
+                    Ok([pcs[0] * scale, pcs[1] * scale, pcs[2] * scale])
+                }
+            }
+        }
+    }
+}
+
+// PCS XYZ relative to D50 (Y = 1 for the media white) to device values (0..1)
+pub enum DeviceTransform<'a> {
+    MatrixTrc {
+        matrix: Matrix3, // Inverse of the rXYZ, gXYZ and bXYZ columns matrix
+        curves: [&'a TagData; 3],
+    },
+    GrayTrc(&'a TagData),
+    Lut {
+        tag: u32,
+        lut: &'a TagData,
+        pcs_lab: bool,
+    },
+}
+
+// Input of an increasing or decreasing TRC giving the output value, found by bisection
+fn invert_tag_curve(tag: &TagData, y: f64) -> Result<f64, String> {
+    let increasing = eval_tag_curve(tag, 1.0)? >= eval_tag_curve(tag, 0.0)?;
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..32 {
+        let mid = (low + high) / 2.0;
+        if (eval_tag_curve(tag, mid)? < y) == increasing {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok((low + high) / 2.0)
+}
+
+impl DeviceTransform<'_> {
+    pub fn name(&self) -> String {
+        match self {
+            DeviceTransform::MatrixTrc { .. } => {
+                "Inverse Matrix/TRC (rXYZ, gXYZ, bXYZ, rTRC, gTRC, bTRC)".to_string()
+            }
+            DeviceTransform::GrayTrc(..) => "Inverse gray TRC (kTRC)".to_string(),
+            DeviceTransform::Lut { tag, lut, .. } => {
+                let lut_type = match lut {
+                    TagData::Lut8(..) => "mft1",
+                    TagData::Lut16(..) => "mft2",
+                    _ => "mBA ",
+                };
+                format!("{} ({})", sig_to_str(*tag), lut_type.trim_end())
+            }
+        }
+    }
+
+    pub fn eval(&self, xyz: &[f64; 3]) -> Result<Vec<f64>, String> {
+        match self {
+            DeviceTransform::MatrixTrc { matrix, curves } => {
+                let linear = mat_vec(matrix, xyz);
+                curves
+                    .iter()
+                    .zip(linear)
+                    .map(|(curve, val)| invert_tag_curve(curve, val.clamp(0.0, 1.0)))
+                    .collect()
+            }
+            DeviceTransform::GrayTrc(curve) => {
+                Ok(vec![invert_tag_curve(curve, xyz[1].clamp(0.0, 1.0))?])
+            }
+            DeviceTransform::Lut { lut, pcs_lab, .. } => {
+                Ok(eval_lut(lut, &encode_pcs(lut, xyz, *pcs_lab))?
+                    .into_iter()
+                    .map(|val| val.clamp(0.0, 1.0))
+                    .collect())
+            }
+        }
+    }
+}
+
+impl Profile {
+    // Table of a rendering intent in a family of table tags (A2B, B2A, pre, D2B, B2D),
+    // the perceptual table (0) if the profile has no table for the intent. The ICC-absolute
+    // intent (3) uses the media-relative table, see to_absolute, except for D2B3 and B2D3.
+    pub fn intent_table(&self, prefix: &str, intent: u32) -> Option<(u32, &TagData)> {
+        let table_intent = match (prefix, intent) {
+            ("D2B" | "B2D", intent) => intent.min(3),
+            (_, 3) => 1,
+            (_, intent) => intent.min(2),
+        };
+
+        [table_intent, 0].iter().find_map(|table_intent| {
+            let tag = str_to_sig(&format!("{}{}", prefix, table_intent));
+            self.tags
+                .iter()
+                .find(|(sig, _)| *sig == tag)
+                .map(|(sig, data)| (*sig, data))
+        })
+    }
+
+    // A2B table of the intent, then the matrix/TRC or gray TRC model.
+    // The floating point D2Bx tables are not evaluated.
+    pub fn device_to_pcs(&self, intent: u32) -> Result<PcsTransform<'_>, String> {
+        let pcs_lab = sig_to_str(self.header.pcs) == "Lab ";
+
+        if let Some((tag, lut)) = self.intent_table("A2B", intent) {
+            return Ok(PcsTransform::Lut { tag, lut, pcs_lab });
+        }
+
+        let colorants = ["rXYZ", "gXYZ", "bXYZ"].map(|sig| self.tag(sig));
+        let curves = ["rTRC", "gTRC", "bTRC"].map(|sig| self.tag(sig));
+        if let ([Some(red), Some(green), Some(blue)], [Some(r_trc), Some(g_trc), Some(b_trc)]) =
+            (colorants, curves)
+        {
+            let mut matrix: Matrix3 = [[0.0; 3]; 3];
+            for (col, colorant) in [red, green, blue].iter().enumerate() {
+                let TagData::XYZ(xyz) = colorant else {
+                    return Err("Colorant tag is not an XYZ tag".to_string());
+                };
+                let xyz = xyz_number_to_f64(xyz);
+                for row in 0..3 {
+                    matrix[row][col] = xyz[row];
+                }
+            }
+            return Ok(PcsTransform::MatrixTrc {
+                matrix,
+                curves: [r_trc, g_trc, b_trc],
+            });
+        }
+
+        match self.tag("kTRC") {
+            Some(curve) => Ok(PcsTransform::GrayTrc(curve)),
+            None => Err("No A2B table, matrix/TRC or gray TRC in the profile".to_string()),
+        }
+    }
+
+    // B2A table of the intent, then the inverse of the matrix/TRC or gray TRC model
+    pub fn pcs_to_device(&self, intent: u32) -> Result<DeviceTransform<'_>, String> {
+        let pcs_lab = sig_to_str(self.header.pcs) == "Lab ";
+
+        if let Some((tag, lut)) = self.intent_table("B2A", intent) {
+            return Ok(DeviceTransform::Lut { tag, lut, pcs_lab });
+        }
+
+        match self.device_to_pcs(intent)? {
+            PcsTransform::MatrixTrc { matrix, curves } => Ok(DeviceTransform::MatrixTrc {
+                matrix: mat_inv(&matrix)?,
+                curves,
+            }),
+            PcsTransform::GrayTrc(curve) => Ok(DeviceTransform::GrayTrc(curve)),
+            PcsTransform::Lut { .. } => {
+                Err("No B2A table for the A2B table of the profile".to_string())
+            }
+        }
+    }
+}
+
+impl Profile {
+    // Media white point (wtpt), the PCS illuminant if absent
+    pub fn media_white(&self) -> [f64; 3] {
+        match self.tag("wtpt") {
+            Some(TagData::XYZ(xyz)) => xyz_number_to_f64(xyz),
+            _ => D50_XYZ,
+        }
+    }
+
+    // Media-relative PCS XYZ to ICC-absolute colorimetric XYZ (ICC.1 Annex D.3)
+    pub fn to_absolute(&self, relative: &[f64; 3]) -> [f64; 3] {
+        let white = self.media_white();
+        std::array::from_fn(|chan| relative[chan] * white[chan] / D50_XYZ[chan])
+    }
+
+    pub fn to_relative(&self, absolute: &[f64; 3]) -> [f64; 3] {
+        let white = self.media_white();
+        std::array::from_fn(|chan| absolute[chan] * D50_XYZ[chan] / white[chan])
+    }
+}
@@ -0,0 +1,526 @@
+use std::fmt::Display;
+
+use crate::analysis::layout::analyze_layout;
+use crate::parsers::icc_types::{read_clro_type, read_clrt_type, read_desc_type};
+use crate::parsers::tags::read_tag_table;
+use crate::types::icc::ProfileIdStatus;
+use crate::utils::bytes::{bytes_to_u32, sig_to_str, str_to_sig};
+use crate::utils::icc::{
+    check_profile_id, color_space_channels, ICC_CLASS_PROFILE, ICC_PF_COLOR_SPACE,
+    ICC_REQUIRED_TAGS, ICC_TAG_TYPES,
+};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "ERROR"),
+            Severity::Warning => write!(f, "WARNING"),
+            Severity::Info => write!(f, "INFO"),
+        }
+    }
+}
+
+pub struct Finding {
+    pub severity: Severity,
+    pub clause: String, // Specification clause the finding refers to
+    pub message: String,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "    [{}] {} ({})",
+            self.severity, self.message, self.clause
+        )
+    }
+}
+
+struct Findings {
+    v4: bool,
+    findings: Vec<Finding>,
+}
+
+impl Findings {
+    // Clauses are given for ICC.1:2022 (v4.4) and ICC.1:2001-04 (v2.4)
+    fn push(&mut self, severity: Severity, v4_clause: &str, v2_clause: &str, message: String) {
+        let clause = if self.v4 {
+            format!("ICC.1:2022 {}", v4_clause)
+        } else {
+            format!("ICC.1:2001-04 {}", v2_clause)
+        };
+        self.findings.push(Finding {
+            severity,
+            clause,
+            message,
+        });
+    }
+}
+
+fn check_header(icc_raw_data: &[u8], findings: &mut Findings) -> Result<(), String> {
+    let pf_size = bytes_to_u32(&icc_raw_data[0..=3])? as usize;
+    if pf_size > icc_raw_data.len() {
+        findings.push(
+            Severity::Error,
+            "7.2.2",
+            "6.1.1",
+            format!(
+                "Header size ({} Bytes) is larger than the file ({} Bytes)",
+                pf_size,
+                icc_raw_data.len()
+            ),
+        );
+    } else if pf_size < icc_raw_data.len() {
+        findings.push(
+            Severity::Warning,
+            "7.2.2",
+            "6.1.1",
+            format!(
+                "File ({} Bytes) is larger than the header size ({} Bytes)",
+                icc_raw_data.len(),
+                pf_size
+            ),
+        );
+    }
+    if findings.v4 && !pf_size.is_multiple_of(4) {
+        findings.push(
+            Severity::Warning,
+            "7.2.2",
+            "6.1.1",
+            format!("Profile size ({} Bytes) is not a multiple of 4", pf_size),
+        );
+    }
+
+    let major_rev = icc_raw_data[8];
+    if major_rev != 2 && major_rev != 4 {
+        findings.push(
+            Severity::Warning,
+            "7.2.4",
+            "6.1.3",
+            format!("Unsupported profile major version {}", major_rev),
+        );
+    }
+
+    let class = sig_to_str(bytes_to_u32(&icc_raw_data[12..=15])?);
+    if !ICC_CLASS_PROFILE.iter().any(|(sig, _)| *sig == class) {
+        findings.push(
+            Severity::Error,
+            "7.2.5",
+            "6.1.4",
+            format!("Unknown profile/device class '{}'", class),
+        );
+    }
+
+    for (name, range, v4_clause, v2_clause) in [
+        ("data color space", 16..=19, "7.2.6", "6.1.5"),
+        ("profile connection space", 20..=23, "7.2.7", "6.1.6"),
+    ] {
+        let space = sig_to_str(bytes_to_u32(&icc_raw_data[range])?);
+        if !ICC_PF_COLOR_SPACE.contains(&space.trim_end()) {
+            findings.push(
+                Severity::Error,
+                v4_clause,
+                v2_clause,
+                format!("Unknown {} '{}'", name, space),
+            );
+        }
+    }
+
+    let pcs = sig_to_str(bytes_to_u32(&icc_raw_data[20..=23])?);
+    if class != "link" && pcs != "XYZ " && pcs != "Lab " {
+        findings.push(
+            Severity::Error,
+            "7.2.7",
+            "6.1.6",
+            format!("PCS must be 'XYZ ' or 'Lab ', found '{}'", pcs),
+        );
+    }
+
+    if bytes_to_u32(&icc_raw_data[36..=39])? != 0x61637370 {
+        findings.push(
+            Severity::Error,
+            "7.2.9",
+            "6.1.8",
+            "Profile file signature is not 'acsp'".to_string(),
+        );
+    }
+
+    let intent = bytes_to_u32(&icc_raw_data[64..=67])?;
+    if intent > 3 {
+        findings.push(
+            Severity::Error,
+            "7.2.15",
+            "6.1.12",
+            format!("Unknown rendering intent {}", intent),
+        );
+    }
+
+    // D50 as encoded in s15Fixed16Number : 0x0000F6D6, 0x00010000, 0x0000D32D
+    let illuminant = [
+        bytes_to_u32(&icc_raw_data[68..=71])?,
+        bytes_to_u32(&icc_raw_data[72..=75])?,
+        bytes_to_u32(&icc_raw_data[76..=79])?,
+    ];
+    let d50 = [0x0000F6D6u32, 0x00010000, 0x0000D32D];
+    if illuminant
+        .iter()
+        .zip(d50.iter())
+        .any(|(val, d50)| val.abs_diff(*d50) > 1)
+    {
+        findings.push(
+            Severity::Warning,
+            "7.2.16",
+            "6.1.13",
+            "PCS illuminant is not D50".to_string(),
+        );
+    }
+
+    let reserved_start = if findings.v4 { 100 } else { 84 };
+    if icc_raw_data[reserved_start..=127]
+        .iter()
+        .any(|byte| *byte != 0)
+    {
+        let severity = if !findings.v4 && icc_raw_data[100..=127].iter().all(|byte| *byte == 0) {
+            // Version 2 profiles carrying a version 4 profile ID
+            Severity::Warning
+        } else {
+            Severity::Error
+        };
+        findings.push(
+            severity,
+            "7.2.19",
+            "6.1.15",
+            format!("Reserved header bytes {}..127 are not zero", reserved_start),
+        );
+    }
+
+    if findings.v4 {
+        match check_profile_id(icc_raw_data) {
+            ProfileIdStatus::Valid => {}
+            ProfileIdStatus::Absent => findings.push(
+                Severity::Info,
+                "7.2.18",
+                "6.1.15",
+                "Profile ID is not set".to_string(),
+            ),
+            status => findings.push(Severity::Error, "7.2.18", "6.1.15", status.to_string()),
+        }
+    }
+    Ok(())
+}
+
+fn check_tags(
+    icc_raw_data: &[u8],
+    tag_table: &[(u32, usize, usize)],
+    findings: &mut Findings,
+) -> Result<(), String> {
+    for (tag_num, (tag_sig, offset, size)) in tag_table.iter().enumerate() {
+        let tag_str = sig_to_str(*tag_sig);
+
+        if tag_table[..tag_num]
+            .iter()
+            .any(|(other_sig, _, _)| other_sig == tag_sig)
+        {
+            findings.push(
+                Severity::Error,
+                "7.3.1",
+                "6.2",
+                format!("Tag '{}' is present more than once", tag_str),
+            );
+        }
+
+        if !offset.is_multiple_of(4) {
+            findings.push(
+                Severity::Error,
+                "7.1.2",
+                "6.2",
+                format!(
+                    "Tag '{}' data (offset {}) does not start on a 4 bytes boundary",
+                    tag_str, offset
+                ),
+            );
+        }
+
+        if *size < 8 || offset + size > icc_raw_data.len() {
+            findings.push(
+                Severity::Error,
+                "7.3.1",
+                "6.2",
+                format!(
+                    "Tag '{}' ({} Bytes at {}) is outside the file ({} Bytes)",
+                    tag_str,
+                    size,
+                    offset,
+                    icc_raw_data.len()
+                ),
+            );
+            continue;
+        }
+
+        if *offset < 132 + 12 * tag_table.len() {
+            findings.push(
+                Severity::Error,
+                "7.3.1",
+                "6.2",
+                format!(
+                    "Tag '{}' data overlaps the header or the tag table",
+                    tag_str
+                ),
+            );
+        }
+
+        let type_sig = sig_to_str(bytes_to_u32(&icc_raw_data[*offset..=offset + 3])?);
+        if let Some((_, v2_types, v4_types)) =
+            ICC_TAG_TYPES.iter().find(|(tag, _, _)| *tag == tag_str)
+        {
+            let allowed = if findings.v4 { v4_types } else { v2_types };
+            if !allowed.contains(&type_sig.as_str()) {
+                let severity = if allowed.is_empty() {
+                    Severity::Warning
+                } else {
+                    Severity::Error
+                };
+                findings.push(
+                    severity,
+                    "9.2",
+                    "6.4",
+                    format!(
+                        "Tag '{}' has type '{}', expected one of {:?} for a version {} profile",
+                        tag_str,
+                        type_sig,
+                        allowed,
+                        if findings.v4 { 4 } else { 2 }
+                    ),
+                );
+            }
+        }
+
+        if type_sig == "desc" {
+            match read_desc_type(icc_raw_data, *offset, *size) {
+                Ok(desc) => {
+                    for warning in desc.warnings() {
+                        findings.push(
+                            Severity::Warning,
+                            "9.2",
+                            "6.5.17",
+                            format!("Tag '{}' : {}", tag_str, warning),
+                        );
+                    }
+                }
+                Err(err) => findings.push(
+                    Severity::Error,
+                    "9.2",
+                    "6.5.17",
+                    format!("Tag '{}' : {}", tag_str, err),
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+// Colorant tables must describe every channel of their color space, the colorant
+// order must list each colorant once
+fn check_colorants(
+    icc_raw_data: &[u8],
+    tag_table: &[(u32, usize, usize)],
+    findings: &mut Findings,
+) {
+    let find_tag = |sig: &str| {
+        tag_table
+            .iter()
+            .find(|(tag_sig, _, _)| *tag_sig == str_to_sig(sig))
+    };
+    let mut colorants_num: Option<usize> = None;
+
+    for (sig, space_idx) in [("clrt", 16), ("clot", 20)] {
+        let Some((_, offset, size)) = find_tag(sig) else {
+            continue;
+        };
+        let Ok(colorants) = read_clrt_type(icc_raw_data, *offset, *size) else {
+            continue;
+        };
+        if sig == "clrt" {
+            colorants_num = Some(colorants.len());
+        }
+        let space = bytes_to_u32(&icc_raw_data[space_idx..space_idx + 4]).unwrap_or(0);
+        match color_space_channels(space) {
+            Some(channels) if channels != colorants.len() => findings.push(
+                Severity::Error,
+                "9.2",
+                "6.4",
+                format!(
+                    "Tag '{}' has {} colorants for the {} channels of the '{}' color space",
+                    sig,
+                    colorants.len(),
+                    channels,
+                    sig_to_str(space).trim_end()
+                ),
+            ),
+            _ => {}
+        }
+    }
+
+    let Some((_, offset, size)) = find_tag("clro") else {
+        return;
+    };
+    let Ok(order) = read_clro_type(icc_raw_data, *offset, *size) else {
+        return;
+    };
+    let channels = colorants_num
+        .or_else(|| color_space_channels(bytes_to_u32(&icc_raw_data[16..20]).unwrap_or(0)));
+    if let Some(channels) = channels {
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        if sorted
+            != (0..channels)
+                .map(|colorant| colorant as u8)
+                .collect::<Vec<u8>>()
+        {
+            findings.push(
+                Severity::Error,
+                "9.2",
+                "6.4",
+                format!(
+                    "Tag 'clro' order {:?} is not a permutation of the {} colorants",
+                    order, channels
+                ),
+            );
+        }
+    }
+}
+
+fn check_required_tags(
+    icc_raw_data: &[u8],
+    tag_table: &[(u32, usize, usize)],
+    findings: &mut Findings,
+) -> Result<(), String> {
+    let has_tag = |sig: &str| {
+        tag_table
+            .iter()
+            .any(|(tag_sig, _, _)| *tag_sig == str_to_sig(sig))
+    };
+    let class = sig_to_str(bytes_to_u32(&icc_raw_data[12..=15])?);
+
+    let mut common: Vec<&str> = vec!["desc", "cprt"];
+    if class != "link" {
+        common.push("wtpt");
+    }
+    for sig in common {
+        if !has_tag(sig) {
+            findings.push(
+                Severity::Error,
+                "8.2",
+                "6.3",
+                format!("Required tag '{}' is missing", sig),
+            );
+        }
+    }
+
+    let Some((_, alternatives)) = ICC_REQUIRED_TAGS.iter().find(|(cls, _)| *cls == class) else {
+        return Ok(());
+    };
+
+    if alternatives
+        .iter()
+        .any(|alternative| alternative.iter().all(|sig| has_tag(sig)))
+    {
+        return Ok(());
+    }
+
+    let (v4_clause, v2_clause) = match class.as_str() {
+        "scnr" => ("8.3", "6.3.1"),
+        "mntr" => ("8.4", "6.3.2"),
+        "prtr" => ("8.5", "6.3.3"),
+        "link" => ("8.6", "6.3.4"),
+        "spac" => ("8.7", "6.3.5"),
+        "abst" => ("8.8", "6.3.6"),
+        _ => ("8.9", "6.3.7"),
+    };
+
+    let missing: Vec<String> = alternatives
+        .iter()
+        .map(|alternative| {
+            let missing_tags: Vec<&str> = alternative
+                .iter()
+                .filter(|sig| !has_tag(sig))
+                .copied()
+                .collect();
+            missing_tags.join(", ")
+        })
+        .collect();
+
+    findings.push(
+        Severity::Error,
+        v4_clause,
+        v2_clause,
+        format!(
+            "'{}' profile is missing required tags : {}",
+            class,
+            missing.join(" or ")
+        ),
+    );
+    Ok(())
+}
+
+pub fn validate_profile(icc_raw_data: &[u8]) -> Vec<Finding> {
+    if icc_raw_data.len() < 132 {
+        return vec![Finding {
+            severity: Severity::Error,
+            clause: "ICC.1:2022 7.2".to_string(),
+            message: format!(
+                "File ({} Bytes) is too short to contain a header and a tag count",
+                icc_raw_data.len()
+            ),
+        }];
+    }
+
+    let mut findings = Findings {
+        v4: icc_raw_data[8] >= 4,
+        findings: vec![],
+    };
+
+    if let Err(err) = check_header(icc_raw_data, &mut findings) {
+        findings.push(Severity::Error, "7.2", "6.1", err);
+    }
+
+    match read_tag_table(icc_raw_data) {
+        Ok(tag_table) => {
+            let checked = check_tags(icc_raw_data, &tag_table, &mut findings).and_then(|_| {
+                check_colorants(icc_raw_data, &tag_table, &mut findings);
+                check_required_tags(icc_raw_data, &tag_table, &mut findings)
+            });
+            if let Err(err) = checked {
+                findings.push(Severity::Error, "7.3", "6.2", err);
+            }
+        }
+        Err(err) => findings.push(Severity::Error, "7.3", "6.2", err),
+    }
+
+    // Shared tags have the same offset and size, anything else intersecting overlaps
+    if let Ok(layout) = analyze_layout(icc_raw_data) {
+        for (tag, other, size) in layout.overlaps.iter() {
+            findings.push(
+                Severity::Error,
+                "7.3.1",
+                "6.2",
+                format!(
+                    "Tag '{}' overlaps tag '{}' on {} Bytes",
+                    sig_to_str(*tag),
+                    sig_to_str(*other),
+                    size
+                ),
+            );
+        }
+    }
+
+    findings.findings.sort_by_key(|finding| finding.severity);
+    findings.findings
+}
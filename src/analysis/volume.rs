@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::types::profile::Profile;
+use crate::utils::color::{xyz_to_lab, D50_XYZ};
+use crate::utils::geometry::{cross3, distance3, dot3, sub3, Point3};
+use crate::utils::icc::color_space_channels;
+
+// Device values sampled per profile, spread over a regular grid of the device space
+pub const GAMUT_SAMPLES: f64 = 10000.0;
+// Voxels counted in the overlapping bounds of two gamuts to measure their intersection
+pub const INTERSECTION_VOXELS: f64 = 200_000.0;
+
+// Distance below which a point is on a face plane, relative to the extent of the colors
+const PLANE_TOLERANCE: f64 = 1e-9;
+// Faces kept while building a hull, far above the few hundreds of a sampled gamut
+pub const MAX_HULL_FACES: usize = 20_000;
+
+// Triangle of the hull, vertices counterclockwise seen from outside
+#[derive(Clone, Copy, Debug)]
+struct HullFace {
+    vertices: [usize; 3],
+    normal: Point3,
+    offset: f64, // normal · point for the points of the face plane
+}
+
+impl HullFace {
+    fn new(points: &[Point3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|idx| points[idx]);
+        let normal = cross3(&sub3(&b, &a), &sub3(&c, &a));
+        let length = dot3(&normal, &normal).sqrt().max(f64::MIN_POSITIVE);
+        let normal = normal.map(|value| value / length);
+        HullFace {
+            vertices,
+            normal,
+            offset: dot3(&normal, &a),
+        }
+    }
+
+    fn distance(&self, point: &Point3) -> f64 {
+        dot3(&self.normal, point) - self.offset
+    }
+}
+
+// Convex hull of the L*a*b* colors of a gamut, as a closed triangle mesh
+pub struct GamutHull {
+    pub vertices: Vec<Point3>,
+    pub faces: Vec<[usize; 3]>,
+    planes: Vec<(Point3, f64)>,
+    min: Point3,
+    max: Point3,
+    epsilon: f64,
+}
+
+// 4 points spanning a volume to start the hull from
+fn initial_tetrahedron(points: &[Point3], extent: f64) -> Result<[usize; 4], String> {
+    if points.len() < 4 {
+        return Err(format!(
+            "{} colors are not enough to span a gamut",
+            points.len()
+        ));
+    }
+    let farthest = |score: &dyn Fn(&Point3) -> f64| {
+        (0..points.len())
+            .max_by(|a, b| score(&points[*a]).total_cmp(&score(&points[*b])))
+            .unwrap_or(0)
+    };
+
+    let p0 = farthest(&|point| -point[0]);
+    let p1 = farthest(&|point| distance3(point, &points[p0]));
+    let axis = sub3(&points[p1], &points[p0]);
+    let p2 = farthest(&|point| {
+        let normal = cross3(&axis, &sub3(point, &points[p0]));
+        dot3(&normal, &normal)
+    });
+    let normal = cross3(&axis, &sub3(&points[p2], &points[p0]));
+    let p3 = farthest(&|point| dot3(&normal, &sub3(point, &points[p0])).abs());
+
+    // Six times the volume, compared to a flat tetrahedron of the cloud extent
+    let volume = dot3(&normal, &sub3(&points[p3], &points[p0])).abs();
+    if volume <= PLANE_TOLERANCE * extent.powi(3) {
+        return Err("The gamut has no volume (all colors on a plane or a line)".to_string());
+    }
+    Ok([p0, p1, p2, p3])
+}
+
+impl GamutHull {
+    // Incremental convex hull : each point outside the hull replaces the faces it sees
+    // Points with NaN or infinite coordinates are ignored
+    pub fn from_points(points: &[Point3]) -> Result<Self, String> {
+        let points: Vec<Point3> = points
+            .iter()
+            .filter(|point| point.iter().all(|value| value.is_finite()))
+            .copied()
+            .collect();
+        let extent = (0..3)
+            .map(|axis| {
+                let (min, max) = points
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), point| {
+                        (min.min(point[axis]), max.max(point[axis]))
+                    });
+                max - min
+            })
+            .fold(0.0, f64::max);
+        let epsilon = PLANE_TOLERANCE * extent;
+        let points = points.as_slice();
+
+        let tetrahedron = initial_tetrahedron(points, extent)?;
+        let inside: Point3 = std::array::from_fn(|axis| {
+            tetrahedron
+                .iter()
+                .map(|idx| points[*idx][axis])
+                .sum::<f64>()
+                / 4.0
+        });
+
+        let mut faces: Vec<HullFace> = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+            .iter()
+            .map(|corners| {
+                let vertices = corners.map(|corner| tetrahedron[corner]);
+                let face = HullFace::new(points, vertices);
+                match face.distance(&inside) > 0.0 {
+                    true => HullFace::new(points, [vertices[0], vertices[2], vertices[1]]),
+                    false => face,
+                }
+            })
+            .collect();
+
+        for (point_idx, point) in points.iter().enumerate() {
+            let (visible, hidden): (Vec<HullFace>, Vec<HullFace>) = faces
+                .iter()
+                .partition(|face| face.distance(point) > epsilon);
+            if visible.is_empty() {
+                continue;
+            }
+
+            // Edges of the visible region not shared by two visible faces
+            let edges: Vec<(usize, usize)> = visible
+                .iter()
+                .flat_map(|face| {
+                    let [a, b, c] = face.vertices;
+                    [(a, b), (b, c), (c, a)]
+                })
+                .collect();
+            let edge_set: HashSet<&(usize, usize)> = edges.iter().collect();
+            faces = hidden;
+            for (a, b) in edges.iter() {
+                if !edge_set.contains(&(*b, *a)) {
+                    faces.push(HullFace::new(points, [*a, *b, point_idx]));
+                }
+            }
+            if faces.len() > MAX_HULL_FACES {
+                return Err(format!(
+                    "The gamut hull has more than {} faces",
+                    MAX_HULL_FACES
+                ));
+            }
+        }
+
+        // Only the points used by the faces are kept
+        let mut indexes: HashMap<usize, usize> = HashMap::new();
+        let mut vertices: Vec<Point3> = vec![];
+        let hull_faces: Vec<[usize; 3]> = faces
+            .iter()
+            .map(|face| {
+                face.vertices.map(|point_idx| {
+                    *indexes.entry(point_idx).or_insert_with(|| {
+                        vertices.push(points[point_idx]);
+                        vertices.len() - 1
+                    })
+                })
+            })
+            .collect();
+
+        let min = std::array::from_fn(|axis| {
+            vertices
+                .iter()
+                .fold(f64::INFINITY, |min, vertex| min.min(vertex[axis]))
+        });
+        let max = std::array::from_fn(|axis| {
+            vertices
+                .iter()
+                .fold(f64::NEG_INFINITY, |max, vertex| max.max(vertex[axis]))
+        });
+        Ok(GamutHull {
+            vertices,
+            faces: hull_faces,
+            planes: faces
+                .iter()
+                .map(|face| (face.normal, face.offset))
+                .collect(),
+            min,
+            max,
+            epsilon,
+        })
+    }
+
+    // Sum of the tetrahedra between the faces and a point inside, in cubic ΔE76
+    pub fn volume(&self) -> f64 {
+        let count = self.vertices.len() as f64;
+        let center: Point3 = std::array::from_fn(|axis| {
+            self.vertices.iter().map(|vertex| vertex[axis]).sum::<f64>() / count
+        });
+        self.faces
+            .iter()
+            .map(|face| {
+                let [a, b, c] = face.map(|idx| sub3(&self.vertices[idx], &center));
+                dot3(&a, &cross3(&b, &c)) / 6.0
+            })
+            .sum::<f64>()
+            .abs()
+    }
+
+    pub fn bounds(&self) -> (Point3, Point3) {
+        (self.min, self.max)
+    }
+
+    pub fn contains(&self, lab: &Point3) -> bool {
+        (0..3).all(|axis| lab[axis] >= self.min[axis] && lab[axis] <= self.max[axis])
+            && self
+                .planes
+                .iter()
+                .all(|(normal, offset)| dot3(normal, lab) - offset <= self.epsilon)
+    }
+}
+
+// Volumes of two gamuts, in cubic ΔE76
+pub struct VolumeComparison {
+    pub source: f64,
+    pub destination: f64,
+    pub intersection: f64,
+}
+
+impl VolumeComparison {
+    pub fn source_only(&self) -> f64 {
+        (self.source - self.intersection).max(0.0)
+    }
+
+    pub fn destination_only(&self) -> f64 {
+        (self.destination - self.intersection).max(0.0)
+    }
+}
+
+// The intersection is counted on a voxel grid of the overlapping bounds of both hulls
+pub fn compare_volumes(source: &GamutHull, destination: &GamutHull) -> VolumeComparison {
+    let min: Point3 = std::array::from_fn(|axis| source.min[axis].max(destination.min[axis]));
+    let max: Point3 = std::array::from_fn(|axis| source.max[axis].min(destination.max[axis]));
+    let size = sub3(&max, &min);
+
+    let mut intersection = 0.0;
+    if size.iter().all(|length| *length > 0.0) {
+        let step = (size[0] * size[1] * size[2] / INTERSECTION_VOXELS).cbrt();
+        let steps = size.map(|length| (length / step).ceil() as usize);
+        let mut count = 0usize;
+        for l_idx in 0..steps[0] {
+            for a_idx in 0..steps[1] {
+                for b_idx in 0..steps[2] {
+                    let lab = [
+                        min[0] + (l_idx as f64 + 0.5) * step,
+                        min[1] + (a_idx as f64 + 0.5) * step,
+                        min[2] + (b_idx as f64 + 0.5) * step,
+                    ];
+                    if source.contains(&lab) && destination.contains(&lab) {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        intersection = count as f64 * step.powi(3);
+    }
+
+    // The voxel count can exceed the exact volume of a hull by a fraction of a voxel layer
+    let (source, destination) = (source.volume(), destination.volume());
+    VolumeComparison {
+        source,
+        destination,
+        intersection: intersection.min(source).min(destination),
+    }
+}
+
+impl Display for VolumeComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "    Source volume : {:.0} ΔE³", self.source)?;
+        writeln!(f, "    Destination volume : {:.0} ΔE³", self.destination)?;
+        writeln!(
+            f,
+            "    Intersection : {:.0} ΔE³ ({:.1} % of the source, {:.1} % of the destination)",
+            self.intersection,
+            100.0 * self.intersection / self.source,
+            100.0 * self.intersection / self.destination
+        )?;
+        writeln!(f, "    Source only : {:.0} ΔE³", self.source_only())?;
+        write!(
+            f,
+            "    Destination only : {:.0} ΔE³",
+            self.destination_only()
+        )
+    }
+}
+
+// Every combination of grid values of the device channels
+fn device_grid(channels: usize) -> Vec<Vec<f64>> {
+    let grid_pts = (GAMUT_SAMPLES.powf(1.0 / channels as f64).floor() as usize).max(2);
+    let mut samples: Vec<Vec<f64>> = vec![vec![]];
+    for _ in 0..channels {
+        samples = samples
+            .into_iter()
+            .flat_map(|sample| {
+                (0..grid_pts).map(move |pt| {
+                    let mut sample = sample.clone();
+                    sample.push(pt as f64 / (grid_pts - 1) as f64);
+                    sample
+                })
+            })
+            .collect();
+    }
+    samples
+}
+
+impl Profile {
+    // L*a*b* (D50) hull of the device space through the A2B table of the intent or the
+    // matrix/TRC model
+    pub fn gamut_hull(&self, intent: u32) -> Result<GamutHull, String> {
+        let channels = color_space_channels(self.header.color_space)
+            .ok_or("Unknown number of device channels")?;
+        let transform = self.device_to_pcs(intent)?;
+
+        let colors = device_grid(channels)
+            .iter()
+            .map(|device| Ok(xyz_to_lab(&transform.eval(device)?, &D50_XYZ)))
+            .collect::<Result<Vec<Point3>, String>>()?;
+        GamutHull::from_points(&colors)
+    }
+}
@@ -0,0 +1,21 @@
+use icc_reader::types::profile::Profile;
+
+use crate::commands::load_icc_data;
+
+// Native primaries, white point and gamut coverage of a matrix/TRC display profile
+pub fn coverage(filename: &str) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    println!("---- Primaries and gamut coverage of {} ----", filename);
+    match Profile::from_bytes(&icc_raw_data).and_then(|profile| profile.primaries_report()) {
+        Ok(report) => println!("{}", report),
+        Err(err) => println!("Unable to compute the gamut coverage : {}", err),
+    }
+    println!("---- ----");
+}
@@ -0,0 +1,52 @@
+use std::fs;
+
+use icc_reader::analysis::metadata::Metadata;
+use icc_reader::parsers::icc_types::decode_dict_entries;
+use icc_reader::types::icc::DictRecordsPrettyPrint;
+use icc_reader::types::profile::{Profile, TagData};
+use icc_reader::writers::dict::write_dict_json;
+
+use crate::commands::load_icc_data;
+
+// Shows a dict tag (meta by default) as a table, exported to JSON when an output file is given
+pub fn dict(filename: &str, tag: &str, output: Option<&str>, lang: Option<&str>) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let profile = match Profile::from_bytes(&icc_raw_data) {
+        Ok(profile) => profile,
+        Err(err) => {
+            println!("Unable to decode profile : {}", err);
+            return;
+        }
+    };
+    let Some(TagData::Dict(dict)) = profile.tag(tag) else {
+        println!("No {} dictionary in {}", tag, filename);
+        return;
+    };
+    let records = decode_dict_entries(dict);
+
+    let Some(output) = output else {
+        println!("---- {} dictionary of {} ----", tag, filename);
+        println!("{}", DictRecordsPrettyPrint(&records, lang));
+        if tag == "meta" {
+            let metadata = Metadata::new(records);
+            println!("    Namespaces : {}", metadata.prefixes().join(", "));
+            if let Some(summary) = metadata.summary() {
+                println!("    {}", summary);
+            }
+        }
+        println!("---- ----");
+        return;
+    };
+
+    match fs::write(output, write_dict_json(&records)) {
+        Ok(..) => println!("{} written ({} entries)", output, records.len()),
+        Err(err) => println!("Unable to export to {} : {}", output, err),
+    }
+}
@@ -0,0 +1,33 @@
+use icc_reader::types::profile::Profile;
+
+use crate::commands::load_icc_data;
+
+// Shows the EDID of the display the profile was made for, checked against the profile primaries
+pub fn edid(filename: &str) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let profile = match Profile::from_bytes(&icc_raw_data) {
+        Ok(profile) => profile,
+        Err(err) => {
+            println!("Unable to decode profile : {}", err);
+            return;
+        }
+    };
+    let Some(edid) = profile.edid() else {
+        println!("No EDID in {}", filename);
+        return;
+    };
+
+    println!("---- EDID of {} ----", filename);
+    println!("{}", edid);
+    for comparison in profile.edid_comparisons(&edid) {
+        println!("{}", comparison);
+    }
+    println!("---- ----");
+}
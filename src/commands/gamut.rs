@@ -0,0 +1,135 @@
+use icc_reader::types::profile::Profile;
+use icc_reader::utils::color::{lab_to_xyz, xyz_to_lab, D50_XYZ};
+
+use crate::commands::load_icc_data;
+
+// Colors of a CSV file : the last 3 numeric fields of each row, the first field as the name
+// when there are more than 3. Header rows are skipped.
+fn read_colors_csv(filename: &str) -> Result<Vec<(String, [f64; 3])>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(filename)
+        .map_err(|err| format!("{} : {}", filename, err))?;
+
+    let mut colors: Vec<(String, [f64; 3])> = vec![];
+    for (row_idx, record) in reader.records().enumerate() {
+        let record = record.map_err(|err| err.to_string())?;
+        if record.len() < 3 {
+            continue;
+        }
+        let values: Option<Vec<f64>> = record
+            .iter()
+            .skip(record.len() - 3)
+            .map(|field| field.parse::<f64>().ok())
+            .collect();
+        let Some(values) = values else {
+            continue;
+        };
+        let name = match record.len() {
+            3 => (row_idx + 1).to_string(),
+            _ => record[0].to_string(),
+        };
+        colors.push((name, [values[0], values[1], values[2]]));
+    }
+
+    if colors.is_empty() {
+        return Err(format!("No color values in {}", filename));
+    }
+    Ok(colors)
+}
+
+fn parse_colors(values: &[String]) -> Result<Vec<(String, [f64; 3])>, String> {
+    if let [filename] = values {
+        if filename.to_lowercase().ends_with(".csv") {
+            return read_colors_csv(filename);
+        }
+    }
+    if values.is_empty() || !values.len().is_multiple_of(3) {
+        return Err("Color values must be given by 3 or in a .csv file".to_string());
+    }
+
+    let numbers = values
+        .iter()
+        .map(|value| {
+            value
+                .parse::<f64>()
+                .map_err(|err| format!("{} : {}", value, err))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+    Ok(numbers
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(color_idx, color)| ((color_idx + 1).to_string(), [color[0], color[1], color[2]]))
+        .collect())
+}
+
+// Reports the colors out of the gamut of an output profile, given as D50 Lab or
+// media-relative XYZ (Y = 100 for the media white)
+pub fn gamut_check(filename: &str, space: &str, values: &[String]) {
+    let xyz_input = match space.to_lowercase().as_str() {
+        "lab" => false,
+        "xyz" => true,
+        _ => {
+            println!("Color space must be lab or xyz, found {}", space);
+            return;
+        }
+    };
+    let colors = match parse_colors(values) {
+        Ok(colors) => colors,
+        Err(err) => {
+            println!("Unable to read colors : {}", err);
+            return;
+        }
+    };
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    let profile = match Profile::from_bytes(&icc_raw_data) {
+        Ok(profile) => profile,
+        Err(err) => {
+            println!("Unable to decode profile : {}", err);
+            return;
+        }
+    };
+
+    println!("---- Gamut check of {} ----", filename);
+    let mut out_num = 0;
+    for (name, color) in colors.iter() {
+        let (xyz, lab) = if xyz_input {
+            let xyz = color.map(|val| val / 100.0);
+            (xyz, xyz_to_lab(&xyz, &D50_XYZ))
+        } else {
+            (lab_to_xyz(color, &D50_XYZ), *color)
+        };
+        let (value, in_gamut) = match profile
+            .gamut_value(&xyz)
+            .and_then(|value| Ok((value, profile.in_gamut(&lab)?)))
+        {
+            Ok(result) => result,
+            Err(err) => {
+                println!("Unable to check the gamut : {}", err);
+                return;
+            }
+        };
+        if !in_gamut {
+            out_num += 1;
+        }
+        println!(
+            "      {:>6}  Lab {:.2} {:.2} {:.2} : {} ({:.2})",
+            name,
+            lab[0],
+            lab[1],
+            lab[2],
+            if in_gamut { "in gamut" } else { "OUT of gamut" },
+            value
+        );
+    }
+    println!("    {} of {} colors out of gamut", out_num, colors.len());
+    println!("---- ----");
+}
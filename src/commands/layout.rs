@@ -0,0 +1,20 @@
+use icc_reader::analysis::layout::analyze_layout;
+
+use crate::commands::load_icc_data;
+
+pub fn layout(filename: &str) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    println!("---- Tag layout of {} ----", filename);
+    match analyze_layout(&icc_raw_data) {
+        Ok(layout) => println!("{}", layout),
+        Err(err) => println!("Unable to analyze tag layout : {}", err),
+    }
+    println!("---- ----");
+}
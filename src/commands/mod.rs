@@ -0,0 +1,36 @@
+pub mod coverage;
+pub mod dict;
+pub mod edid;
+pub mod gamut;
+pub mod layout;
+pub mod ncl2;
+pub mod resp;
+pub mod ti3;
+pub mod validate;
+pub mod verify;
+pub mod volume;
+pub mod write;
+
+use std::fs::File;
+use std::io::Read;
+
+use icc_reader::standard_profiles::standard_profile;
+
+// "std:<name>" loads a built-in standard profile instead of a file
+pub fn load_icc_data(name: &str) -> Result<Vec<u8>, String> {
+    if let Some(std_name) = name.strip_prefix("std:") {
+        return match standard_profile(std_name) {
+            Some(profile) => Ok(profile?.to_bytes()),
+            None => Err(format!("Unknown standard profile : {}", std_name)),
+        };
+    }
+
+    let mut icc_raw_data: Vec<u8> = vec![];
+    match File::open(name) {
+        Ok(mut fd) => match fd.read_to_end(&mut icc_raw_data) {
+            Ok(..) => Ok(icc_raw_data),
+            Err(err) => Err(format!("Error while reading file ({}): {}", name, err)),
+        },
+        Err(err) => Err(format!("{} : {}", err, name)),
+    }
+}
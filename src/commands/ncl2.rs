@@ -0,0 +1,64 @@
+use std::fs;
+
+use icc_reader::types::icc::{NamedColor, NamedColorPrettyPrint};
+use icc_reader::types::profile::{Profile, TagData};
+use icc_reader::utils::bytes::sig_to_str;
+use icc_reader::writers::named_colors::{write_named_colors_csv, write_named_colors_json};
+
+use crate::commands::load_icc_data;
+
+// Lists the named colors matching the filter, exported to CSV or JSON by output extension
+pub fn named_colors(filename: &str, filter: Option<&str>, output: Option<&str>) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let profile = match Profile::from_bytes(&icc_raw_data) {
+        Ok(profile) => profile,
+        Err(err) => {
+            println!("Unable to decode profile : {}", err);
+            return;
+        }
+    };
+    let Some(TagData::Ncl2(named_colors)) = profile.tag("ncl2") else {
+        println!("No ncl2 named colors in {}", filename);
+        return;
+    };
+    let pcs_lab = sig_to_str(profile.header.pcs) == "Lab ";
+
+    let colors: Vec<&NamedColor> = match filter {
+        Some(name) => named_colors.find(name),
+        None => named_colors.colors.iter().collect(),
+    };
+
+    let Some(output) = output else {
+        println!("---- Named colors of {} ----", filename);
+        for color in colors.iter() {
+            println!("{}", NamedColorPrettyPrint(named_colors, color, pcs_lab));
+        }
+        println!(
+            "    {} of {} colors",
+            colors.len(),
+            named_colors.colors.len()
+        );
+        println!("---- ----");
+        return;
+    };
+
+    let exported = if output.to_lowercase().ends_with(".json") {
+        Ok(write_named_colors_json(named_colors, &colors, pcs_lab))
+    } else if output.to_lowercase().ends_with(".csv") {
+        write_named_colors_csv(named_colors, &colors, pcs_lab)
+    } else {
+        Err("Output file must end with .csv or .json".to_string())
+    };
+
+    match exported.and_then(|text| fs::write(output, text).map_err(|err| err.to_string())) {
+        Ok(..) => println!("{} written ({} colors)", output, colors.len()),
+        Err(err) => println!("Unable to export to {} : {}", output, err),
+    }
+}
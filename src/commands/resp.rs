@@ -0,0 +1,66 @@
+use std::fs;
+
+use icc_reader::types::profile::{Profile, TagData};
+use icc_reader::writers::response::write_response_csv;
+
+use crate::commands::load_icc_data;
+
+// Shows the output response curves (resp), exported to CSV when an output file is given
+pub fn response_curves(filename: &str, output: Option<&str>) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let profile = match Profile::from_bytes(&icc_raw_data) {
+        Ok(profile) => profile,
+        Err(err) => {
+            println!("Unable to decode profile : {}", err);
+            return;
+        }
+    };
+    let Some(TagData::Rcs2(response)) = profile.tag("resp") else {
+        println!("No resp output response in {}", filename);
+        return;
+    };
+
+    let Some(output) = output else {
+        println!("---- Output response of {} ----", filename);
+        for curve in response.curves.iter() {
+            println!("{}", curve);
+            for (chan, values) in curve.responses.iter().enumerate() {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|(device, measurement)| {
+                        format!(
+                            "{:.1}% {:.3}",
+                            *device as f64 * 100.0 / 65535.0,
+                            measurement
+                        )
+                    })
+                    .collect();
+                println!(
+                    "        Channel {} response : {}",
+                    chan + 1,
+                    values.join(", ")
+                );
+            }
+        }
+        println!("---- ----");
+        return;
+    };
+
+    match write_response_csv(response)
+        .and_then(|text| fs::write(output, text).map_err(|err| err.to_string()))
+    {
+        Ok(..) => println!(
+            "{} written ({} measurement units)",
+            output,
+            response.curves.len()
+        ),
+        Err(err) => println!("Unable to export to {} : {}", output, err),
+    }
+}
@@ -0,0 +1,31 @@
+use std::fs;
+
+use icc_reader::types::profile::Profile;
+use icc_reader::writers::cgats::write_cgats;
+
+use crate::commands::load_icc_data;
+
+// Extract the CGATS data of the targ, DevD or CIED tag to a .ti3 file
+pub fn extract_ti3(input: &str, output: &str, tag: &str) {
+    let icc_raw_data = match load_icc_data(input) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let cgats = match Profile::from_bytes(&icc_raw_data).and_then(|profile| profile.cgats(tag)) {
+        Ok(cgats) => cgats,
+        Err(err) => {
+            println!("Unable to read {} data : {}", tag, err);
+            return;
+        }
+    };
+
+    println!("{}", cgats);
+    match fs::write(output, write_cgats(&cgats)) {
+        Ok(..) => println!("{} written ({} tables)", output, cgats.tables.len()),
+        Err(err) => println!("Unable to write {} : {}", output, err),
+    }
+}
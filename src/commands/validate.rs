@@ -0,0 +1,37 @@
+use icc_reader::analysis::validate::{validate_profile, Severity};
+
+use crate::commands::load_icc_data;
+
+pub fn validate(filename: &str) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    println!("---- Validation of {} ----", filename);
+
+    let findings = validate_profile(&icc_raw_data);
+    findings.iter().for_each(|finding| println!("{}", finding));
+
+    let count = |severity: Severity| {
+        findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    };
+    let errors_num = count(Severity::Error);
+
+    println!(
+        "    {} error(s), {} warning(s), {} info(s)",
+        errors_num,
+        count(Severity::Warning),
+        count(Severity::Info)
+    );
+    if errors_num == 0 {
+        println!("    Profile conforms to the checked requirements");
+    }
+    println!("---- ----");
+}
@@ -0,0 +1,26 @@
+use icc_reader::analysis::accuracy::verify_profile;
+use icc_reader::types::profile::Profile;
+
+use crate::commands::load_icc_data;
+
+pub fn verify(filename: &str, worst_num: usize) {
+    let icc_raw_data = match load_icc_data(filename) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    println!("---- Accuracy of {} ----", filename);
+    match Profile::from_bytes(&icc_raw_data).and_then(|profile| verify_profile(&profile)) {
+        Ok(report) => {
+            println!("{}", report);
+            for patch in report.worst(worst_num) {
+                println!("{}", patch);
+            }
+        }
+        Err(err) => println!("Unable to verify the profile : {}", err),
+    }
+    println!("---- ----");
+}
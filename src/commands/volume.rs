@@ -0,0 +1,69 @@
+use std::fs;
+
+use icc_reader::analysis::volume::{compare_volumes, GamutHull};
+use icc_reader::types::profile::Profile;
+use icc_reader::writers::mesh::{write_hull_obj, write_hull_wrl, write_hull_x3d};
+
+use crate::commands::load_icc_data;
+
+// Gamuts are compared through the media-relative colorimetric tables
+const VOLUME_INTENT: u32 = 1;
+
+fn gamut_hull(filename: &str) -> Result<GamutHull, String> {
+    let profile = Profile::from_bytes(&load_icc_data(filename)?)?;
+    println!(
+        "    {} : {}",
+        filename,
+        profile.device_to_pcs(VOLUME_INTENT)?.name()
+    );
+    profile.gamut_hull(VOLUME_INTENT)
+}
+
+// Gamut volume of a profile, compared to a second one, the hull exported as a mesh
+pub fn volume(filename: &str, other: Option<&str>, output: Option<&str>) {
+    match other {
+        Some(other) => println!("---- Gamut volumes of {} and {} ----", filename, other),
+        None => println!("---- Gamut volume of {} ----", filename),
+    }
+
+    let hull = match gamut_hull(filename) {
+        Ok(hull) => hull,
+        Err(err) => {
+            println!("Unable to compute the gamut of {} : {}", filename, err);
+            return;
+        }
+    };
+
+    match other.map(|other| (other, gamut_hull(other))) {
+        Some((_, Ok(other_hull))) => println!("{}", compare_volumes(&hull, &other_hull)),
+        Some((other, Err(err))) => {
+            println!("Unable to compute the gamut of {} : {}", other, err);
+            return;
+        }
+        None => println!(
+            "    Hull : {} vertices, {} faces\n    Volume : {:.0} ΔE³",
+            hull.vertices.len(),
+            hull.faces.len(),
+            hull.volume()
+        ),
+    }
+    println!("---- ----");
+
+    let Some(output) = output else {
+        return;
+    };
+    let exported = if output.to_lowercase().ends_with(".wrl") {
+        write_hull_wrl(&hull, filename)
+    } else if output.to_lowercase().ends_with(".x3d") {
+        write_hull_x3d(&hull, filename)
+    } else if output.to_lowercase().ends_with(".obj") {
+        write_hull_obj(&hull, filename)
+    } else {
+        Err("Output file must end with .wrl, .x3d or .obj".to_string())
+    };
+
+    match exported.and_then(|mesh| fs::write(output, mesh).map_err(|err| err.to_string())) {
+        Ok(..) => println!("{} written ({} faces)", output, hull.faces.len()),
+        Err(err) => println!("Unable to export to {} : {}", output, err),
+    }
+}
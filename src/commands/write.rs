@@ -0,0 +1,65 @@
+use std::fs;
+
+use icc_reader::types::profile::{Profile, TagData};
+
+use crate::commands::load_icc_data;
+
+fn decode_profile(input: &str) -> Option<Profile> {
+    let icc_raw_data = match load_icc_data(input) {
+        Ok(data) => data,
+        Err(err) => {
+            println!("{}", err);
+            return None;
+        }
+    };
+
+    match Profile::from_bytes(&icc_raw_data) {
+        Ok(profile) => Some(profile),
+        Err(err) => {
+            println!("Unable to decode profile : {}", err);
+            None
+        }
+    }
+}
+
+fn save_profile(profile: &Profile, output: &str) {
+    let raw_tags = profile
+        .tags
+        .iter()
+        .filter(|(_, data)| matches!(data, TagData::Unknown(_)))
+        .count();
+
+    let bytes = profile.to_bytes();
+    match fs::write(output, &bytes) {
+        Ok(..) => println!(
+            "{} written ({} Bytes, {} tags, {} kept as raw data)",
+            output,
+            bytes.len(),
+            profile.tags.len(),
+            raw_tags
+        ),
+        Err(err) => println!("Unable to write {} : {}", output, err),
+    }
+}
+
+// Decode the input profile and serialize it again to the output file
+pub fn write_profile(input: &str, output: &str) {
+    if let Some(profile) = decode_profile(input) {
+        save_profile(&profile, output);
+    }
+}
+
+// Add or replace the translation of a text tag (desc, cprt, dmnd...) before writing
+pub fn translate_profile(input: &str, output: &str, tag: &str, lang: &str, text: &str) {
+    if tag.len() != 4 {
+        println!("Tag signature must be 4 characters long : '{}'", tag);
+        return;
+    }
+
+    if let Some(mut profile) = decode_profile(input) {
+        match profile.set_localized_text(tag, lang, text) {
+            Ok(()) => save_profile(&profile, output),
+            Err(err) => println!("Unable to translate {} : {}", input, err),
+        }
+    }
+}
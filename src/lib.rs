@@ -0,0 +1,6 @@
+pub mod analysis;
+pub mod parsers;
+pub mod standard_profiles;
+pub mod types;
+pub mod utils;
+pub mod writers;
@@ -1,21 +1,212 @@
 use std::env;
 
-mod parsers;
-mod types;
-mod utils;
+mod commands;
 
-use crate::utils::print::print_usage;
+use icc_reader::utils::print::print_usage;
 
-use std::fs::File;
-use std::io::Read;
+use icc_reader::parsers::profile_info::parse_profile_info;
+use icc_reader::parsers::tags::parse_icc_tags;
 
-use crate::parsers::profile_info::parse_profile_info;
-use crate::parsers::tags::parse_icc_tags;
+use crate::commands::coverage::coverage;
+use crate::commands::dict::dict;
+use crate::commands::edid::edid;
+use crate::commands::gamut::gamut_check;
+use crate::commands::layout::layout;
+use crate::commands::load_icc_data;
+use crate::commands::ncl2::named_colors;
+use crate::commands::resp::response_curves;
+use crate::commands::ti3::extract_ti3;
+use crate::commands::validate::validate;
+use crate::commands::verify::verify;
+use crate::commands::volume::volume;
+use crate::commands::write::{translate_profile, write_profile};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
 
-    if args.len() > 4 || args.len() < 2 {
+    // "--lang fr-FR" selects the translation shown for multi-localized texts
+    let lang = match args.iter().position(|arg| arg == "--lang") {
+        Some(pos) if pos + 1 < args.len() => {
+            let lang = args.remove(pos + 1);
+            args.remove(pos);
+            Some(lang)
+        }
+        Some(..) => {
+            print_usage(&args);
+            return;
+        }
+        None => None,
+    };
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("write") => {
+            if args.len() != 4 {
+                print_usage(&args);
+                return;
+            }
+            write_profile(&args[2], &args[3]);
+            return;
+        }
+        Some("translate") => {
+            if args.len() != 7 {
+                print_usage(&args);
+                return;
+            }
+            translate_profile(&args[2], &args[3], &args[4], &args[5], &args[6]);
+            return;
+        }
+        Some("ti3") => {
+            match args.len() {
+                4 => extract_ti3(&args[2], &args[3], "targ"),
+                5 => extract_ti3(&args[2], &args[3], &args[4]),
+                _ => print_usage(&args),
+            }
+            return;
+        }
+        Some("validate") => {
+            if args.len() != 3 {
+                print_usage(&args);
+                return;
+            }
+            validate(&args[2]);
+            return;
+        }
+        Some("verify") => {
+            let worst_num = match args.get(3).map(|arg| arg.parse::<usize>()) {
+                None => 10,
+                Some(Ok(worst_num)) => worst_num,
+                Some(Err(err)) => {
+                    println!("Unable to parse worst patches count : {}", err);
+                    return;
+                }
+            };
+            if args.len() != 3 && args.len() != 4 {
+                print_usage(&args);
+                return;
+            }
+            verify(&args[2], worst_num);
+            return;
+        }
+        Some("ncl2") => {
+            // "--export <file.csv|file.json>" writes the listed colors
+            let output = match args.iter().position(|arg| arg == "--export") {
+                Some(pos) if pos + 1 < args.len() => {
+                    let output = args.remove(pos + 1);
+                    args.remove(pos);
+                    Some(output)
+                }
+                Some(..) => {
+                    print_usage(&args);
+                    return;
+                }
+                None => None,
+            };
+            match args.len() {
+                3 => named_colors(&args[2], None, output.as_deref()),
+                4 => named_colors(&args[2], Some(&args[3]), output.as_deref()),
+                _ => print_usage(&args),
+            }
+            return;
+        }
+        Some("resp") => {
+            // "--export <file.csv>" writes the response curves
+            let output = match args.iter().position(|arg| arg == "--export") {
+                Some(pos) if pos + 1 < args.len() => {
+                    let output = args.remove(pos + 1);
+                    args.remove(pos);
+                    Some(output)
+                }
+                Some(..) => {
+                    print_usage(&args);
+                    return;
+                }
+                None => None,
+            };
+            if args.len() != 3 {
+                print_usage(&args);
+                return;
+            }
+            response_curves(&args[2], output.as_deref());
+            return;
+        }
+        Some("dict") => {
+            // "--export <file.json>" writes the dictionary
+            let output = match args.iter().position(|arg| arg == "--export") {
+                Some(pos) if pos + 1 < args.len() => {
+                    let output = args.remove(pos + 1);
+                    args.remove(pos);
+                    Some(output)
+                }
+                Some(..) => {
+                    print_usage(&args);
+                    return;
+                }
+                None => None,
+            };
+            match args.len() {
+                3 => dict(&args[2], "meta", output.as_deref(), lang.as_deref()),
+                4 => dict(&args[2], &args[3], output.as_deref(), lang.as_deref()),
+                _ => print_usage(&args),
+            }
+            return;
+        }
+        Some("gamut") => {
+            if args.len() < 5 {
+                print_usage(&args);
+                return;
+            }
+            gamut_check(&args[2], &args[3], &args[4..]);
+            return;
+        }
+        Some("coverage") => {
+            if args.len() != 3 {
+                print_usage(&args);
+                return;
+            }
+            coverage(&args[2]);
+            return;
+        }
+        Some("volume") => {
+            // "--export <file.wrl|file.x3d|file.obj>" writes the gamut hull of the first profile
+            let output = match args.iter().position(|arg| arg == "--export") {
+                Some(pos) if pos + 1 < args.len() => {
+                    let output = args.remove(pos + 1);
+                    args.remove(pos);
+                    Some(output)
+                }
+                Some(..) => {
+                    print_usage(&args);
+                    return;
+                }
+                None => None,
+            };
+            match args.len() {
+                3 => volume(&args[2], None, output.as_deref()),
+                4 => volume(&args[2], Some(&args[3]), output.as_deref()),
+                _ => print_usage(&args),
+            }
+            return;
+        }
+        Some("edid") => {
+            if args.len() != 3 {
+                print_usage(&args);
+                return;
+            }
+            edid(&args[2]);
+            return;
+        }
+        Some("layout") => {
+            if args.len() != 3 {
+                print_usage(&args);
+                return;
+            }
+            layout(&args[2]);
+            return;
+        }
+        _ => {}
+    }
+
+    if args.len() > 4 || args.len() < 2 || args.len() == 3 {
         print_usage(&args);
         return;
     }
@@ -44,17 +235,11 @@ fn main() {
 
     println!("----- ICC READER -----\n");
 
-    match File::open(filename) {
-        Ok(mut fd) => {
-            let mut icc_raw_data: Vec<u8> = vec![];
-            match fd.read_to_end(&mut icc_raw_data) {
-                Ok(..) => {
-                    parse_profile_info(&icc_raw_data);
-                    parse_icc_tags(&icc_raw_data, &pt_num, &corr_scale);
-                }
-                Err(err) => println!("Error while reading file ({}): {}", filename, err),
-            }
+    match load_icc_data(filename) {
+        Ok(icc_raw_data) => {
+            parse_profile_info(&icc_raw_data);
+            parse_icc_tags(&icc_raw_data, &pt_num, &corr_scale, lang.as_deref());
         }
-        Err(err) => println!("{} : {}", err, filename),
+        Err(err) => println!("{}", err),
     }
 }
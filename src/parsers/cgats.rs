@@ -0,0 +1,133 @@
+use crate::types::cgats::{Cgats, CgatsTable};
+use crate::types::profile::{Profile, TagData};
+
+enum Section {
+    Header,
+    DataFormat,
+    Data,
+}
+
+// Whitespace separated tokens, quoted strings are kept whole with their quotes
+fn split_tokens(line: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = vec![];
+    let mut token = String::new();
+    let mut quoted = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                token.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+fn end_table(mut table: CgatsTable, values: Vec<String>) -> Result<CgatsTable, String> {
+    if table.fields.is_empty() {
+        return Err(format!(
+            "{} table has data but no data format",
+            table.file_type
+        ));
+    }
+    if !values.len().is_multiple_of(table.fields.len()) {
+        return Err(format!(
+            "{} table has {} values, not a multiple of its {} fields",
+            table.file_type,
+            values.len(),
+            table.fields.len()
+        ));
+    }
+
+    table.sets = values
+        .chunks_exact(table.fields.len())
+        .map(|set| set.to_vec())
+        .collect();
+    Ok(table)
+}
+
+// CGATS.17 text, as embedded by ArgyllCMS in the targ, DevD and CIED tags (.ti3 files)
+pub fn parse_cgats(text: &str) -> Result<Cgats, String> {
+    let mut tables: Vec<CgatsTable> = vec![];
+    let mut table: Option<CgatsTable> = None;
+    let mut values: Vec<String> = vec![];
+    let mut section = Section::Header;
+
+    for line in text.lines() {
+        let line = line.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let tokens = split_tokens(line);
+
+        match section {
+            Section::Header => {
+                let Some(current) = table.as_mut() else {
+                    table = Some(CgatsTable {
+                        file_type: line.to_string(),
+                        ..Default::default()
+                    });
+                    continue;
+                };
+                match tokens[0].as_str() {
+                    "BEGIN_DATA_FORMAT" => section = Section::DataFormat,
+                    "BEGIN_DATA" => section = Section::Data,
+                    key => {
+                        let value = line[key.len()..].trim().to_string();
+                        current.keywords.push((key.to_string(), value));
+                    }
+                }
+            }
+            Section::DataFormat => match tokens[0].as_str() {
+                "END_DATA_FORMAT" => section = Section::Header,
+                _ => {
+                    if let Some(current) = table.as_mut() {
+                        current.fields.extend(tokens);
+                    }
+                }
+            },
+            Section::Data => match tokens[0].as_str() {
+                "END_DATA" => {
+                    if let Some(current) = table.take() {
+                        tables.push(end_table(current, std::mem::take(&mut values))?);
+                    }
+                    section = Section::Header;
+                }
+                _ => values.extend(tokens),
+            },
+        }
+    }
+
+    match section {
+        Section::Header => {}
+        Section::DataFormat => return Err("Missing END_DATA_FORMAT".to_string()),
+        Section::Data => return Err("Missing END_DATA".to_string()),
+    }
+    if tables.is_empty() {
+        return Err("No CGATS table found".to_string());
+    }
+
+    Ok(Cgats { tables })
+}
+
+impl Profile {
+    // targ, DevD or CIED measurement data
+    pub fn cgats(&self, sig: &str) -> Result<Cgats, String> {
+        match self.tag(sig) {
+            Some(TagData::Text(text)) => parse_cgats(text),
+            Some(..) => Err(format!("Tag {} is not a text tag", sig)),
+            None => Err(format!("No {} tag in the profile", sig)),
+        }
+    }
+}
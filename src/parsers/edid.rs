@@ -0,0 +1,114 @@
+use crate::types::edid::Edid;
+use crate::utils::bytes::{decode_base64, decode_hex};
+
+pub const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+pub const EDID_BLOCK_SIZE: usize = 128;
+
+// 10 bits chromaticity : 8 high bits and 2 low bits packed in bytes 25 and 26
+fn chromaticity(edid: &[u8], high_idx: usize, low_byte: u8, low_shift: u8) -> f64 {
+    let value = ((edid[high_idx] as u16) << 2) | ((low_byte >> low_shift) & 0x03) as u16;
+    value as f64 / 1024.0
+}
+
+// Text of a display descriptor, terminated by a line feed and padded with spaces
+fn descriptor_text(descriptor: &[u8]) -> String {
+    let text: Vec<u8> = descriptor[5..18]
+        .iter()
+        .take_while(|byte| **byte != 0x0A)
+        .copied()
+        .collect();
+    String::from_utf8_lossy(&text).trim().to_string()
+}
+
+// EDID 1.x base block (the first 128 bytes, extension blocks are ignored)
+pub fn parse_edid(bytes: &[u8]) -> Result<Edid, String> {
+    if bytes.len() < EDID_BLOCK_SIZE {
+        return Err(format!(
+            "EDID too short : {} bytes, {} expected",
+            bytes.len(),
+            EDID_BLOCK_SIZE
+        ));
+    }
+    if bytes[0..8] != EDID_HEADER {
+        return Err("Invalid EDID header".to_string());
+    }
+    let edid = &bytes[..EDID_BLOCK_SIZE];
+
+    // Three 5 bits letters, 1 = 'A'
+    let mnft = u16::from_be_bytes([edid[8], edid[9]]);
+    let manufacturer_id: String = [10, 5, 0]
+        .iter()
+        .map(|shift| (b'@' + ((mnft >> shift) & 0x1F) as u8) as char)
+        .collect();
+
+    let serial_number = u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]);
+    // Week 0xFF : byte 17 is a model year
+    let week = match edid[16] {
+        0 | 0xFF => None,
+        week => Some(week),
+    };
+
+    let (rg_low, bw_low) = (edid[25], edid[26]);
+    let primaries = [
+        (
+            chromaticity(edid, 27, rg_low, 6),
+            chromaticity(edid, 28, rg_low, 4),
+        ),
+        (
+            chromaticity(edid, 29, rg_low, 2),
+            chromaticity(edid, 30, rg_low, 0),
+        ),
+        (
+            chromaticity(edid, 31, bw_low, 6),
+            chromaticity(edid, 32, bw_low, 4),
+        ),
+    ];
+    let white = (
+        chromaticity(edid, 33, bw_low, 2),
+        chromaticity(edid, 34, bw_low, 0),
+    );
+
+    let mut serial = None;
+    let mut model = None;
+    for descriptor in edid[54..126].chunks_exact(18) {
+        // Display descriptors start with a zero pixel clock
+        if descriptor[0..3] != [0, 0, 0] {
+            continue;
+        }
+        match descriptor[3] {
+            0xFF => serial = Some(descriptor_text(descriptor)),
+            0xFC => model = Some(descriptor_text(descriptor)),
+            _ => (),
+        }
+    }
+
+    Ok(Edid {
+        manufacturer_id,
+        product_code: Some(u16::from_le_bytes([edid[10], edid[11]])),
+        serial_number: (serial_number != 0).then_some(serial_number),
+        serial: serial.filter(|serial| !serial.is_empty()),
+        model: model.filter(|model| !model.is_empty()),
+        week,
+        year: Some(1990 + edid[17] as u16),
+        gamma: (edid[23] != 0xFF).then(|| (edid[23] as f64 + 100.0) / 100.0),
+        primaries: Some(primaries),
+        white: Some(white),
+        checksum_ok: edid.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) == 0,
+    })
+}
+
+// EDID block stored as is, or as an hexadecimal or base64 text, in tag data or a dict value
+pub fn find_edid(bytes: &[u8]) -> Option<Edid> {
+    if let Some(pos) = bytes.windows(8).position(|window| window == EDID_HEADER) {
+        if let Ok(edid) = parse_edid(&bytes[pos..]) {
+            return Some(edid);
+        }
+    }
+
+    let text = String::from_utf8_lossy(bytes);
+    let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    [decode_hex(text), decode_base64(text)]
+        .into_iter()
+        .flatten()
+        .find_map(|blob| parse_edid(&blob).ok())
+}
@@ -2,137 +2,160 @@ use crate::types::icc::*;
 use crate::utils::bytes::*;
 use crate::utils::icc::ICC_CHRM_TYPE;
 
-fn read_text_type_v2(icc_raw_data: &[u8], idx: usize) -> Result<(String, String), String> {
-    let ascii_desc_size_range: &[u8; 4] = match icc_raw_data[(idx + 8)..=(idx + 11)].try_into() {
-        Ok(range) => range,
-        Err(err) => return Err(err.to_string()),
-    };
-    let ascii_desc_size = match usize::try_from(u32::from_be_bytes(*ascii_desc_size_range)) {
-        Ok(usize) => usize,
-        Err(err) => return Err(err.to_string()),
-    };
-
-    let ascii_desc_str =
-        String::from_utf8_lossy(&icc_raw_data[(idx + 12)..(idx + 12 + ascii_desc_size)]);
-
-    let utf_lang_code_range: [u8; 4] = match icc_raw_data
-        [(idx + 12 + ascii_desc_size)..=(idx + 12 + ascii_desc_size + 3)]
-        .try_into()
-    {
-        Ok(range) => range,
-        Err(err) => return Err(err.to_string()),
-    };
-    let _utf_lang_code = u32::from_be_bytes(utf_lang_code_range);
+// Many version 2 profiles have wrong counts or lack the NUL terminators,
+// such issues are recorded in the description instead of failing
+pub fn read_desc_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<TextDescription, String> {
+    let desc_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+    if desc_sig != 0x64657363 {
+        return Err(format!(
+            "Bad tag signature for desc (0x64657363) found {:X}",
+            desc_sig
+        ));
+    }
 
-    let utf_desc_size_range: &[u8; 4] = match icc_raw_data
-        [(idx + 12 + ascii_desc_size + 4)..=(idx + 12 + ascii_desc_size + 7)]
-        .try_into()
-    {
-        Ok(range) => range,
-        Err(err) => return Err(err.to_string()),
-    };
+    let tag_end = (idx + size).min(icc_raw_data.len());
+    let mut issues: Vec<String> = vec![];
 
-    let utf_desc_size = match usize::try_from(u32::from_be_bytes(*utf_desc_size_range)) {
-        Ok(usize) => usize,
-        Err(err) => return Err(err.to_string()),
-    };
+    // ASCII part
+    if idx + 12 > tag_end {
+        return Err("desc tag too short for the ASCII count".to_string());
+    }
+    let mut pos = idx + 12;
+    let mut ascii_count = bytes_u32_usize(&icc_raw_data[idx + 8..=idx + 11])?;
+    if pos + ascii_count > tag_end {
+        issues.push(format!(
+            "ASCII count ({}) exceeds the tag size, truncated to {}",
+            ascii_count,
+            tag_end - pos
+        ));
+        ascii_count = tag_end - pos;
+    }
+    let ascii_bytes = &icc_raw_data[pos..pos + ascii_count];
+    if ascii_count > 0 && ascii_bytes[ascii_count - 1] != 0 {
+        issues.push("ASCII description is not NUL terminated".to_string());
+    }
+    let ascii = String::from_utf8_lossy(ascii_bytes)
+        .split('\0')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    pos += ascii_count;
+
+    // Unicode part
+    let mut unicode_lang = 0;
+    let mut unicode = None;
+    if pos + 8 > tag_end {
+        issues.push("Unicode part is missing".to_string());
+    } else {
+        unicode_lang = bytes_to_u32(&icc_raw_data[pos..=pos + 3])?;
+        let mut unicode_count = bytes_u32_usize(&icc_raw_data[pos + 4..=pos + 7])?;
+        pos += 8;
+
+        // Some writers store the size in bytes instead of characters
+        if pos + 2 * unicode_count > tag_end && pos + unicode_count <= tag_end {
+            issues.push(format!(
+                "Unicode count ({}) looks like a byte count",
+                unicode_count
+            ));
+            unicode_count /= 2;
+        } else if pos + 2 * unicode_count > tag_end {
+            issues.push(format!(
+                "Unicode count ({}) exceeds the tag size",
+                unicode_count
+            ));
+            unicode_count = (tag_end - pos) / 2;
+        }
 
-    let mut utf_desc_str = "None".to_string();
+        if unicode_count > 0 {
+            let utf16: Vec<u16> = icc_raw_data[pos..pos + 2 * unicode_count]
+                .chunks_exact(2)
+                .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+                .collect();
+            if utf16.last() != Some(&0) {
+                issues.push("Unicode description is not NUL terminated".to_string());
+            }
+            let text = String::from_utf16_lossy(&utf16);
+            unicode = Some(text.split('\0').next().unwrap_or_default().to_string());
+        }
+        pos += 2 * unicode_count;
+    }
 
-    if utf_desc_size >= 4 {
-        utf_desc_str = match read_utf16(
-            &icc_raw_data[(idx + 12 + ascii_desc_size + 8)
-                ..(idx + 12 + ascii_desc_size + 8 + (2 * utf_desc_size))],
-        ) {
-            Some(str) => str,
-            None => return Err("Unable to read UTF16 string".to_string()),
-        };
+    // ScriptCode part : code, count and a 67 bytes field
+    let mut scriptcode_code = 0;
+    let mut scriptcode_count = 0;
+    let mut scriptcode_field = [0u8; 67];
+    if pos + 3 > tag_end {
+        issues.push("ScriptCode part is missing".to_string());
+    } else {
+        scriptcode_code = u16::from_be_bytes([icc_raw_data[pos], icc_raw_data[pos + 1]]);
+        scriptcode_count = icc_raw_data[pos + 2];
+        let field_end = (pos + 3 + 67).min(tag_end);
+        if field_end < pos + 3 + 67 {
+            issues.push(format!(
+                "ScriptCode field is {} Bytes long instead of 67",
+                field_end - pos - 3
+            ));
+        }
+        scriptcode_field[..field_end - pos - 3].copy_from_slice(&icc_raw_data[pos + 3..field_end]);
+        if scriptcode_count > 67 {
+            issues.push(format!(
+                "ScriptCode count ({}) exceeds the 67 Bytes field",
+                scriptcode_count
+            ));
+            scriptcode_count = 67;
+        }
     }
 
-    Ok((ascii_desc_str.to_string(), utf_desc_str))
+    Ok(TextDescription {
+        ascii,
+        unicode_lang,
+        unicode,
+        scriptcode_code,
+        scriptcode_count,
+        scriptcode_field,
+        issues,
+    })
 }
 
-fn read_text_type_v4(icc_raw_data: &[u8], idx: usize) -> Result<(String, String), String> {
-    let utf_desc_size_range: &[u8; 4] = match icc_raw_data[(idx + 8)..=(idx + 11)].try_into() {
-        Ok(range) => range,
-        Err(err) => return Err(err.to_string()),
-    };
-    let utf_desc_size = match usize::try_from(u32::from_be_bytes(*utf_desc_size_range)) {
-        Ok(usize) => usize,
-        Err(err) => return Err(err.to_string()),
-    };
-
-    let mut utf_str_vec: Vec<String> = Vec::with_capacity(utf_desc_size);
-
-    for int_idx in 0..utf_desc_size {
-        let utf_lang_code_range: &[u8; 2] = match icc_raw_data
-            [(idx + 16 + (int_idx * 15))..=(idx + 17 + (int_idx * 15))]
-            .try_into()
-        {
-            Ok(range) => range,
-            Err(err) => return Err(err.to_string()),
-        };
-
-        let utf_lang_code = String::from_utf8_lossy(utf_lang_code_range);
-
-        let utf_country_code_range: &[u8; 2] = match icc_raw_data
-            [(idx + 18 + (int_idx * 15))..=(idx + 19 + (int_idx * 15))]
-            .try_into()
-        {
-            Ok(range) => range,
-            Err(err) => return Err(err.to_string()),
-        };
-
-        let utf_country_code = String::from_utf8_lossy(utf_country_code_range);
-
-        let utf_desc_size_range: &[u8; 4] = match icc_raw_data
-            [(idx + 20 + (int_idx * 15))..=(idx + 23 + (int_idx * 15))]
-            .try_into()
-        {
-            Ok(range) => range,
-            Err(err) => return Err(err.to_string()),
-        };
-
-        let utf_desc_size = match usize::try_from(u32::from_be_bytes(*utf_desc_size_range)) {
-            Ok(usize) => usize,
-            Err(err) => return Err(err.to_string()),
-        };
-
-        let utf_desc_offset_range: &[u8; 4] = match icc_raw_data
-            [(idx + 24 + (int_idx * 15))..=(idx + 27 + (int_idx * 15))]
-            .try_into()
-        {
-            Ok(range) => range,
-            Err(err) => return Err(err.to_string()),
-        };
-
-        let utf_desc_offset = match usize::try_from(u32::from_be_bytes(*utf_desc_offset_range)) {
-            Ok(usize) => usize,
-            Err(err) => return Err(err.to_string()),
-        };
-
-        let mut utf_desc_str = "None".to_string();
+// Selected translation of a mluc, with the other available languages
+fn read_text_type_v4(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+    lang: Option<&str>,
+) -> Result<String, String> {
+    let mluc = read_mluc_type(icc_raw_data, idx, size)?;
 
-        if utf_desc_size >= 2 {
-            utf_desc_str = match read_utf16(
-                &icc_raw_data
-                    [(idx + utf_desc_offset)..=(idx + utf_desc_offset + utf_desc_size + 1)],
-            ) {
-                Some(str) => str,
-                None => return Err("Unable to read UTF16 string".to_string()),
-            };
-        }
+    let record = match mluc.localized(lang) {
+        Some(record) => record,
+        None => return Ok("None".to_string()),
+    };
 
-        utf_str_vec.push(format!(
-            "({}/{}) {}",
-            utf_lang_code, utf_country_code, utf_desc_str
-        ));
+    let others: Vec<String> = mluc
+        .records
+        .iter()
+        .filter(|other| other.0 != record.0 || other.1 != record.1)
+        .map(|other| format!("{}/{}", other.0, other.1))
+        .collect();
+    let mut utf_desc_str = MlucRecordPrettyPrint(record).to_string();
+    if !others.is_empty() {
+        utf_desc_str.push_str(&format!(" [also in {}]", others.join(", ")));
     }
 
-    Ok(("None".to_string(), utf_str_vec[0].clone()))
+    Ok(utf_desc_str)
 }
 
-pub fn read_text_desc_type(icc_raw_data: &[u8], idx: usize) -> Result<DescType, String> {
+// desc (version 2) or mluc (version 4) description, ready to be displayed
+pub fn read_text_desc_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+    lang: Option<&str>,
+) -> Result<String, String> {
     let tag_sig_range: &[u8; 4] = match icc_raw_data[idx..=(idx + 3)].try_into() {
         Ok(range) => range,
         Err(err) => return Err(err.to_string()),
@@ -140,8 +163,8 @@ pub fn read_text_desc_type(icc_raw_data: &[u8], idx: usize) -> Result<DescType,
     let tag_sig = u32::from_be_bytes(*tag_sig_range);
 
     match tag_sig {
-        0x6D6C7563 => read_text_type_v4(icc_raw_data, idx),
-        0x64657363 => read_text_type_v2(icc_raw_data, idx),
+        0x6D6C7563 => read_text_type_v4(icc_raw_data, idx, size, lang),
+        0x64657363 => Ok(read_desc_type(icc_raw_data, idx, size)?.to_string()),
         _ => Err("Bad tag signature for description".to_string()),
     }
 }
@@ -150,6 +173,7 @@ pub fn read_text_type(
     icc_raw_data: &[u8],
     idx: usize,
     end: usize,
+    lang: Option<&str>,
 ) -> Result<(u32, String), String> {
     let text_sig_range: &[u8; 4] = match icc_raw_data[idx..=(idx + 3)].try_into() {
         Ok(range) => range,
@@ -164,8 +188,8 @@ pub fn read_text_type(
             Ok((text_sig, text_str.to_string()))
         }
         0x6D6C7563 => {
-            let text_str = match read_text_type_v4(icc_raw_data, idx) {
-                Ok(str) => str.1,
+            let text_str = match read_text_type_v4(icc_raw_data, idx, end, lang) {
+                Ok(str) => str,
                 Err(err) => return Err(err.to_string()),
             };
             Ok((text_sig, text_str))
@@ -182,8 +206,7 @@ pub fn new_xyz(x: &[u8], y: &[u8], z: &[u8]) -> Result<XYZNumber, String> {
             Ok(x_range) => x_range,
             Err(err) => return Err(err.to_string()),
         };
-        let cie_coor: S15Fixed16Number = S15Fixed16Number::from_be_bytes(*x_range);
-        xyz[idx] = cie_coor * 100;
+        xyz[idx] = S15Fixed16Number::from_be_bytes(*x_range);
     }
 
     Ok(xyz)
@@ -255,39 +278,43 @@ fn extract_key_value(tag_dict: &[u8], elem_size: usize) -> Result<Vec<usize>, St
     Ok(key_value_desc)
 }
 
+// Names and values are UTF-16BE, display names and values are mluc types
+pub fn decode_dict_entries(dict: &DictType) -> Vec<DictRecord> {
+    let mluc = |raw: &Option<Vec<u8>>| match raw {
+        Some(raw) => read_mluc_type(raw, 0, raw.len()).ok(),
+        _ => None,
+    };
+
+    dict.entries
+        .iter()
+        .map(|entry| DictRecord {
+            name: utf16_be_to_string(&entry.name),
+            value: entry.value.as_deref().map(utf16_be_to_string),
+            display_name: mluc(&entry.display_name),
+            display_value: mluc(&entry.display_value),
+        })
+        .collect()
+}
+
 pub fn read_dict_type(
     icc_raw_data: &[u8],
     idx: usize,
     size: usize,
-) -> Result<(u32, Vec<(String, String)>), String> {
-    let dict_tag = &icc_raw_data[idx..(idx + size)];
-    let mut dict_str: Vec<(String, String)> = vec![];
-    let dict_sig = bytes_to_u32(&dict_tag[0..=3])?;
-    let dict_size = bytes_u32_usize(&dict_tag[8..=11])?;
-    let element_size = bytes_u32_usize(&dict_tag[12..=15])?;
+) -> Result<Vec<DictRecord>, String> {
+    let dict_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
 
-    let element_last_idx = element_size / 4;
+    if dict_sig != 0x64696374 {
+        return Err(format!(
+            "Bad tag signature for dict (0x64696374) found {:X}",
+            dict_sig
+        ));
+    };
 
-    for entry_num in 0..dict_size {
-        let entry_idx = 16 + (16 * entry_num);
-        let key_value_desc = extract_key_value(
-            &dict_tag[entry_idx..(entry_idx + element_size)],
-            element_size,
-        )?;
-
-        for idx in (0..element_last_idx).step_by(4) {
-            let name_idx = key_value_desc[idx];
-            let name_last_idx = name_idx + key_value_desc[idx + 1];
-            let value_idx = key_value_desc[idx + 2];
-            let value_last_idx = value_idx + key_value_desc[idx + 3];
-            let name_range = &dict_tag[name_idx..name_last_idx];
-            let value_range = &dict_tag[value_idx..value_last_idx];
-            let name: String = String::from_utf8_lossy(name_range).to_string();
-            let value: String = String::from_utf8_lossy(value_range).to_string();
-            dict_str.push((name, value));
-        }
-    }
-    Ok((dict_sig, dict_str))
+    Ok(decode_dict_entries(&read_dict_entries(
+        icc_raw_data,
+        idx,
+        size,
+    )?))
 }
 
 pub fn read_sf32_type(
@@ -295,14 +322,15 @@ pub fn read_sf32_type(
     idx: usize,
     size: usize,
 ) -> Result<(u32, S15Fixed16Array), String> {
-    let sf32_tag = &icc_raw_data[idx..(idx + size)];
+    let sf32_tag = match icc_raw_data.get(idx..(idx + size)) {
+        Some(sf32_tag) if size >= 8 && size.is_multiple_of(4) => sf32_tag,
+        _ => return Err("sf32 tag out of data range".to_string()),
+    };
     let sf32_sig = bytes_to_u32(&sf32_tag[0..=3])?;
-    let mut sf32: S15Fixed16Array = Vec::with_capacity(size);
-
-    for idx in (8..sf32_tag.len()).step_by(4) {
-        let fixed: S15Fixed16Number = bytes_to_sf32(&sf32_tag[idx..idx + 4])?;
-        sf32.push(fixed);
-    }
+    let sf32 = sf32_tag[8..]
+        .chunks_exact(4)
+        .map(bytes_to_sf32)
+        .collect::<Result<S15Fixed16Array, String>>()?;
 
     Ok((sf32_sig, sf32))
 }
@@ -312,7 +340,10 @@ pub fn read_vcgt_type(
     idx: usize,
     size: usize,
 ) -> Result<Option<Vcgt>, String> {
-    let vcgt_tag = &icc_raw_data[idx..(idx + size)];
+    let vcgt_tag = match icc_raw_data.get(idx..(idx + size)) {
+        Some(vcgt_tag) if size >= 18 => vcgt_tag,
+        _ => return Err("vcgt tag out of data range".to_string()),
+    };
     let vcgt_sig = bytes_to_u32(&vcgt_tag[0..=3])?;
 
     if vcgt_sig != 0x76636774 {
@@ -331,6 +362,12 @@ pub fn read_vcgt_type(
             if channels_num != 3 {
                 return Err("channel number must be 3 (RGB)".to_string());
             }
+            if vcgt_tag.len() < 18 + 3 * entries_num * entry_size {
+                return Err(format!(
+                    "vcgt ramps ({} entries) overflow the tag size ({} Bytes)",
+                    entries_num, size
+                ));
+            }
 
             let mut r_ramp: Vec<u16> = Vec::with_capacity(entries_num + 1);
             let mut g_ramp: Vec<u16> = Vec::with_capacity(entries_num + 1);
@@ -422,9 +459,13 @@ pub fn read_vcgt_type(
         }
         1 => {
             println!("Simple gamma value found");
-            let r_gamma = bytes_to_sf32(&vcgt_tag[12..=15])?;
-            let r_min = bytes_to_sf32(&vcgt_tag[16..=19])?;
-            let r_max = bytes_to_sf32(&vcgt_tag[20..=23])?;
+            let red = match vcgt_tag.get(12..24) {
+                Some(red) => red,
+                None => return Err("vcgt formula out of data range".to_string()),
+            };
+            let r_gamma = bytes_to_sf32(&red[0..=3])?;
+            let r_min = bytes_to_sf32(&red[4..=7])?;
+            let r_max = bytes_to_sf32(&red[8..=11])?;
 
             println!("Red channel : G{} Min{} Max{}", r_gamma, r_min, r_max);
 
@@ -436,57 +477,53 @@ pub fn read_vcgt_type(
     Ok(vcgt)
 }
 
-fn read_lut8_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<Lut8, String> {
-    let lut_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+// Grid points ^ input channels * output channels, None when it overflows
+fn lut_clut_len(grid_pts_num: u8, in_chans_num: u8, out_chans_num: u8) -> Option<usize> {
+    (grid_pts_num as usize)
+        .checked_pow(in_chans_num.into())?
+        .checked_mul(out_chans_num.into())
+}
 
-    let lut_in_channels_num = icc_raw_data[idx + 8];
-    let lut_out_channels_num = icc_raw_data[idx + 9];
-    let lut_grid_pts_num = icc_raw_data[idx + 10];
-    let mut lut_encoded_params: Vec<S15Fixed16Number> = Vec::with_capacity(9);
+fn read_lut8_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<Lut8, String> {
+    let lut_header = match icc_raw_data.get(idx..idx + 48) {
+        Some(header) if tag_size >= 48 => header,
+        _ => return Err("Lut8 header out of data range".to_string()),
+    };
+    let lut_sig = bytes_to_u32(&lut_header[0..=3])?;
 
-    for e_param_idx in (12..=44).step_by(4) {
-        let e_param =
-            match bytes_to_sf32(&icc_raw_data[(idx + e_param_idx)..=(idx + e_param_idx + 3)]) {
-                Ok(sf32) => sf32,
-                Err(err) => return Err(err.to_string()),
-            };
-        lut_encoded_params.push(e_param);
-    }
+    let lut_in_channels_num = lut_header[8];
+    let lut_out_channels_num = lut_header[9];
+    let lut_grid_pts_num = lut_header[10];
+    let lut_encoded_params = lut_header[12..48]
+        .chunks_exact(4)
+        .map(bytes_to_sf32)
+        .collect::<Result<Vec<S15Fixed16Number>, String>>()?;
 
     let lut_in_table_size: usize = 256 * <u8 as Into<usize>>::into(lut_in_channels_num);
-
-    let mut lut_in_table: Vec<u8> = Vec::with_capacity(lut_in_table_size);
-
-    for lut_in in icc_raw_data
-        .iter()
-        .take(idx + 47 + lut_in_table_size + 1)
-        .skip(idx + 48)
-    {
-        lut_in_table.push(*lut_in);
-    }
-
-    let lut_clut_table_size: usize =
-        (lut_grid_pts_num.pow(lut_in_channels_num.into()) * lut_out_channels_num).into();
-    let mut lut_clut_table: Vec<u8> = Vec::with_capacity(lut_clut_table_size);
-
-    for lut_clut in icc_raw_data
-        .iter()
-        .take(idx + 47 + lut_in_table_size + lut_clut_table_size + 1)
-        .skip(idx + 48 + lut_in_table_size)
-    {
-        lut_clut_table.push(*lut_clut);
-    }
-
     let lut_out_table_size: usize = 256 * <u8 as Into<usize>>::into(lut_out_channels_num);
+    let lut_clut_table_size =
+        match lut_clut_len(lut_grid_pts_num, lut_in_channels_num, lut_out_channels_num) {
+            Some(len) if len <= tag_size => len,
+            _ => {
+                return Err(format!(
+                    "Lut8 CLUT ({} points, {} channels) overflows the tag size ({} Bytes)",
+                    lut_grid_pts_num, lut_in_channels_num, tag_size
+                ))
+            }
+        };
 
-    let mut lut_out_table: Vec<u8> = Vec::with_capacity(lut_out_table_size);
+    let in_table_idx = idx + 48;
+    let clut_table_idx = in_table_idx + lut_in_table_size;
+    let out_table_idx = clut_table_idx + lut_clut_table_size;
 
-    for lut_out in icc_raw_data
-        .iter()
-        .take(tag_size)
-        .skip(idx + 48 + lut_in_table_size + lut_clut_table_size)
+    if out_table_idx + lut_out_table_size > idx + tag_size
+        || out_table_idx + lut_out_table_size > icc_raw_data.len()
     {
-        lut_out_table.push(*lut_out);
+        return Err(format!(
+            "Lut8 tables ({} Bytes) overflow the tag size ({} Bytes)",
+            out_table_idx + lut_out_table_size - idx,
+            tag_size
+        ));
     }
 
     let lut8: Lut8 = Lut8 {
@@ -495,79 +532,72 @@ fn read_lut8_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<Lu
         out_chans_num: lut_out_channels_num,
         grid_pts_num: lut_grid_pts_num,
         e_params: lut_encoded_params,
-        in_table: lut_in_table,
-        clut_table: lut_clut_table,
-        out_table: lut_out_table,
+        in_table: icc_raw_data[in_table_idx..clut_table_idx].to_vec(),
+        clut_table: icc_raw_data[clut_table_idx..out_table_idx].to_vec(),
+        out_table: icc_raw_data[out_table_idx..(out_table_idx + lut_out_table_size)].to_vec(),
     };
 
     Ok(lut8)
 }
 
-fn read_lut16_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<Lut16, String> {
-    let lut_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+fn read_u16_table(icc_raw_data: &[u8], idx: usize, len: usize) -> Result<Vec<u16>, String> {
+    let mut table: Vec<u16> = Vec::with_capacity(len);
 
-    let lut_in_channels_num = icc_raw_data[idx + 8];
-    let lut_out_channels_num = icc_raw_data[idx + 9];
-    let lut_grid_pts_num = icc_raw_data[idx + 10];
-    let mut lut_encoded_params: Vec<S15Fixed16Number> = Vec::with_capacity(9);
-
-    for e_param_idx in (12..=44).step_by(4) {
-        let e_param = bytes_to_sf32(&icc_raw_data[(idx + e_param_idx)..=(idx + e_param_idx + 3)])?;
-        lut_encoded_params.push(e_param);
-    }
-
-    let lut_in_entries_num: u16 = bytes_to_u16(&icc_raw_data[idx + 48..=idx + 49])?;
-
-    let lut_out_entries_num: u16 = bytes_to_u16(&icc_raw_data[idx + 50..=idx + 51])?;
-
-    let lut_in_table_size: usize =
-        (2 * lut_in_entries_num * <u8 as Into<u16>>::into(lut_in_channels_num)).into();
-
-    let mut lut_in_table: Vec<u16> = Vec::with_capacity(lut_in_table_size);
-
-    for table_idx in ((idx + 52)..=(idx + 49 + lut_in_table_size)).step_by(2) {
-        let table_val: u16 = match bytes_to_u16(&icc_raw_data[table_idx..=table_idx + 1]) {
-            Ok(u16) => u16,
-            Err(err) => return Err(err.to_string()),
+    for table_idx in (idx..(idx + 2 * len)).step_by(2) {
+        let table_val: u16 = match icc_raw_data.get(table_idx..=table_idx + 1) {
+            Some(bytes) => bytes_to_u16(bytes)?,
+            None => return Err(format!("Table value out of data range at {}", table_idx)),
         };
-        lut_in_table.push(table_val);
+        table.push(table_val);
     }
 
-    let lut_clut_table_size: usize = 2
-        * <u8 as Into<usize>>::into(lut_grid_pts_num).pow(lut_in_channels_num.into())
-        * <u8 as Into<usize>>::into(lut_out_channels_num);
-
-    let mut lut_clut_table: Vec<u16> = Vec::with_capacity(lut_clut_table_size);
+    Ok(table)
+}
 
-    for clut_idx in ((idx + 52 + lut_in_table_size)
-        ..=(idx + 51 + lut_in_table_size + lut_clut_table_size))
-        .step_by(2)
-    {
-        let clut_val: u16 = match bytes_to_u16(&icc_raw_data[clut_idx..=clut_idx + 1]) {
-            Ok(u16) => u16,
-            Err(err) => return Err(err.to_string()),
+fn read_lut16_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<Lut16, String> {
+    let lut_header = match icc_raw_data.get(idx..idx + 52) {
+        Some(header) if tag_size >= 52 => header,
+        _ => return Err("Lut16 header out of data range".to_string()),
+    };
+    let lut_sig = bytes_to_u32(&lut_header[0..=3])?;
+
+    let lut_in_channels_num = lut_header[8];
+    let lut_out_channels_num = lut_header[9];
+    let lut_grid_pts_num = lut_header[10];
+    let lut_encoded_params = lut_header[12..48]
+        .chunks_exact(4)
+        .map(bytes_to_sf32)
+        .collect::<Result<Vec<S15Fixed16Number>, String>>()?;
+
+    let lut_in_entries_num: u16 = bytes_to_u16(&lut_header[48..=49])?;
+
+    let lut_out_entries_num: u16 = bytes_to_u16(&lut_header[50..=51])?;
+
+    let lut_in_table_len: usize = <u16 as Into<usize>>::into(lut_in_entries_num)
+        * <u8 as Into<usize>>::into(lut_in_channels_num);
+    let lut_clut_table_len =
+        match lut_clut_len(lut_grid_pts_num, lut_in_channels_num, lut_out_channels_num) {
+            Some(len) if len <= tag_size / 2 => len,
+            _ => {
+                return Err(format!(
+                    "Lut16 CLUT ({} points, {} channels) overflows the tag size ({} Bytes)",
+                    lut_grid_pts_num, lut_in_channels_num, tag_size
+                ))
+            }
         };
-        lut_clut_table.push(clut_val);
-    }
-
-    let lut_out_table_size: usize = 2
-        * <u8 as Into<usize>>::into(lut_out_channels_num)
-        * <u16 as Into<usize>>::into(lut_out_entries_num);
+    let lut_out_table_len: usize = <u16 as Into<usize>>::into(lut_out_entries_num)
+        * <u8 as Into<usize>>::into(lut_out_channels_num);
 
-    let mut lut_out_table: Vec<u16> = Vec::with_capacity(lut_out_table_size);
+    let in_table_idx = idx + 52;
+    let clut_table_idx = in_table_idx + 2 * lut_in_table_len;
+    let out_table_idx = clut_table_idx + 2 * lut_clut_table_len;
 
-    //println!("TAG size : {}", tag_size);
-    //println!(
-    //    "SIZE : {}",
-    //    (idx + 52 + lut_in_table_size + lut_clut_table_size)
-    //);
-    //
-    for table_idx in ((idx + 52 + lut_in_table_size + lut_clut_table_size)..tag_size).step_by(2) {
-        let table_val: u16 = match bytes_to_u16(&icc_raw_data[table_idx..=table_idx + 1]) {
-            Ok(u16) => u16,
-            Err(err) => return Err(err.to_string()),
-        };
-        lut_out_table.push(table_val);
+    if out_table_idx + 2 * lut_out_table_len > idx + tag_size {
+        return Err(format!(
+            "Lut16 tables ({} Bytes) overflow the tag size ({} Bytes)",
+            out_table_idx + 2 * lut_out_table_len - idx,
+            tag_size
+        ));
     }
 
     let lut16: Lut16 = Lut16 {
@@ -578,9 +608,9 @@ fn read_lut16_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<L
         e_params: lut_encoded_params,
         in_table_entries_num: lut_in_entries_num,
         out_table_entries_num: lut_out_entries_num,
-        in_table: lut_in_table,
-        clut_table: lut_clut_table,
-        out_table: lut_out_table,
+        in_table: read_u16_table(icc_raw_data, in_table_idx, lut_in_table_len)?,
+        clut_table: read_u16_table(icc_raw_data, clut_table_idx, lut_clut_table_len)?,
+        out_table: read_u16_table(icc_raw_data, out_table_idx, lut_out_table_len)?,
     };
 
     Ok(lut16)
@@ -589,8 +619,10 @@ fn read_lut16_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<L
 //pub fn read_a2b_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result<A2B, String> {}
 
 pub fn read_curve_type(icc_raw_data: &[u8], idx: usize) -> Result<Curve, String> {
-    let n_entries_u32: u32 = bytes_to_u32(&icc_raw_data[idx + 8..=idx + 11])?;
-    let n_entries: usize = n_entries_u32.try_into().unwrap();
+    let n_entries = match icc_raw_data.get(idx + 8..idx + 12) {
+        Some(range) => bytes_u32_usize(range)?,
+        None => return Err("Curve header out of data range".to_string()),
+    };
 
     let mut curve = Curve {
         identity: false,
@@ -602,18 +634,16 @@ pub fn read_curve_type(icc_raw_data: &[u8], idx: usize) -> Result<Curve, String>
         0 => {
             curve.identity = true;
         }
-        1 => {
-            let gamma_val = bytes_to_uf8(&icc_raw_data[idx + 12..=idx + 13])?;
-            curve.gamma = Some(gamma_val);
-        }
+        1 => match icc_raw_data.get(idx + 12..idx + 14) {
+            Some(range) => curve.gamma = Some(bytes_to_uf8(range)?),
+            None => return Err("Curve gamma out of data range".to_string()),
+        },
         n if n > 1 => {
-            let mut curve_arr: Vec<u16> = Vec::with_capacity(n_entries);
-            for entry in (0..n_entries).step_by(2) {
-                let entry: u16 =
-                    bytes_to_u16(&icc_raw_data[idx + 12 + entry..=idx + 12 + entry + 1])?;
-                curve_arr.push(entry);
+            // The entry count comes from the file, checked before allocating the table
+            if icc_raw_data.len() < idx + 12 + 2 * n_entries {
+                return Err("Curve entries out of data range".to_string());
             }
-            curve.curve = Some(curve_arr);
+            curve.curve = Some(read_u16_table(icc_raw_data, idx + 12, n_entries)?);
         }
         _ => return Err("The number of curve entries is not in possible values".to_string()),
     };
@@ -672,78 +702,1054 @@ pub fn read_b2a_type(icc_raw_data: &[u8], idx: usize, tag_size: usize) -> Result
 }
 
 pub fn read_chrm_type(icc_raw_data: &[u8], idx: usize) -> Result<Chrm, String> {
-    let chrm_chan_num: u16 = bytes_to_u16(&icc_raw_data[(idx + 8)..=(idx + 9)])?;
-    let chrm_chan_num_usize: usize = chrm_chan_num.into();
-    let phs_col_type: u16 = bytes_to_u16(&icc_raw_data[(idx + 10)..=(idx + 11)])?;
-    let mut phs_col_type_str: String = "Unknown".to_string();
+    let header = icc_raw_data
+        .get(idx + 8..idx + 12)
+        .ok_or("chrm tag too short")?;
+    let chrm_chan_num = bytes_u16_usize(&header[0..=1])?;
+    let phs_col_code: u16 = bytes_to_u16(&header[2..=3])?;
+
+    // 0 is unknown, the defined codes index ICC_CHRM_TYPE from 1
+    let phs_col_type = match usize::from(phs_col_code).checked_sub(1) {
+        None => "Unknown".to_string(),
+        Some(code_idx) => match ICC_CHRM_TYPE.get(code_idx) {
+            Some(chrm_type) => chrm_type.0.to_string(),
+            None => format!("Unknown (0x{:04X})", phs_col_code),
+        },
+    };
+
+    let channels = icc_raw_data
+        .get(idx + 12..idx + 12 + chrm_chan_num * 8)
+        .ok_or(format!("chrm tag too short for {} channels", chrm_chan_num))?
+        .chunks_exact(8)
+        .map(|chan| Ok((bytes_to_uf16(&chan[0..4])?, bytes_to_uf16(&chan[4..8])?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    if channels.is_empty() {
+        return Err("No channels found in chrm".to_string());
+    };
+
+    Ok(Chrm {
+        phs_col_type,
+        phs_col_code,
+        channels,
+    })
+}
+
+pub fn read_mmod_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<MmodType, String> {
+    let mmod_tag = match icc_raw_data.get(idx..idx + size) {
+        Some(mmod_tag) if size >= 24 => mmod_tag,
+        _ => return Err("mmod tag out of data range".to_string()),
+    };
+    let mmod_sig = bytes_to_u32(&mmod_tag[0..=3])?;
+
+    if mmod_sig != 0x6D6D6F64 {
+        return Err(format!(
+            "Bad tag signature for mmod (0x6D6D6F64) found {}",
+            mmod_sig
+        ));
+    };
+
+    Ok(MmodType {
+        manufacturer: bytes_to_u32(&mmod_tag[8..=11])?,
+        model: bytes_to_u32(&mmod_tag[12..=15])?,
+        serial_number: bytes_to_u32(&mmod_tag[16..=19])?,
+        date: bytes_to_u32(&mmod_tag[20..=23])?,
+        reserved: mmod_tag[24..].to_vec(),
+    })
+}
+
+pub fn read_para_type(icc_raw_data: &[u8], idx: usize) -> Result<ParametricCurve, String> {
+    let para_header = match icc_raw_data.get(idx..idx + 12) {
+        Some(header) => header,
+        None => return Err("Parametric curve header out of data range".to_string()),
+    };
+    let para_sig = bytes_to_u32(&para_header[0..=3])?;
+
+    if para_sig != 0x70617261 {
+        return Err(format!(
+            "Bad tag signature for para (0x70617261) found {:X}",
+            para_sig
+        ));
+    };
+
+    let function_type: u16 = bytes_to_u16(&para_header[8..=9])?;
+    let params_num: usize = match function_type {
+        0 => 1,
+        1 => 3,
+        2 => 4,
+        3 => 5,
+        4 => 7,
+        _ => {
+            return Err(format!(
+                "Unknown parametric function type {}",
+                function_type
+            ))
+        }
+    };
+
+    let mut params: Vec<S15Fixed16Number> = Vec::with_capacity(params_num);
+    for param_idx in (0..(params_num * 4)).step_by(4) {
+        let param_range = match icc_raw_data.get(idx + 12 + param_idx..idx + 16 + param_idx) {
+            Some(range) => range,
+            None => return Err("Parametric curve parameters out of data range".to_string()),
+        };
+        params.push(bytes_to_sf32(param_range)?);
+    }
+
+    Ok(ParametricCurve {
+        function_type,
+        params,
+    })
+}
+
+// Returns the curve and the number of bytes it uses (without padding)
+pub fn read_lut_curve(icc_raw_data: &[u8], idx: usize) -> Result<(LutCurve, usize), String> {
+    let curve_sig = match icc_raw_data.get(idx..idx + 4) {
+        Some(range) => bytes_to_u32(range)?,
+        None => return Err("LUT curve out of data range".to_string()),
+    };
+
+    match curve_sig {
+        0x63757276 => {
+            // 'curv'
+            let curve = read_curve_type(icc_raw_data, idx)?;
+            let size = 12
+                + 2 * curve.curve.as_ref().map_or(0, |c| c.len())
+                + if curve.gamma.is_some() { 2 } else { 0 };
+            Ok((LutCurve::Curv(curve), size))
+        }
+        0x70617261 => {
+            // 'para'
+            let para = read_para_type(icc_raw_data, idx)?;
+            let size = 12 + 4 * para.params.len();
+            Ok((LutCurve::Para(para), size))
+        }
+        _ => Err(format!("Unknown curve type signature (0x{:X})", curve_sig)),
+    }
+}
+
+fn read_lut_curves(
+    icc_raw_data: &[u8],
+    idx: usize,
+    curves_num: u8,
+) -> Result<Vec<LutCurve>, String> {
+    let mut curves: Vec<LutCurve> = Vec::with_capacity(curves_num.into());
+    let mut curve_idx = idx;
+
+    for _ in 0..curves_num {
+        let (curve, size) = read_lut_curve(icc_raw_data, curve_idx)?;
+        curves.push(curve);
+        curve_idx += size.div_ceil(4) * 4;
+    }
+
+    Ok(curves)
+}
+
+fn read_clut(icc_raw_data: &[u8], idx: usize, in_chans: u8, out_chans: u8) -> Result<Clut, String> {
+    let clut_header = match icc_raw_data.get(idx..idx + 20) {
+        Some(header) => header,
+        None => return Err("CLUT header out of data range".to_string()),
+    };
+    // 16 grid point counts at most, unused ones are 0
+    let grid_pts: Vec<u8> = match clut_header.get(..usize::from(in_chans)) {
+        Some(grid_pts) if in_chans <= 16 => grid_pts.to_vec(),
+        _ => return Err(format!("Bad CLUT input channels number {}", in_chans)),
+    };
+    let precision = clut_header[16];
+    let values_num = match grid_pts
+        .iter()
+        .try_fold(usize::from(out_chans), |num, pts| {
+            num.checked_mul(usize::from(*pts))
+        }) {
+        Some(values_num) => values_num,
+        None => return Err("CLUT values out of data range".to_string()),
+    };
+
+    // Values count from the file, checked against the data left before reading
+    let data_left = icc_raw_data.len().saturating_sub(idx + 20);
+    let table = match precision {
+        1 | 2 if data_left / usize::from(precision) < values_num => {
+            return Err("CLUT values out of data range".to_string())
+        }
+        1 => icc_raw_data[idx + 20..idx + 20 + values_num]
+            .iter()
+            .map(|val| u16::from(*val))
+            .collect(),
+        2 => read_u16_table(icc_raw_data, idx + 20, values_num)?,
+        _ => return Err(format!("Bad CLUT precision {}", precision)),
+    };
+
+    Ok(Clut {
+        grid_pts,
+        precision,
+        table,
+    })
+}
+
+pub fn read_lutab_type(icc_raw_data: &[u8], idx: usize) -> Result<LutAB, String> {
+    let lut_header = match icc_raw_data.get(idx..idx + 32) {
+        Some(header) => header,
+        None => return Err("mAB/mBA header out of data range".to_string()),
+    };
+    let lut_sig = bytes_to_u32(&lut_header[0..=3])?;
+
+    let in_chans_num = lut_header[8];
+    let out_chans_num = lut_header[9];
+
+    // mAB : A curves are on the input side, mBA : B curves are on the input side
+    let (a_chans, b_chans) = match lut_sig {
+        0x6D414220 => (in_chans_num, out_chans_num),
+        0x6D424120 => (out_chans_num, in_chans_num),
+        _ => {
+            return Err(format!(
+                "Bad tag signature for mAB/mBA found 0x{:X}",
+                lut_sig
+            ))
+        }
+    };
+
+    let b_offset = bytes_u32_usize(&lut_header[12..=15])?;
+    let matrix_offset = bytes_u32_usize(&lut_header[16..=19])?;
+    let m_offset = bytes_u32_usize(&lut_header[20..=23])?;
+    let clut_offset = bytes_u32_usize(&lut_header[24..=27])?;
+    let a_offset = bytes_u32_usize(&lut_header[28..=31])?;
+
+    if b_offset == 0 {
+        return Err("mAB/mBA tag without B curves".to_string());
+    }
+
+    let b_curves = read_lut_curves(icc_raw_data, idx + b_offset, b_chans)?;
+
+    let matrix = match matrix_offset {
+        0 => None,
+        offset => {
+            let matrix_range = match icc_raw_data.get(idx + offset..idx + offset + 48) {
+                Some(range) => range,
+                None => return Err("mAB/mBA matrix out of data range".to_string()),
+            };
+            Some(
+                matrix_range
+                    .chunks_exact(4)
+                    .map(bytes_to_sf32)
+                    .collect::<Result<Vec<S15Fixed16Number>, String>>()?,
+            )
+        }
+    };
+
+    let m_curves = match m_offset {
+        0 => None,
+        offset => Some(read_lut_curves(icc_raw_data, idx + offset, b_chans)?),
+    };
+
+    let clut = match clut_offset {
+        0 => None,
+        offset => Some(read_clut(
+            icc_raw_data,
+            idx + offset,
+            in_chans_num,
+            out_chans_num,
+        )?),
+    };
+
+    let a_curves = match a_offset {
+        0 => None,
+        offset => Some(read_lut_curves(icc_raw_data, idx + offset, a_chans)?),
+    };
+
+    Ok(LutAB {
+        lut_sig,
+        in_chans_num,
+        out_chans_num,
+        b_curves,
+        matrix,
+        m_curves,
+        clut,
+        a_curves,
+    })
+}
+
+pub fn read_mluc_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<MultiLocalizedText, String> {
+    let mluc_header = match icc_raw_data.get(idx..idx + 16) {
+        Some(header) if size >= 16 => header,
+        _ => return Err("mluc tag out of data range".to_string()),
+    };
+    let mluc_sig = bytes_to_u32(&mluc_header[0..=3])?;
+
+    if mluc_sig != 0x6D6C7563 {
+        return Err(format!(
+            "Bad tag signature for mluc (0x6D6C7563) found {:X}",
+            mluc_sig
+        ));
+    };
+
+    // The counts come from the file : every record takes at least 12 bytes of the tag
+    let records_num = bytes_u32_usize(&mluc_header[8..=11])?;
+    let record_size = bytes_u32_usize(&mluc_header[12..=15])?;
+    if records_num > (size - 16) / 12 {
+        return Err(format!(
+            "{} mluc records overflow the tag size ({} Bytes)",
+            records_num, size
+        ));
+    }
+    let mut records: Vec<MlucRecord> = vec![];
+
+    for record_num in 0..records_num {
+        let record_idx = idx + 16 + record_num * record_size;
+        let record = match icc_raw_data.get(record_idx..record_idx + 12) {
+            Some(record) => record,
+            None => return Err("mluc record out of data range".to_string()),
+        };
+
+        let lang_code = String::from_utf8_lossy(&record[0..=1]).to_string();
+        let country_code = String::from_utf8_lossy(&record[2..=3]).to_string();
+        let str_size = bytes_u32_usize(&record[4..=7])?;
+        let str_offset = bytes_u32_usize(&record[8..=11])?;
+
+        let str_range = match icc_raw_data.get((idx + str_offset)..(idx + str_offset + str_size)) {
+            Some(range) => range,
+            None => return Err("mluc string out of data range".to_string()),
+        };
+        let utf16: Vec<u16> = str_range
+            .chunks_exact(2)
+            .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+            .collect();
+        let string = match String::from_utf16(&utf16) {
+            Ok(string) => string,
+            Err(err) => return Err(err.to_string()),
+        };
+
+        records.push((lang_code, country_code, string));
+    }
 
-    if phs_col_type > 0 {
-        phs_col_type_str = ICC_CHRM_TYPE[<u16 as Into<usize>>::into(phs_col_type) - 1]
-            .0
-            .to_string();
+    Ok(MultiLocalizedText { records })
+}
+
+fn dict_range(dict_tag: &[u8], offset: usize, size: usize) -> Result<Option<Vec<u8>>, String> {
+    if offset == 0 {
+        return Ok(None);
+    }
+
+    match dict_tag.get(offset..(offset + size)) {
+        Some(range) => Ok(Some(range.to_vec())),
+        None => Err("dict string out of tag range".to_string()),
+    }
+}
+
+pub fn read_dict_entries(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<DictType, String> {
+    let dict_tag = match icc_raw_data.get(idx..(idx + size)) {
+        Some(dict_tag) if size >= 16 => dict_tag,
+        _ => return Err("dict tag out of data range".to_string()),
+    };
+    let dict_size = bytes_u32_usize(&dict_tag[8..=11])?;
+    let element_size = bytes_u32_usize(&dict_tag[12..=15])?;
+
+    if ![16, 24, 32].contains(&element_size) {
+        return Err(format!("Bad dict element size {}", element_size));
+    }
+
+    let mut entries: Vec<DictEntry> = Vec::with_capacity(dict_size.min(1024));
+
+    for entry_num in 0..dict_size {
+        let entry_idx = 16 + (element_size * entry_num);
+        let entry = match dict_tag.get(entry_idx..(entry_idx + element_size)) {
+            Some(entry) => entry,
+            None => return Err(format!("dict entry {} out of tag range", entry_num + 1)),
+        };
+        let key_value_desc = extract_key_value(entry, element_size)?;
+
+        let name = dict_range(dict_tag, key_value_desc[0], key_value_desc[1])?;
+        let value = dict_range(dict_tag, key_value_desc[2], key_value_desc[3])?;
+        let display_name = match key_value_desc.get(4..=5) {
+            Some(desc) => dict_range(dict_tag, desc[0], desc[1])?,
+            None => None,
+        };
+        let display_value = match key_value_desc.get(6..=7) {
+            Some(desc) => dict_range(dict_tag, desc[0], desc[1])?,
+            None => None,
+        };
+
+        entries.push(DictEntry {
+            name: name.unwrap_or_default(),
+            value,
+            display_name,
+            display_value,
+        });
+    }
+
+    Ok(DictType {
+        element_size,
+        entries,
+    })
+}
 
-        if chrm_chan_num != 3 {
+pub fn read_vcgt_formula(icc_raw_data: &[u8], idx: usize) -> Result<VcgtFormula, String> {
+    // Gamma type then gamma, minimum and maximum of each channel
+    let vcgt_tag = match icc_raw_data.get(idx..idx + 48) {
+        Some(vcgt_tag) => vcgt_tag,
+        None => return Err("vcgt formula out of data range".to_string()),
+    };
+    let gamma_type = bytes_to_u32(&vcgt_tag[8..=11])?;
+
+    if gamma_type != 1 {
+        return Err(format!("vcgt is not a formula (gamma type {})", gamma_type));
+    }
+
+    let mut formula: VcgtFormula = [S15Fixed16Number::from_num(0); 9];
+    for (param, bytes) in formula.iter_mut().zip(vcgt_tag[12..].chunks_exact(4)) {
+        *param = bytes_to_sf32(bytes)?;
+    }
+
+    Ok(formula)
+}
+
+fn read_ascii32(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .split('\0')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+pub fn read_ncl2_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<NamedColors, String> {
+    let ncl2_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if ncl2_sig != 0x6E636C32 {
+        return Err(format!(
+            "Bad tag signature for ncl2 (0x6E636C32) found {:X}",
+            ncl2_sig
+        ));
+    };
+
+    let header = match icc_raw_data.get(idx..idx + 84) {
+        Some(header) => header,
+        None => return Err("ncl2 header out of data range".to_string()),
+    };
+    let flags = bytes_to_u32(&header[8..=11])?;
+    let colors_num = bytes_u32_usize(&header[12..=15])?;
+    let device_coords_num = bytes_u32_usize(&header[16..=19])?;
+    let prefix = read_ascii32(&header[20..52]);
+    let suffix = read_ascii32(&header[52..84]);
+
+    // Root name (32 Bytes), 3 PCS and n device coordinates
+    let record_size = 38 + 2 * device_coords_num;
+    if 84 + colors_num * record_size > size {
+        return Err(format!(
+            "{} named colors ({} Bytes each) overflow the tag size ({} Bytes)",
+            colors_num, record_size, size
+        ));
+    }
+
+    let mut colors: Vec<NamedColor> = Vec::with_capacity(colors_num);
+    for color_num in 0..colors_num {
+        let record_idx = idx + 84 + color_num * record_size;
+        let record = match icc_raw_data.get(record_idx..record_idx + record_size) {
+            Some(record) => record,
+            None => return Err("ncl2 color out of data range".to_string()),
+        };
+        let coords: Vec<u16> = record[32..]
+            .chunks_exact(2)
+            .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+            .collect();
+
+        colors.push(NamedColor {
+            name: read_ascii32(&record[0..32]),
+            pcs: [coords[0], coords[1], coords[2]],
+            device: coords[3..].to_vec(),
+        });
+    }
+
+    Ok(NamedColors {
+        flags,
+        prefix,
+        suffix,
+        device_coords_num,
+        colors,
+    })
+}
+
+// Size of a desc or mluc tag embedded in a pseq, which is not given by the structure
+fn embedded_text_size(icc_raw_data: &[u8], idx: usize) -> Result<usize, String> {
+    let header = match icc_raw_data.get(idx..idx + 16) {
+        Some(header) => header,
+        None => return Err("Embedded text tag out of data range".to_string()),
+    };
+
+    match bytes_to_u32(&header[0..=3])? {
+        // 'desc'
+        0x64657363 => {
+            let ascii_count = bytes_u32_usize(&header[8..=11])?;
+            let unicode_idx = idx + 12 + ascii_count;
+            let unicode_count = match icc_raw_data.get(unicode_idx + 4..unicode_idx + 8) {
+                Some(count) => bytes_u32_usize(count)?,
+                None => return Err("Embedded desc out of data range".to_string()),
+            };
+            Ok(12 + ascii_count + 8 + 2 * unicode_count + 3 + 67)
+        }
+        // 'mluc'
+        0x6D6C7563 => {
+            let records_num = bytes_u32_usize(&header[8..=11])?;
+            let record_size = bytes_u32_usize(&header[12..=15])?;
+            let data_left = icc_raw_data.len() - idx;
+            if records_num > (data_left - 16) / 12 {
+                return Err(format!(
+                    "{} embedded mluc records overflow the data ({} Bytes)",
+                    records_num, data_left
+                ));
+            }
+            let mut size = 16 + records_num * record_size;
+            for record_num in 0..records_num {
+                let record_idx = idx + 16 + record_num * record_size;
+                let record = match icc_raw_data.get(record_idx + 4..record_idx + 12) {
+                    Some(record) => record,
+                    None => return Err("Embedded mluc record out of data range".to_string()),
+                };
+                size =
+                    size.max(bytes_u32_usize(&record[4..=7])? + bytes_u32_usize(&record[0..=3])?);
+            }
+            Ok(size)
+        }
+        sig => Err(format!(
+            "Embedded text tag must be desc or mluc, found {}",
+            sig_to_str(sig)
+        )),
+    }
+}
+
+fn read_embedded_text(icc_raw_data: &[u8], idx: usize) -> Result<(TextTag, usize), String> {
+    let size = embedded_text_size(icc_raw_data, idx)?;
+    let text = match bytes_to_u32(&icc_raw_data[idx..=idx + 3])? {
+        0x64657363 => TextTag::Desc(read_desc_type(icc_raw_data, idx, size)?),
+        _ => TextTag::Mluc(read_mluc_type(icc_raw_data, idx, size)?),
+    };
+
+    Ok((text, size))
+}
+
+pub fn read_pseq_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<Vec<ProfileDescription>, String> {
+    let pseq_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if pseq_sig != 0x70736571 {
+        return Err(format!(
+            "Bad tag signature for pseq (0x70736571) found {:X}",
+            pseq_sig
+        ));
+    };
+
+    let descs_num = match icc_raw_data.get(idx + 8..idx + 12) {
+        Some(count) => bytes_u32_usize(count)?,
+        None => return Err("pseq count out of data range".to_string()),
+    };
+    let tag_end = (idx + size).min(icc_raw_data.len());
+    let mut descs: Vec<ProfileDescription> = Vec::with_capacity(descs_num.min(size / 20));
+    let mut pos = idx + 12;
+
+    for desc_num in 0..descs_num {
+        let desc = match icc_raw_data.get(pos..pos + 20) {
+            Some(desc) if pos + 20 <= tag_end => desc,
+            _ => {
+                return Err(format!(
+                    "pseq description {} out of tag range",
+                    desc_num + 1
+                ))
+            }
+        };
+        let manufacturer = bytes_to_u32(&desc[0..=3])?;
+        let model = bytes_to_u32(&desc[4..=7])?;
+        let attributes = u64::from_be_bytes(desc[8..16].try_into().unwrap_or_default());
+        let technology = bytes_to_u32(&desc[16..=19])?;
+        pos += 20;
+
+        let (manufacturer_desc, manufacturer_size) = read_embedded_text(icc_raw_data, pos)?;
+        pos += manufacturer_size;
+        let (model_desc, model_size) = read_embedded_text(icc_raw_data, pos)?;
+        pos += model_size;
+        if pos > tag_end {
             return Err(format!(
-                "The number of channel ({}) not match the colorant type ({})",
-                chrm_chan_num, phs_col_type,
+                "pseq description {} overflows the tag",
+                desc_num + 1
             ));
         }
+
+        descs.push(ProfileDescription {
+            manufacturer,
+            model,
+            attributes,
+            technology,
+            manufacturer_desc,
+            model_desc,
+        });
+    }
+
+    Ok(descs)
+}
+
+pub fn read_psid_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<Vec<ProfileIdentifier>, String> {
+    let psid_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if psid_sig != 0x70736964 {
+        return Err(format!(
+            "Bad tag signature for psid (0x70736964) found {:X}",
+            psid_sig
+        ));
     };
 
-    let mut chrm_channels: Vec<(U16Fixed16Number, U16Fixed16Number)> =
-        Vec::with_capacity(chrm_chan_num.into());
+    let ids_num = match icc_raw_data.get(idx + 8..idx + 12) {
+        Some(count) => bytes_u32_usize(count)?,
+        None => return Err("psid count out of data range".to_string()),
+    };
+    if 12 + ids_num * 8 > size {
+        return Err(format!(
+            "{} psid positions overflow the tag size ({} Bytes)",
+            ids_num, size
+        ));
+    }
 
-    for chan_idx in (0..(chrm_chan_num_usize * 8)).step_by(8) {
-        let x_slice = &icc_raw_data[(idx + 12 + chan_idx)..=(idx + 12 + chan_idx + 3)];
-        let y_slice = &icc_raw_data[(idx + 12 + chan_idx + 4)..=(idx + 12 + chan_idx + 7)];
-        let x = bytes_to_uf16(x_slice)?;
-        let y = bytes_to_uf16(y_slice)?;
+    let mut ids: Vec<ProfileIdentifier> = Vec::with_capacity(ids_num);
+    for id_num in 0..ids_num {
+        let position_idx = idx + 12 + id_num * 8;
+        let offset = bytes_u32_usize(&icc_raw_data[position_idx..=position_idx + 3])?;
+        let elem_size = bytes_u32_usize(&icc_raw_data[position_idx + 4..=position_idx + 7])?;
+        if offset + elem_size > size || elem_size < 16 {
+            return Err(format!("psid element {} out of tag range", id_num + 1));
+        }
 
-        chrm_channels.push((x, y));
+        let elem_idx = idx + offset;
+        let id = match icc_raw_data.get(elem_idx..elem_idx + 16) {
+            Some(id) => id.try_into().unwrap_or_default(),
+            None => return Err("psid profile ID out of data range".to_string()),
+        };
+        ids.push(ProfileIdentifier {
+            id,
+            desc: read_mluc_type(icc_raw_data, elem_idx + 16, elem_size - 16)?,
+        });
     }
 
-    if chrm_channels.is_empty() {
-        return Err("No channels found in chrm".to_string());
+    Ok(ids)
+}
+
+fn read_sf32_xyz(bytes: &[u8]) -> Result<[f64; 3], String> {
+    let mut xyz = [0.0; 3];
+    for (coord, chunk) in xyz.iter_mut().zip(bytes.chunks_exact(4)) {
+        *coord = bytes_to_sf32(chunk)?.to_num::<f64>();
+    }
+    Ok(xyz)
+}
+
+pub fn read_meas_type(icc_raw_data: &[u8], idx: usize) -> Result<Measurement, String> {
+    let meas_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if meas_sig != 0x6D656173 {
+        return Err(format!(
+            "Bad tag signature for meas (0x6D656173) found {:X}",
+            meas_sig
+        ));
     };
 
-    let chan_1 = Some(chrm_channels[0]);
+    let meas = match icc_raw_data.get(idx..idx + 36) {
+        Some(meas) => meas,
+        None => return Err("meas tag out of data range".to_string()),
+    };
 
-    let chan_2 = if chrm_channels.len() > 1 {
-        Some(chrm_channels[1])
-    } else {
-        None
+    Ok(Measurement {
+        observer: StandardObserver::from_code(bytes_to_u32(&meas[8..=11])?),
+        backing: read_sf32_xyz(&meas[12..24])?,
+        geometry: MeasurementGeometry::from_code(bytes_to_u32(&meas[24..=27])?),
+        flare: bytes_to_uf16(&meas[28..=31])?.to_num::<f64>(),
+        illuminant: StandardIlluminant::from_code(bytes_to_u32(&meas[32..=35])?),
+    })
+}
+
+pub fn read_view_type(icc_raw_data: &[u8], idx: usize) -> Result<ViewingConditions, String> {
+    let view_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if view_sig != 0x76696577 {
+        return Err(format!(
+            "Bad tag signature for view (0x76696577) found {:X}",
+            view_sig
+        ));
     };
-    let chan_3 = if chrm_channels.len() > 2 {
-        Some(chrm_channels[2])
-    } else {
-        None
+
+    let view = match icc_raw_data.get(idx..idx + 36) {
+        Some(view) => view,
+        None => return Err("view tag out of data range".to_string()),
     };
 
-    let chrm = Chrm {
-        phs_col_type: phs_col_type_str,
-        chans_num: chrm_chan_num,
-        chan_1,
-        chan_2,
-        chan_3,
+    Ok(ViewingConditions {
+        illuminant: read_sf32_xyz(&view[8..20])?,
+        surround: read_sf32_xyz(&view[20..32])?,
+        illuminant_type: StandardIlluminant::from_code(bytes_to_u32(&view[32..=35])?),
+    })
+}
+
+pub fn read_sig_type(icc_raw_data: &[u8], idx: usize) -> Result<u32, String> {
+    let sig_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if sig_sig != 0x73696720 {
+        return Err(format!(
+            "Bad tag signature for sig (0x73696720) found {:X}",
+            sig_sig
+        ));
     };
 
-    Ok(chrm)
+    match icc_raw_data.get(idx + 8..idx + 12) {
+        Some(sig) => bytes_to_u32(sig),
+        None => Err("sig tag out of data range".to_string()),
+    }
 }
 
-pub fn read_mmod_type(icc_raw_data: &[u8], idx: usize) -> Result<MmodType, String> {
-    let mmod_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+// colorantTableType, used by both the clrt and clot tags
+pub fn read_clrt_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<Vec<Colorant>, String> {
+    let clrt_sig = match icc_raw_data.get(idx..idx + 4) {
+        Some(sig) => bytes_to_u32(sig)?,
+        None => return Err("clrt tag out of data range".to_string()),
+    };
 
-    if mmod_sig != 0x6D6D6F64 {
+    if clrt_sig != 0x636C7274 {
         return Err(format!(
-            "Bad tag signature for mmod (0x6D6D6F64) found {}",
-            mmod_sig
+            "Bad tag signature for clrt (0x636C7274) found {:X}",
+            clrt_sig
+        ));
+    };
+
+    let colorants_num = match icc_raw_data.get(idx + 8..idx + 12) {
+        Some(count) => bytes_u32_usize(count)?,
+        None => return Err("clrt count out of data range".to_string()),
+    };
+    if 12 + colorants_num * 38 > size {
+        return Err(format!(
+            "{} colorants overflow the tag size ({} Bytes)",
+            colorants_num, size
+        ));
+    }
+
+    let mut colorants: Vec<Colorant> = Vec::with_capacity(colorants_num);
+    for colorant_num in 0..colorants_num {
+        let colorant_idx = idx + 12 + colorant_num * 38;
+        let colorant = match icc_raw_data.get(colorant_idx..colorant_idx + 38) {
+            Some(colorant) => colorant,
+            None => return Err("clrt colorant out of data range".to_string()),
+        };
+        colorants.push(Colorant {
+            name: read_ascii32(&colorant[0..32]),
+            pcs: std::array::from_fn(|coord| {
+                u16::from_be_bytes([colorant[32 + 2 * coord], colorant[33 + 2 * coord]])
+            }),
+        });
+    }
+
+    Ok(colorants)
+}
+
+// Laydown order : colorant numbers (as in clrt) from the first printed to the last
+pub fn read_clro_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<Vec<u8>, String> {
+    let clro_sig = match icc_raw_data.get(idx..idx + 4) {
+        Some(sig) => bytes_to_u32(sig)?,
+        None => return Err("clro tag out of data range".to_string()),
+    };
+
+    if clro_sig != 0x636C726F {
+        return Err(format!(
+            "Bad tag signature for clro (0x636C726F) found {:X}",
+            clro_sig
+        ));
+    };
+
+    let colorants_num = match icc_raw_data.get(idx + 8..idx + 12) {
+        Some(count) => bytes_u32_usize(count)?,
+        None => return Err("clro count out of data range".to_string()),
+    };
+    if 12 + colorants_num > size {
+        return Err(format!(
+            "{} colorants overflow the tag size ({} Bytes)",
+            colorants_num, size
+        ));
+    }
+
+    match icc_raw_data.get(idx + 12..idx + 12 + colorants_num) {
+        Some(order) => Ok(order.to_vec()),
+        None => Err("clro order out of data range".to_string()),
+    }
+}
+
+fn read_response_curve(
+    icc_raw_data: &[u8],
+    idx: usize,
+    channels_num: usize,
+    tag_end: usize,
+) -> Result<ResponseCurve, String> {
+    let header_size = 4 + 16 * channels_num;
+    let header = match icc_raw_data.get(idx..idx + header_size) {
+        Some(header) if idx + header_size <= tag_end => header,
+        _ => return Err("rcs2 curve out of tag range".to_string()),
+    };
+    let unit = bytes_to_u32(&header[0..=3])?;
+    let counts = header[4..4 + 4 * channels_num]
+        .chunks_exact(4)
+        .map(bytes_u32_usize)
+        .collect::<Result<Vec<usize>, String>>()?;
+    let max_colorant = header[4 + 4 * channels_num..]
+        .chunks_exact(12)
+        .map(read_sf32_xyz)
+        .collect::<Result<Vec<[f64; 3]>, String>>()?;
+
+    let mut pos = idx + header_size;
+    let mut responses: Vec<Vec<(u16, f64)>> = Vec::with_capacity(channels_num);
+    for count in counts {
+        let values = match icc_raw_data.get(pos..pos + 8 * count) {
+            Some(values) if pos + 8 * count <= tag_end => values,
+            _ => return Err("rcs2 response values out of tag range".to_string()),
+        };
+        responses.push(
+            values
+                .chunks_exact(8)
+                .map(|value| {
+                    Ok((
+                        bytes_to_u16(&value[0..=1])?,
+                        bytes_to_sf32(&value[4..=7])?.to_num::<f64>(),
+                    ))
+                })
+                .collect::<Result<Vec<(u16, f64)>, String>>()?,
+        );
+        pos += 8 * count;
+    }
+
+    Ok(ResponseCurve {
+        unit,
+        max_colorant,
+        responses,
+    })
+}
+
+pub fn read_rcs2_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<ResponseCurveSet, String> {
+    let rcs2_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if rcs2_sig != 0x72637332 {
+        return Err(format!(
+            "Bad tag signature for rcs2 (0x72637332) found {:X}",
+            rcs2_sig
+        ));
+    };
+
+    let header = match icc_raw_data.get(idx..idx + 12) {
+        Some(header) => header,
+        None => return Err("rcs2 header out of data range".to_string()),
+    };
+    let channels_num = bytes_to_u16(&header[8..=9])?;
+    let curves_num = bytes_u16_usize(&header[10..=11])?;
+    let tag_end = (idx + size).min(icc_raw_data.len());
+    if 12 + 4 * curves_num > size {
+        return Err(format!(
+            "{} curve offsets overflow the tag size ({} Bytes)",
+            curves_num, size
+        ));
+    }
+
+    let mut curves: Vec<ResponseCurve> = Vec::with_capacity(curves_num);
+    for curve_num in 0..curves_num {
+        let offset_idx = idx + 12 + 4 * curve_num;
+        let offset = bytes_u32_usize(&icc_raw_data[offset_idx..=offset_idx + 3])?;
+        curves.push(read_response_curve(
+            icc_raw_data,
+            idx + offset,
+            usize::from(channels_num),
+            tag_end,
+        )?);
+    }
+
+    Ok(ResponseCurveSet {
+        channels_num,
+        curves,
+    })
+}
+
+// Values of the array types (ui08, ui16, ui32, ui64, uf32) following the 8 bytes header
+fn read_array_values(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+    type_sig: u32,
+    value_size: usize,
+) -> Result<&[u8], String> {
+    let sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+    if sig != type_sig {
+        return Err(format!(
+            "Bad tag signature for {} (0x{:X}) found {:X}",
+            sig_to_str(type_sig),
+            type_sig,
+            sig
+        ));
+    }
+    if size < 8 || !(size - 8).is_multiple_of(value_size) {
+        return Err(format!(
+            "{} tag size ({} Bytes) is not a whole number of {} Bytes values",
+            sig_to_str(type_sig),
+            size,
+            value_size
+        ));
+    }
+
+    match icc_raw_data.get(idx + 8..idx + size) {
+        Some(values) => Ok(values),
+        None => Err(format!("{} values out of data range", sig_to_str(type_sig))),
+    }
+}
+
+pub fn read_ui08_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<Vec<u8>, String> {
+    Ok(read_array_values(icc_raw_data, idx, size, 0x75693038, 1)?.to_vec())
+}
+
+pub fn read_ui16_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<Vec<u16>, String> {
+    Ok(read_array_values(icc_raw_data, idx, size, 0x75693136, 2)?
+        .chunks_exact(2)
+        .map(|value| u16::from_be_bytes([value[0], value[1]]))
+        .collect())
+}
+
+pub fn read_ui32_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<Vec<u32>, String> {
+    read_array_values(icc_raw_data, idx, size, 0x75693332, 4)?
+        .chunks_exact(4)
+        .map(bytes_to_u32)
+        .collect()
+}
+
+pub fn read_ui64_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<Vec<u64>, String> {
+    Ok(read_array_values(icc_raw_data, idx, size, 0x75693634, 8)?
+        .chunks_exact(8)
+        .map(|value| u64::from_be_bytes(value.try_into().unwrap_or_default()))
+        .collect())
+}
+
+pub fn read_uf32_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<Vec<U16Fixed16Number>, String> {
+    read_array_values(icc_raw_data, idx, size, 0x75663332, 4)?
+        .chunks_exact(4)
+        .map(bytes_to_uf16)
+        .collect()
+}
+
+pub fn read_dtim_type(icc_raw_data: &[u8], idx: usize) -> Result<[u16; 6], String> {
+    let dtim_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if dtim_sig != 0x6474696D {
+        return Err(format!(
+            "Bad tag signature for dtim (0x6474696D) found {:X}",
+            dtim_sig
+        ));
+    };
+
+    match icc_raw_data.get(idx + 8..idx + 20) {
+        Some(date) => Ok(std::array::from_fn(|field| {
+            u16::from_be_bytes([date[2 * field], date[2 * field + 1]])
+        })),
+        None => Err("dtim tag out of data range".to_string()),
+    }
+}
+
+pub fn read_data_type(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<DataType, String> {
+    let data_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    if data_sig != 0x64617461 {
+        return Err(format!(
+            "Bad tag signature for data (0x64617461) found {:X}",
+            data_sig
+        ));
+    };
+
+    match icc_raw_data.get(idx + 8..idx + size) {
+        Some(data) if size >= 12 => Ok(DataType {
+            flags: bytes_to_u32(&data[0..=3])?,
+            data: data[4..].to_vec(),
+        }),
+        _ => Err("data tag out of data range".to_string()),
+    }
+}
+
+pub fn read_mpet_type(
+    icc_raw_data: &[u8],
+    idx: usize,
+    size: usize,
+) -> Result<MultiProcessElements, String> {
+    let mpet_tag = match icc_raw_data.get(idx..idx + size) {
+        Some(mpet_tag) if size >= 16 => mpet_tag,
+        _ => return Err("mpet tag out of data range".to_string()),
+    };
+    let mpet_sig = bytes_to_u32(&mpet_tag[0..=3])?;
+
+    if mpet_sig != 0x6D706574 {
+        return Err(format!(
+            "Bad tag signature for mpet (0x6D706574) found {:X}",
+            mpet_sig
         ));
     };
 
-    let manufacturer: u16 = bytes_to_u16(&icc_raw_data[idx + 10..=idx + 11])?;
-    let device: u16 = bytes_to_u16(&icc_raw_data[idx + 14..=idx + 15])?;
+    let elements_num = bytes_u32_usize(&mpet_tag[12..=15])?;
+    let mut elements: Vec<ProcessElement> = Vec::with_capacity(elements_num.min(256));
 
-    let mmod: MmodType = (manufacturer, device);
+    // Position table : offset from the start of the tag and size of each element
+    for element_num in 0..elements_num {
+        let position_idx = 16 + element_num * 8;
+        let position = match mpet_tag.get(position_idx..position_idx + 8) {
+            Some(position) => position,
+            None => return Err("mpet position table out of data range".to_string()),
+        };
+        let offset = bytes_u32_usize(&position[0..=3])?;
+        let element = match mpet_tag.get(offset..offset + 12) {
+            Some(element) => element,
+            None => {
+                return Err(format!(
+                    "mpet element {} out of data range",
+                    element_num + 1
+                ))
+            }
+        };
+        let element_sig = bytes_to_u32(&element[0..=3])?;
+        let in_chans_num = bytes_to_u16(&element[8..=9])?;
+
+        // 'clut' : grid points of the (up to 16) input channels follow the header
+        let grid_pts = match element_sig {
+            0x636C7574 => {
+                match mpet_tag.get(offset + 12..offset + 12 + usize::from(in_chans_num)) {
+                    Some(grid_pts) if in_chans_num <= 16 => Some(grid_pts.to_vec()),
+                    _ => return Err("mpet clut grid points out of data range".to_string()),
+                }
+            }
+            _ => None,
+        };
+
+        elements.push(ProcessElement {
+            element_sig,
+            in_chans_num,
+            out_chans_num: bytes_to_u16(&element[10..=11])?,
+            grid_pts,
+        });
+    }
 
-    Ok(mmod)
+    Ok(MultiProcessElements {
+        in_chans_num: bytes_to_u16(&mpet_tag[8..=9])?,
+        out_chans_num: bytes_to_u16(&mpet_tag[10..=11])?,
+        elements,
+    })
 }
@@ -1,3 +1,7 @@
+pub mod cgats;
+pub mod edid;
 pub mod icc_types;
+pub mod profile;
 pub mod profile_info;
+pub mod registry;
 pub mod tags;
@@ -0,0 +1,176 @@
+use crate::parsers::icc_types::*;
+use crate::types::icc::XYZNumber;
+use crate::types::profile::{Profile, ProfileHeader, TagData};
+use crate::utils::bytes::{bytes_to_u16, bytes_to_u32, bytes_u32_usize};
+
+fn parse_header(icc_raw_data: &[u8]) -> Result<ProfileHeader, String> {
+    if icc_raw_data.len() < 132 {
+        return Err(format!(
+            "Profile is too short to contain a header ({} Bytes)",
+            icc_raw_data.len()
+        ));
+    }
+
+    if bytes_to_u32(&icc_raw_data[36..=39])? != 0x61637370 {
+        return Err("Profile file is not 'acsp'".to_string());
+    }
+
+    let mut date = [0u16; 6];
+    for (date_idx, date_val) in date.iter_mut().enumerate() {
+        *date_val = bytes_to_u16(&icc_raw_data[(24 + 2 * date_idx)..=(25 + 2 * date_idx)])?;
+    }
+
+    let illuminant: XYZNumber = new_xyz(
+        &icc_raw_data[68..=71],
+        &icc_raw_data[72..=75],
+        &icc_raw_data[76..=79],
+    )?;
+
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&icc_raw_data[84..=99]);
+
+    Ok(ProfileHeader {
+        size: bytes_to_u32(&icc_raw_data[0..=3])?,
+        cmm: bytes_to_u32(&icc_raw_data[4..=7])?,
+        version: bytes_to_u32(&icc_raw_data[8..=11])?,
+        class: bytes_to_u32(&icc_raw_data[12..=15])?,
+        color_space: bytes_to_u32(&icc_raw_data[16..=19])?,
+        pcs: bytes_to_u32(&icc_raw_data[20..=23])?,
+        date,
+        platform: bytes_to_u32(&icc_raw_data[40..=43])?,
+        flags: bytes_to_u32(&icc_raw_data[44..=47])?,
+        manufacturer: bytes_to_u32(&icc_raw_data[48..=51])?,
+        model: bytes_to_u32(&icc_raw_data[52..=55])?,
+        attributes: u64::from_be_bytes(icc_raw_data[56..=63].try_into().unwrap()),
+        rendering_intent: bytes_to_u32(&icc_raw_data[64..=67])?,
+        illuminant,
+        creator: bytes_to_u32(&icc_raw_data[80..=83])?,
+        id,
+    })
+}
+
+pub fn read_tag_data(icc_raw_data: &[u8], idx: usize, size: usize) -> Result<TagData, String> {
+    let type_sig = bytes_to_u32(&icc_raw_data[idx..=idx + 3])?;
+
+    let tag_data = match type_sig {
+        // 'text'
+        0x74657874 => {
+            let (_, text) = read_text_type(icc_raw_data, idx, size, None)?;
+            TagData::Text(text.trim_end_matches('\0').to_string())
+        }
+        // 'desc'
+        0x64657363 => TagData::Desc(read_desc_type(icc_raw_data, idx, size)?),
+        // 'mluc'
+        0x6D6C7563 => TagData::Mluc(read_mluc_type(icc_raw_data, idx, size)?),
+        // 'XYZ '
+        0x58595A20 => TagData::XYZ(read_xyz_type(icc_raw_data, idx, size)?.1),
+        // 'curv'
+        0x63757276 => TagData::Curve(read_curve_type(icc_raw_data, idx)?),
+        // 'para'
+        0x70617261 => TagData::Para(read_para_type(icc_raw_data, idx)?),
+        // 'sf32'
+        0x73663332 => TagData::Sf32(read_sf32_type(icc_raw_data, idx, size)?.1),
+        // 'mft1' / 'mft2'
+        0x6D667431 | 0x6D667432 => match read_a2b_type(icc_raw_data, idx, size)? {
+            (Some(lut8), None) => TagData::Lut8(lut8),
+            (None, Some(lut16)) => TagData::Lut16(lut16),
+            _ => return Err("Lut neither 8bits nor 16bits".to_string()),
+        },
+        // 'mAB ' / 'mBA '
+        0x6D414220 | 0x6D424120 => TagData::LutAB(read_lutab_type(icc_raw_data, idx)?),
+        // 'dict'
+        0x64696374 => TagData::Dict(read_dict_entries(icc_raw_data, idx, size)?),
+        // 'vcgt'
+        0x76636774 => match icc_raw_data.get(idx + 8..idx + 12) {
+            Some(gamma_type) if bytes_to_u32(gamma_type)? == 1 => {
+                TagData::VcgtFormula(read_vcgt_formula(icc_raw_data, idx)?)
+            }
+            _ => match read_vcgt_type(icc_raw_data, idx, size)? {
+                Some(vcgt) => TagData::Vcgt(vcgt),
+                None => return Err("Unsupported vcgt".to_string()),
+            },
+        },
+        // 'chrm'
+        0x6368726D => TagData::Chrm(read_chrm_type(icc_raw_data, idx)?),
+        // 'mmod'
+        0x6D6D6F64 => TagData::Mmod(read_mmod_type(icc_raw_data, idx, size)?),
+        // 'ncl2'
+        0x6E636C32 => TagData::Ncl2(read_ncl2_type(icc_raw_data, idx, size)?),
+        // 'sig '
+        0x73696720 => TagData::Sig(read_sig_type(icc_raw_data, idx)?),
+        // 'clrt'
+        0x636C7274 => TagData::Clrt(read_clrt_type(icc_raw_data, idx, size)?),
+        // 'clro'
+        0x636C726F => TagData::Clro(read_clro_type(icc_raw_data, idx, size)?),
+        // 'rcs2'
+        0x72637332 => TagData::Rcs2(read_rcs2_type(icc_raw_data, idx, size)?),
+        // 'dtim'
+        0x6474696D => TagData::DateTime(read_dtim_type(icc_raw_data, idx)?),
+        // 'ui08'
+        0x75693038 => TagData::UInt8(read_ui08_type(icc_raw_data, idx, size)?),
+        // 'ui16'
+        0x75693136 => TagData::UInt16(read_ui16_type(icc_raw_data, idx, size)?),
+        // 'ui32'
+        0x75693332 => TagData::UInt32(read_ui32_type(icc_raw_data, idx, size)?),
+        // 'ui64'
+        0x75693634 => TagData::UInt64(read_ui64_type(icc_raw_data, idx, size)?),
+        // 'uf32'
+        0x75663332 => TagData::Uf32(read_uf32_type(icc_raw_data, idx, size)?),
+        // 'data'
+        0x64617461 => TagData::Data(read_data_type(icc_raw_data, idx, size)?),
+        // 'meas'
+        0x6D656173 => TagData::Meas(read_meas_type(icc_raw_data, idx)?),
+        // 'view'
+        0x76696577 => TagData::View(read_view_type(icc_raw_data, idx)?),
+        // 'pseq'
+        0x70736571 => TagData::Pseq(read_pseq_type(icc_raw_data, idx, size)?),
+        // 'psid'
+        0x70736964 => TagData::Psid(read_psid_type(icc_raw_data, idx, size)?),
+        _ => TagData::Unknown(icc_raw_data[idx..(idx + size)].to_vec()),
+    };
+
+    Ok(tag_data)
+}
+
+impl Profile {
+    // Tags that cannot be decoded are kept as raw bytes so nothing is lost
+    pub fn from_bytes(icc_raw_data: &[u8]) -> Result<Profile, String> {
+        let header = parse_header(icc_raw_data)?;
+        let tags_num = match icc_raw_data.get(128..=131) {
+            Some(range) => bytes_u32_usize(range)?,
+            None => return Err("Profile is too short to contain a tag table".to_string()),
+        };
+        // The count comes from the file : entries beyond the end of file are reported below
+        let mut tags: Vec<(u32, TagData)> = Vec::with_capacity(tags_num.min(1024));
+
+        for tag_num in 0..tags_num {
+            let tag_idx: usize = 132 + (tag_num * 12);
+            let tag_entry = match icc_raw_data.get(tag_idx..(tag_idx + 12)) {
+                Some(entry) => entry,
+                None => return Err(format!("Tag table entry {} out of file", tag_num)),
+            };
+            let tag_sig = bytes_to_u32(&tag_entry[0..=3])?;
+            let offset = bytes_u32_usize(&tag_entry[4..=7])?;
+            let size = bytes_u32_usize(&tag_entry[8..=11])?;
+
+            if size < 8 || offset + size > icc_raw_data.len() {
+                return Err(format!(
+                    "Tag {:X} ({} Bytes at {}) out of file ({} Bytes)",
+                    tag_sig,
+                    size,
+                    offset,
+                    icc_raw_data.len()
+                ));
+            }
+
+            let tag_data = match read_tag_data(icc_raw_data, offset, size) {
+                Ok(tag_data) => tag_data,
+                Err(_) => TagData::Unknown(icc_raw_data[offset..(offset + size)].to_vec()),
+            };
+
+            tags.push((tag_sig, tag_data));
+        }
+
+        Ok(Profile { header, tags })
+    }
+}
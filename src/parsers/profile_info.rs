@@ -1,11 +1,55 @@
 use std::collections::HashMap;
 
-use crate::types::icc::{S15Fixed16Number, XYZNumber};
+use crate::analysis::metadata::Metadata;
+use crate::parsers::icc_types::read_dict_type;
+use crate::parsers::tags::read_tag_table;
+use crate::types::icc::{ProfileIdPrettyPrint, ProfileIdStatus, S15Fixed16Number};
+use crate::types::profile::Profile;
 use crate::utils::bytes::get_bits_of_byte;
 use crate::utils::icc::{
-    get_date_time_number, ICC_CLASS_PROFILE, ICC_PF_COLOR_SPACE, ICC_PRIM_PLATFORM,
+    check_profile_id, get_date_time_number, intent_name, ICC_CLASS_PROFILE, ICC_PF_COLOR_SPACE,
+    ICC_PRIM_PLATFORM,
 };
 
+// Creator, instrument, display and accuracy found in the meta dict of DisplayCAL profiles
+fn meta_summary(icc_raw_data: &[u8]) -> Option<String> {
+    let (_, offset, size) = read_tag_table(icc_raw_data)
+        .ok()?
+        .into_iter()
+        .find(|(sig, _, _)| *sig == 0x6D657461)?;
+    let records = read_dict_type(icc_raw_data, offset, size).ok()?;
+    Metadata::new(records).summary()
+}
+
+// Display identification, with a warning when the profile primaries are not the EDID ones.
+// Nothing is reported when the profile cannot be decoded, the rest of the info still is
+pub fn edid_summary(icc_raw_data: &[u8]) -> Vec<String> {
+    let profile = match Profile::from_bytes(icc_raw_data) {
+        Ok(profile) => profile,
+        Err(_) => return vec![],
+    };
+    // Identification only EDID_ keys are already in the meta summary
+    let Some(edid) = profile.edid().filter(|edid| edid.primaries.is_some()) else {
+        return vec![];
+    };
+
+    let mut summary = format!("EDID : {}", edid.manufacturer_id);
+    if let Some(model) = &edid.model {
+        summary += &format!(" {}", model);
+    }
+    if let Some(serial) = edid.serial_str() {
+        summary += &format!(", serial {}", serial);
+    }
+    let mut lines = vec![summary];
+    lines.extend(
+        profile
+            .edid_comparisons(&edid)
+            .iter()
+            .filter_map(|comparison| comparison.warning()),
+    );
+    lines
+}
+
 pub fn parse_profile_info(icc_raw_data: &[u8]) {
     println!("---- Profile info ----");
 
@@ -105,13 +149,7 @@ pub fn parse_profile_info(icc_raw_data: &[u8]) {
         refl_attr, gloss_attr, pos_attr, color_attr
     );
 
-    let pf_render_int = match icc_raw_data[67] {
-        0 => "Perceptual",
-        1 => "Media-Relative Colorimetric",
-        2 => "Saturation",
-        3 => "ICC-Absolute Colorimetric",
-        _ => "Unknown",
-    };
+    let pf_render_int = intent_name(u32::from(icc_raw_data[67]));
     println!("    Rendering intent : {}", pf_render_int);
 
     let cie_x: S15Fixed16Number =
@@ -121,7 +159,7 @@ pub fn parse_profile_info(icc_raw_data: &[u8]) {
     let cie_z: S15Fixed16Number =
         S15Fixed16Number::from_be_bytes(icc_raw_data[76..=79].try_into().unwrap());
 
-    let pf_xyz_illum: XYZNumber = [cie_x * 100, cie_y * 100, cie_z * 100];
+    let pf_xyz_illum: [f64; 3] = [cie_x, cie_y, cie_z].map(|coor| coor.to_num::<f64>() * 100.0);
 
     println!(
         "    Illuminant : {:.2} {:.2} {:.2}",
@@ -133,5 +171,21 @@ pub fn parse_profile_info(icc_raw_data: &[u8]) {
         "    Profile Creator : 0x{:X} ({})",
         pf_creator_sig_hex, pf_creator_sig
     );
+
+    let pf_id: [u8; 16] = icc_raw_data[84..=99].try_into().unwrap();
+    match check_profile_id(icc_raw_data) {
+        ProfileIdStatus::Absent => println!("    Profile ID : {}", ProfileIdStatus::Absent),
+        status => println!(
+            "    Profile ID : {} ({})",
+            ProfileIdPrettyPrint(pf_id),
+            status
+        ),
+    }
+    if let Some(summary) = meta_summary(icc_raw_data) {
+        println!("    {}", summary);
+    }
+    for line in edid_summary(icc_raw_data) {
+        println!("    {}", line);
+    }
     println!("---- End of Profile info ----\n");
 }
@@ -0,0 +1,459 @@
+use crate::analysis::appearance::AppearanceConditions;
+use crate::analysis::primaries::nearest_standard;
+use crate::parsers::cgats::parse_cgats;
+use crate::parsers::edid::parse_edid;
+use crate::parsers::icc_types::*;
+use crate::types::icc::{
+    ColorantPrettyPrint, DictRecordsPrettyPrint, NamedColorPrettyPrint,
+    ProfileDescriptionPrettyPrint, ProfileIdentifierPrettyPrint,
+};
+use crate::utils::bytes::{sig_to_str, str_to_sig};
+use crate::utils::icc::{
+    color_space_channels, get_date_time_number, signature_name, ICC_IMAGE_STATE,
+    ICC_RENDERING_INTENT_GAMUT, ICC_TECHNOLOGY,
+};
+
+const NAMED_COLORS_SHOWN: usize = 10;
+const GENERIC_VALUES_SHOWN: usize = 16;
+
+// What a type decoder knows about the tag being decoded : the same type is shown differently
+// depending on the tag (lumi is a luminance, tech names a technology, A2B0 has device inputs...)
+pub struct TagContext<'a> {
+    pub tag: &'a str,
+    pub lang: Option<&'a str>,
+    pub pt_num: usize,                         // vcgt : number of values to display
+    pub corr_scale: usize,                     // vcgt : correction scale
+    pub data_space: u32,                       // Header data color space
+    pub pcs: u32,                              // Header PCS, output color space of a DeviceLink
+    pub device_channels: Option<&'a [String]>, // clrt colorant names
+    pub output_channels: Option<&'a [String]>, // clot colorant names
+}
+
+impl TagContext<'_> {
+    fn pcs_lab(&self) -> bool {
+        sig_to_str(self.pcs) == "Lab "
+    }
+}
+
+// Display of the tag data found at idx (starting with the type signature), one line per
+// item indented by 6 spaces
+pub type TypeDecoder = fn(&[u8], usize, usize, &TagContext) -> Result<String, String>;
+
+// Decoders keyed on the type signature, whatever the tag using the type. The types
+// expected for each tag are a separate concern, checked with ICC_TAG_TYPES.
+pub struct TypeRegistry {
+    decoders: Vec<(u32, TypeDecoder)>,
+}
+
+impl TypeRegistry {
+    pub fn empty() -> Self {
+        TypeRegistry { decoders: vec![] }
+    }
+
+    // Adds the decoder of a custom or private type, or replaces a built-in one
+    pub fn register(&mut self, type_sig: &str, decoder: TypeDecoder) {
+        let type_sig = str_to_sig(type_sig);
+        self.decoders.retain(|(sig, _)| *sig != type_sig);
+        self.decoders.push((type_sig, decoder));
+    }
+
+    pub fn decoder(&self, type_sig: u32) -> Option<TypeDecoder> {
+        self.decoders
+            .iter()
+            .find(|(sig, _)| *sig == type_sig)
+            .map(|(_, decoder)| *decoder)
+    }
+}
+
+impl Default for TypeRegistry {
+    fn default() -> Self {
+        let mut registry = TypeRegistry::empty();
+        registry.register("desc", decode_desc);
+        registry.register("mluc", decode_mluc);
+        registry.register("text", decode_text);
+        registry.register("XYZ ", decode_xyz);
+        registry.register("curv", decode_curv);
+        registry.register("para", decode_para);
+        registry.register("mft1", decode_lut);
+        registry.register("mft2", decode_lut);
+        registry.register("mAB ", decode_lutab);
+        registry.register("mBA ", decode_lutab);
+        registry.register("mpet", decode_mpet);
+        registry.register("sf32", decode_sf32);
+        registry.register("uf32", decode_uf32);
+        registry.register("ui08", decode_ui08);
+        registry.register("ui16", decode_ui16);
+        registry.register("ui32", decode_ui32);
+        registry.register("ui64", decode_ui64);
+        registry.register("dtim", decode_dtim);
+        registry.register("data", decode_data);
+        registry.register("vcgt", decode_vcgt);
+        registry.register("chrm", decode_chrm);
+        registry.register("mmod", decode_mmod);
+        registry.register("ncl2", decode_ncl2);
+        registry.register("sig ", decode_sig);
+        registry.register("clrt", decode_clrt);
+        registry.register("clro", decode_clro);
+        registry.register("rcs2", decode_rcs2);
+        registry.register("meas", decode_meas);
+        registry.register("view", decode_view);
+        registry.register("pseq", decode_pseq);
+        registry.register("psid", decode_psid);
+        registry.register("dict", decode_dict);
+        registry
+    }
+}
+
+fn values_str<T: std::fmt::Display>(values: &[T]) -> String {
+    let mut str = values
+        .iter()
+        .take(GENERIC_VALUES_SHOWN)
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(" ");
+    if values.len() > GENERIC_VALUES_SHOWN {
+        str.push_str(&format!(" … {} more", values.len() - GENERIC_VALUES_SHOWN));
+    }
+    format!("      {} values : {}", values.len(), str)
+}
+
+fn channels_str(direction: &str, names: Option<&[String]>) -> String {
+    match names {
+        Some(names) => format!("\n      {} channels : {}", direction, names.join(", ")),
+        None => String::new(),
+    }
+}
+
+// Colorant names of the input and output sides of the LUT tags
+fn lut_channels_str(ctx: &TagContext) -> String {
+    match ctx.tag.get(..3) {
+        Some("A2B" | "D2B") => {
+            channels_str("Input", ctx.device_channels)
+                + &channels_str("Output", ctx.output_channels)
+        }
+        Some("B2A" | "B2D") => channels_str("Output", ctx.device_channels),
+        _ => String::new(),
+    }
+}
+
+fn cgats_str(text: &str) -> String {
+    match parse_cgats(text) {
+        Ok(cgats) => cgats.to_string(),
+        Err(err) => format!("      ! Not CGATS data : {}", err),
+    }
+}
+
+fn decode_desc(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(format!("      {}", read_desc_type(data, idx, size)?))
+}
+
+fn decode_mluc(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    Ok(format!(
+        "      {}",
+        read_text_desc_type(data, idx, size, ctx.lang)?
+    ))
+}
+
+fn decode_text(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let (_, text) = read_text_type(data, idx, size, ctx.lang)?;
+    match ctx.tag {
+        // Argyll .ti1 / .ti3 files
+        "DevD" | "CIED" | "targ" => Ok(format!(
+            "      [{} Bytes]\n{}",
+            text.len().saturating_sub(1),
+            cgats_str(&text)
+        )),
+        _ => Ok(format!("      {}", text)),
+    }
+}
+
+fn decode_xyz(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let (_, xyz) = read_xyz_type(data, idx, size)?;
+    let xyz = xyz.map(|coor| coor.to_num::<f64>());
+    match ctx.tag {
+        "lumi" => Ok(format!("      {:.2} cd/m²", xyz[1])),
+        _ => Ok(format!(
+            "      {:.2} {:.2} {:.2}",
+            xyz[0] * 100.0,
+            xyz[1] * 100.0,
+            xyz[2] * 100.0
+        )),
+    }
+}
+
+fn decode_curv(data: &[u8], idx: usize, _: usize, _: &TagContext) -> Result<String, String> {
+    Ok(read_curve_type(data, idx)?.to_string())
+}
+
+fn decode_para(data: &[u8], idx: usize, _: usize, _: &TagContext) -> Result<String, String> {
+    Ok(read_para_type(data, idx)?.to_string())
+}
+
+fn decode_lut(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let lut = match read_a2b_type(data, idx, size)? {
+        (Some(lut8), None) => lut8.to_string(),
+        (None, Some(lut16)) => lut16.to_string(),
+        _ => return Err("neither 8bits nor 16bits LUT".to_string()),
+    };
+    Ok(lut + &lut_channels_str(ctx))
+}
+
+fn decode_lutab(data: &[u8], idx: usize, _: usize, ctx: &TagContext) -> Result<String, String> {
+    Ok(read_lutab_type(data, idx)?.to_string() + &lut_channels_str(ctx))
+}
+
+fn decode_mpet(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    Ok(read_mpet_type(data, idx, size)?.to_string() + &lut_channels_str(ctx))
+}
+
+fn decode_sf32(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let (_, values) = read_sf32_type(data, idx, size)?;
+    match ctx.tag {
+        "chad" | "arts" if values.len() == 9 => {
+            let rows: Vec<String> = values
+                .chunks_exact(3)
+                .map(|row| format!("       {} {} {}", row[0], row[1], row[2]))
+                .collect();
+            Ok(format!("      Matrix :\n{}", rows.join("\n")))
+        }
+        _ => Ok(values_str(&values)),
+    }
+}
+
+fn decode_uf32(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(values_str(&read_uf32_type(data, idx, size)?))
+}
+
+fn decode_ui08(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(values_str(&read_ui08_type(data, idx, size)?))
+}
+
+fn decode_ui16(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(values_str(&read_ui16_type(data, idx, size)?))
+}
+
+fn decode_ui32(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(values_str(&read_ui32_type(data, idx, size)?))
+}
+
+fn decode_ui64(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(values_str(&read_ui64_type(data, idx, size)?))
+}
+
+fn decode_dtim(data: &[u8], idx: usize, _: usize, _: &TagContext) -> Result<String, String> {
+    let date = read_dtim_type(data, idx)?;
+    let mut bytes = [0u8; 12];
+    for (field, value) in date.iter().enumerate() {
+        bytes[2 * field..2 * field + 2].copy_from_slice(&value.to_be_bytes());
+    }
+    Ok(format!("      {}", get_date_time_number(&bytes)))
+}
+
+fn decode_data(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    let data = read_data_type(data, idx, size)?;
+    if let Ok(edid) = parse_edid(&data.data) {
+        return Ok(format!("      EDID block :\n{}", edid));
+    }
+    if data.is_ascii() {
+        Ok(format!(
+            "      ASCII : {}",
+            String::from_utf8_lossy(&data.data).trim_end_matches('\0')
+        ))
+    } else {
+        let hex: Vec<String> = data
+            .data
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        Ok(format!(
+            "      Binary data, {}",
+            values_str(&hex).trim_start()
+        ))
+    }
+}
+
+fn decode_vcgt(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let vcgt = match read_vcgt_type(data, idx, size)? {
+        Some(vcgt) => vcgt,
+        None => return Ok("      VCGT Gamma type 1 not implemented yet…".to_string()),
+    };
+    let mut lines = vec![
+        format!("      Max Values (RGB) : {:.2?}", vcgt.4),
+        format!("      Min Values (RGB) : {:.2?}", vcgt.5),
+        format!("      Channels : {}", vcgt.6),
+        format!("      Entries per channel : {}", vcgt.7),
+        format!("      Bitdepth : {} bits", vcgt.8),
+        format!(
+            "      Number of value to display : {} (step : {})",
+            ctx.pt_num,
+            (100.0 / ctx.pt_num as f32)
+        ),
+        format!("      Correction scale +/- {}", ctx.corr_scale),
+        "      In (RGB) -> Out (R,G,B)".to_string(),
+    ];
+    let corr_scale = ctx.corr_scale as f32;
+    let step: usize = ((1.0 / ctx.pt_num as f32) * 256.0).ceil() as usize;
+    for idx in (0..vcgt.1.len()).step_by(step.max(1)) {
+        let lin_val: f32 = idx as f32 / 256.0;
+        let correction =
+            |ramp: &[u16]| (((ramp[idx] as f32 / 256.0) / 256.0) - lin_val) * corr_scale;
+        lines.push(format!(
+            "      {:.2}  {:.0}, {:.0}, {:.0}",
+            lin_val,
+            correction(&vcgt.1),
+            correction(&vcgt.2),
+            correction(&vcgt.3),
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn decode_chrm(data: &[u8], idx: usize, _: usize, _: &TagContext) -> Result<String, String> {
+    let chrm = read_chrm_type(data, idx)?;
+    let mut decoded = chrm.to_string();
+    if let Some(primaries) = chrm.rgb_primaries() {
+        let (name, deltas) = nearest_standard(&primaries);
+        decoded += &format!(
+            "\n      Nearest standard : {} (Δu′v′ R {:.4}, G {:.4}, B {:.4})",
+            name, deltas[0], deltas[1], deltas[2]
+        );
+    }
+    Ok(decoded)
+}
+
+fn decode_mmod(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    Ok(read_mmod_type(data, idx, size)?.to_string())
+}
+
+fn decode_ncl2(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let named_colors = read_ncl2_type(data, idx, size)?;
+    let mut lines = vec![
+        format!(
+            "      Prefix : '{}', suffix : '{}'",
+            named_colors.prefix, named_colors.suffix
+        ),
+        format!(
+            "      # Colors : {}, # device coordinates : {}",
+            named_colors.colors.len(),
+            named_colors.device_coords_num
+        ),
+    ];
+    for color in named_colors.colors.iter().take(NAMED_COLORS_SHOWN) {
+        lines.push(NamedColorPrettyPrint(&named_colors, color, ctx.pcs_lab()).to_string());
+    }
+    if named_colors.colors.len() > NAMED_COLORS_SHOWN {
+        lines.push(format!(
+            "      ... {} more colors",
+            named_colors.colors.len() - NAMED_COLORS_SHOWN
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn decode_sig(data: &[u8], idx: usize, _: usize, ctx: &TagContext) -> Result<String, String> {
+    let sig = read_sig_type(data, idx)?;
+    let table: &[(&str, &str)] = match ctx.tag {
+        "tech" => &ICC_TECHNOLOGY,
+        "rig0" | "rig2" => &ICC_RENDERING_INTENT_GAMUT,
+        "ciis" => &ICC_IMAGE_STATE,
+        _ => &[],
+    };
+    Ok(format!("      {}", signature_name(table, sig)))
+}
+
+fn decode_clrt(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let colorants = read_clrt_type(data, idx, size)?;
+    // clrt describes the data color space, clot the output (PCS field) of a DeviceLink
+    let space = if ctx.tag == "clot" {
+        ctx.pcs
+    } else {
+        ctx.data_space
+    };
+    let mut lines = vec![format!("      {} colorants", colorants.len())];
+    for (colorant_idx, colorant) in colorants.iter().enumerate() {
+        lines.push(ColorantPrettyPrint(colorant_idx + 1, colorant, ctx.pcs_lab()).to_string());
+    }
+    match color_space_channels(space) {
+        Some(channels) if channels != colorants.len() => lines.push(format!(
+            "      ! {} colorants for the {} channels of the '{}' color space",
+            colorants.len(),
+            channels,
+            sig_to_str(space).trim_end()
+        )),
+        _ => {}
+    }
+    Ok(lines.join("\n"))
+}
+
+fn decode_clro(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let names: Vec<String> = read_clro_type(data, idx, size)?
+        .iter()
+        .map(|colorant| {
+            match ctx
+                .device_channels
+                .and_then(|names| names.get(*colorant as usize))
+            {
+                Some(name) => name.clone(),
+                None => format!("#{}", colorant),
+            }
+        })
+        .collect();
+    Ok(format!("      {}", names.join(" → ")))
+}
+
+fn decode_rcs2(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+    let response = read_rcs2_type(data, idx, size)?;
+    let mut lines = vec![format!(
+        "      {} channels, {} measurement units",
+        response.channels_num,
+        response.curves.len()
+    )];
+    lines.extend(response.curves.iter().map(|curve| curve.to_string()));
+    Ok(lines.join("\n"))
+}
+
+fn decode_meas(data: &[u8], idx: usize, _: usize, _: &TagContext) -> Result<String, String> {
+    Ok(read_meas_type(data, idx)?.to_string())
+}
+
+fn decode_view(data: &[u8], idx: usize, _: usize, _: &TagContext) -> Result<String, String> {
+    let view = read_view_type(data, idx)?;
+    Ok(format!(
+        "{}\n{}",
+        view,
+        AppearanceConditions::new(Some(&view), None)
+    ))
+}
+
+fn decode_pseq(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let descs = read_pseq_type(data, idx, size)?;
+    let mut lines = vec![format!("      {} profiles", descs.len())];
+    for (desc_idx, desc) in descs.iter().enumerate() {
+        if desc_idx > 0 {
+            lines.push("         ↓".to_string());
+        }
+        lines.push(ProfileDescriptionPrettyPrint(desc_idx + 1, desc, ctx.lang).to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+fn decode_psid(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let ids = read_psid_type(data, idx, size)?;
+    let mut lines = vec![format!("      {} profiles", ids.len())];
+    for (id_idx, id) in ids.iter().enumerate() {
+        if id_idx > 0 {
+            lines.push("         ↓".to_string());
+        }
+        lines.push(ProfileIdentifierPrettyPrint(id_idx + 1, id, ctx.lang).to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+fn decode_dict(data: &[u8], idx: usize, size: usize, ctx: &TagContext) -> Result<String, String> {
+    let records = read_dict_type(data, idx, size)?;
+    Ok(format!(
+        "      {} entries\n{}",
+        records.len(),
+        DictRecordsPrettyPrint(&records, ctx.lang)
+    ))
+}
@@ -0,0 +1,219 @@
+use crate::types::icc::{Curve, Lut16, S15Fixed16Number, TextDescription, U8Fixed8Number};
+use crate::types::profile::{Profile, ProfileHeader, TagData};
+use crate::utils::color::{
+    bradford_adaptation, f64_to_xyz_number, mat_mul, rgb_to_xyz_matrix, xy_to_xyz, D50_XY, D50_XYZ,
+    D65_XY,
+};
+use crate::utils::icc::{itu_r_bt1886, ADOBE_RGB_PRIMARIES, ICC_CHRM_TYPE, PROPHOTO_RGB_PRIMARIES};
+
+pub type StandardProfile = (&'static str, fn() -> Result<Profile, String>);
+
+pub const STANDARD_PROFILES: [StandardProfile; 7] = [
+    ("sRGB", srgb),
+    ("Display P3", display_p3),
+    ("Adobe RGB", adobe_rgb),
+    ("Rec.2020", rec2020),
+    ("ProPhoto", prophoto_rgb),
+    ("Lab D50", lab_d50),
+    ("XYZ D50", xyz_d50),
+];
+
+const CURVE_TABLE_SIZE: usize = 1024;
+const COPYRIGHT: &str = "No copyright, use freely";
+
+// Names are matched without case, spaces, dots or dashes ("display-p3", "rec2020"…)
+pub fn standard_profile(name: &str) -> Option<Result<Profile, String>> {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+    let name = normalize(name);
+
+    STANDARD_PROFILES
+        .iter()
+        .find(|(std_name, _)| normalize(std_name) == name)
+        .map(|(_, build)| build())
+}
+
+pub fn srgb() -> Result<Profile, String> {
+    let bt709 = ICC_CHRM_TYPE[0];
+    rgb_profile(
+        "sRGB IEC61966-2.1",
+        (bt709.1, bt709.2, bt709.3),
+        D65_XY,
+        srgb_curve(),
+    )
+}
+
+pub fn display_p3() -> Result<Profile, String> {
+    let p3 = ICC_CHRM_TYPE[4];
+    rgb_profile("Display P3", (p3.1, p3.2, p3.3), D65_XY, srgb_curve())
+}
+
+pub fn adobe_rgb() -> Result<Profile, String> {
+    let adobe = ADOBE_RGB_PRIMARIES;
+    rgb_profile(
+        "Adobe RGB (1998)",
+        (adobe.1, adobe.2, adobe.3),
+        D65_XY,
+        gamma_curve(563.0 / 256.0),
+    )
+}
+
+pub fn rec2020() -> Result<Profile, String> {
+    let bt2020 = ICC_CHRM_TYPE[5];
+    let mut curve_table: Vec<u16> = Vec::with_capacity(CURVE_TABLE_SIZE);
+    for entry in 0..CURVE_TABLE_SIZE {
+        let signal = entry as f32 / (CURVE_TABLE_SIZE - 1) as f32;
+        let lum = itu_r_bt1886(signal, 1.0, 0.0)?;
+        curve_table.push((lum.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16);
+    }
+
+    rgb_profile(
+        "ITU-R BT.2020 (BT.1886)",
+        (bt2020.1, bt2020.2, bt2020.3),
+        D65_XY,
+        Curve {
+            identity: false,
+            gamma: None,
+            curve: Some(curve_table),
+        },
+    )
+}
+
+pub fn prophoto_rgb() -> Result<Profile, String> {
+    let romm = PROPHOTO_RGB_PRIMARIES;
+    rgb_profile(
+        "ProPhoto RGB (ROMM)",
+        (romm.1, romm.2, romm.3),
+        D50_XY,
+        gamma_curve(1.8),
+    )
+}
+
+pub fn lab_d50() -> Result<Profile, String> {
+    identity_profile("Lab identity (D50)", "Lab ")
+}
+
+pub fn xyz_d50() -> Result<Profile, String> {
+    identity_profile("XYZ identity (D50)", "XYZ ")
+}
+
+fn gamma_curve(gamma: f64) -> Curve {
+    Curve {
+        identity: false,
+        gamma: Some(U8Fixed8Number::from_num(gamma)),
+        curve: None,
+    }
+}
+
+fn srgb_curve() -> Curve {
+    let curve_table = (0..CURVE_TABLE_SIZE)
+        .map(|entry| {
+            let signal = entry as f64 / (CURVE_TABLE_SIZE - 1) as f64;
+            let lin = if signal <= 0.04045 {
+                signal / 12.92
+            } else {
+                ((signal + 0.055) / 1.055).powf(2.4)
+            };
+            (lin * u16::MAX as f64).round() as u16
+        })
+        .collect();
+
+    Curve {
+        identity: false,
+        gamma: None,
+        curve: Some(curve_table),
+    }
+}
+
+fn header_and_text(desc: &str, class: &str, color_space: &str, pcs: &str) -> Profile {
+    let mut profile = Profile::new(ProfileHeader::new(
+        class,
+        color_space,
+        pcs,
+        f64_to_xyz_number(&D50_XYZ),
+    ));
+    profile.set_tag("desc", TagData::Desc(TextDescription::new(desc)));
+    profile.set_tag("cprt", TagData::Text(COPYRIGHT.to_string()));
+    profile.set_tag("wtpt", TagData::XYZ(f64_to_xyz_number(&D50_XYZ)));
+    profile
+}
+
+fn rgb_profile(
+    desc: &str,
+    primaries: ((f64, f64), (f64, f64), (f64, f64)),
+    white: (f64, f64),
+    trc: Curve,
+) -> Result<Profile, String> {
+    let mut profile = header_and_text(desc, "mntr", "RGB ", "XYZ ");
+
+    let chad = bradford_adaptation(&xy_to_xyz(white), &D50_XYZ)?;
+    let rgb_to_xyz = rgb_to_xyz_matrix(primaries.0, primaries.1, primaries.2, white)?;
+    let colorants = mat_mul(&chad, &rgb_to_xyz);
+
+    profile.set_tag(
+        "chad",
+        TagData::Sf32(
+            chad.iter()
+                .flatten()
+                .map(|val| S15Fixed16Number::from_num(*val))
+                .collect(),
+        ),
+    );
+
+    for (chan, sig) in ["rXYZ", "gXYZ", "bXYZ"].iter().enumerate() {
+        let colorant = [colorants[0][chan], colorants[1][chan], colorants[2][chan]];
+        profile.set_tag(sig, TagData::XYZ(f64_to_xyz_number(&colorant)));
+    }
+
+    for sig in ["rTRC", "gTRC", "bTRC"] {
+        profile.set_tag(sig, TagData::Curve(trc.clone()));
+    }
+
+    Ok(profile)
+}
+
+// Abstract profile whose A2B0/B2A0 are 2 grid points identity Lut16
+fn identity_profile(desc: &str, space: &str) -> Result<Profile, String> {
+    let mut profile = header_and_text(desc, "abst", space, space);
+
+    for sig in ["A2B0", "B2A0"] {
+        profile.set_tag(sig, TagData::Lut16(identity_lut16(3)));
+    }
+
+    Ok(profile)
+}
+
+fn identity_lut16(chans_num: u8) -> Lut16 {
+    let chans: usize = chans_num.into();
+    let one = S15Fixed16Number::from_num(1);
+    let zero = S15Fixed16Number::from_num(0);
+    let e_params = vec![one, zero, zero, zero, one, zero, zero, zero, one];
+
+    let linear_table: Vec<u16> = (0..chans).flat_map(|_| [0, u16::MAX]).collect();
+
+    // 2^chans grid nodes, the first input channel varies the slowest
+    let mut clut_table: Vec<u16> = Vec::with_capacity((1 << chans) * chans);
+    for node in 0..(1usize << chans) {
+        for chan in 0..chans {
+            let bit = (node >> (chans - 1 - chan)) & 1;
+            clut_table.push(if bit == 1 { u16::MAX } else { 0 });
+        }
+    }
+
+    Lut16 {
+        lut_sig: 0x6D667432,
+        in_chans_num: chans_num,
+        out_chans_num: chans_num,
+        grid_pts_num: 2,
+        e_params,
+        in_table_entries_num: 2,
+        out_table_entries_num: 2,
+        in_table: linear_table.clone(),
+        clut_table,
+        out_table: linear_table,
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Display;
+
+// One CGATS.17 table, e.g. the "CTI3" or "CAL" part of an ArgyllCMS .ti3 file
+#[derive(Clone, Default)]
+pub struct CgatsTable {
+    pub file_type: String,               // First line identifier (CTI3, CAL...)
+    pub keywords: Vec<(String, String)>, // Header keywords and values, quotes kept
+    pub fields: Vec<String>,             // Field names of the data format
+    pub sets: Vec<Vec<String>>,          // Data sets, one value per field
+}
+
+impl CgatsTable {
+    pub fn keyword(&self, name: &str) -> Option<&str> {
+        self.keywords
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim_matches('"'))
+    }
+
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field == name)
+    }
+
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        let field_idx = self.field_index(name)?;
+        self.sets
+            .iter()
+            .map(|set| set.get(field_idx)?.parse::<f64>().ok())
+            .collect()
+    }
+
+    // Columns of several fields, one array of values per data set
+    pub fn columns<const N: usize>(&self, names: [&str; N]) -> Option<Vec<[f64; N]>> {
+        let columns = names
+            .iter()
+            .map(|name| self.column(name))
+            .collect::<Option<Vec<Vec<f64>>>>()?;
+        Some(
+            (0..self.sets.len())
+                .map(|set_idx| std::array::from_fn(|col| columns[col][set_idx]))
+                .collect(),
+        )
+    }
+
+    // Device values fields : RGB_R, CMYK_C, GRAY_G, 6CLR_1...
+    pub fn device_fields(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|field| {
+                let prefix = field.split('_').next().unwrap_or_default();
+                matches!(prefix, "RGB" | "CMY" | "CMYK" | "GRAY" | "K") || prefix.ends_with("CLR")
+            })
+            .map(|field| field.as_str())
+            .collect()
+    }
+
+    pub fn device_values(&self) -> Option<Vec<Vec<f64>>> {
+        let fields = self.device_fields();
+        if fields.is_empty() {
+            return None;
+        }
+        let columns = fields
+            .iter()
+            .map(|field| self.column(field))
+            .collect::<Option<Vec<Vec<f64>>>>()?;
+        Some(
+            (0..self.sets.len())
+                .map(|set_idx| columns.iter().map(|column| column[set_idx]).collect())
+                .collect(),
+        )
+    }
+
+    pub fn xyz(&self) -> Option<Vec<[f64; 3]>> {
+        self.columns(["XYZ_X", "XYZ_Y", "XYZ_Z"])
+    }
+
+    pub fn lab(&self) -> Option<Vec<[f64; 3]>> {
+        self.columns(["LAB_L", "LAB_A", "LAB_B"])
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Cgats {
+    pub tables: Vec<CgatsTable>,
+}
+
+impl Cgats {
+    // First table carrying the given fields
+    pub fn table_with(&self, fields: &[&str]) -> Option<&CgatsTable> {
+        self.tables.iter().find(|table| {
+            fields
+                .iter()
+                .all(|field| table.field_index(field).is_some())
+        })
+    }
+}
+
+impl Display for CgatsTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {} patches", self.file_type, self.sets.len())?;
+        for key in ["DEVICE_CLASS", "COLOR_REP", "TARGET_INSTRUMENT"] {
+            if let Some(value) = self.keyword(key) {
+                write!(f, ", {} {}", key, value)?;
+            }
+        }
+        write!(f, "\n        Fields : {}", self.fields.join(" "))
+    }
+}
+
+impl Display for Cgats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tables: Vec<String> = self
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(table_idx, table)| format!("      Table {} : {}", table_idx + 1, table))
+            .collect();
+        write!(f, "{}", tables.join("\n"))
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+// Display identification, from an EDID 1.x base block or the EDID_ keys of the meta dict
+#[derive(Clone, Default, Debug)]
+pub struct Edid {
+    pub manufacturer_id: String,            // 3 letters PNP ID, e.g. "DEL"
+    pub product_code: Option<u16>,          // Manufacturer product code
+    pub serial_number: Option<u32>,         // Numeric serial number, None when not set
+    pub serial: Option<String>,             // Serial number descriptor
+    pub model: Option<String>,              // Display product name descriptor
+    pub week: Option<u8>,                   // Week of manufacture
+    pub year: Option<u16>,                  // Year of manufacture (or model year)
+    pub gamma: Option<f64>,                 // Native transfer characteristic
+    pub primaries: Option<[(f64, f64); 3]>, // Native red, green and blue xy
+    pub white: Option<(f64, f64)>,          // Native white point xy
+    pub checksum_ok: bool,                  // Always true for metadata keys
+}
+
+impl Edid {
+    // Descriptor serial first, numeric serial otherwise
+    pub fn serial_str(&self) -> Option<String> {
+        self.serial
+            .clone()
+            .or_else(|| self.serial_number.map(|serial| serial.to_string()))
+    }
+}
+
+impl Display for Edid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "      Manufacturer : {}", self.manufacturer_id)?;
+        if let Some(code) = self.product_code {
+            write!(f, ", product code 0x{:04X}", code)?;
+        }
+        if let Some(model) = &self.model {
+            write!(f, "\n      Model : {}", model)?;
+        }
+        if let Some(serial) = self.serial_str() {
+            write!(f, "\n      Serial : {}", serial)?;
+        }
+        match (self.year, self.week) {
+            (Some(year), Some(week)) => {
+                write!(f, "\n      Manufactured : {}, week {}", year, week)?
+            }
+            (Some(year), None) => write!(f, "\n      Manufactured : {}", year)?,
+            _ => (),
+        }
+        if let Some(gamma) = self.gamma {
+            write!(f, "\n      Gamma : {:.2}", gamma)?;
+        }
+        if let Some([red, green, blue]) = self.primaries {
+            write!(
+                f,
+                "\n      Primaries (xy) : R {:.4} {:.4}, G {:.4} {:.4}, B {:.4} {:.4}",
+                red.0, red.1, green.0, green.1, blue.0, blue.1
+            )?;
+        }
+        if let Some(white) = self.white {
+            write!(
+                f,
+                "\n      White point (xy) : {:.4} {:.4}",
+                white.0, white.1
+            )?;
+        }
+        if !self.checksum_ok {
+            write!(f, "\n      ! Invalid EDID checksum")?;
+        }
+        Ok(())
+    }
+}
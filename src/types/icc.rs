@@ -3,6 +3,11 @@ use std::fmt::Display;
 use fixed::types::extra::{U16, U8};
 use fixed::{FixedI32, FixedU16, FixedU32};
 
+use crate::utils::bytes::write_utf16;
+use crate::utils::icc::{
+    attributes_str, signature_name, ICC_PROCESS_ELEMENTS, ICC_RESPONSE_UNITS, ICC_TECHNOLOGY,
+};
+
 pub type S15Fixed16Number = FixedI32<U16>;
 pub type U8Fixed8Number = FixedU16<U8>;
 pub type U16Fixed16Number = FixedU32<U16>;
@@ -10,12 +15,14 @@ pub type XYZNumber = [S15Fixed16Number; 3];
 
 pub struct XYZNumberPrettyPrint(pub (u32, XYZNumber));
 
+// Displayed x100, so that Y = 100 for the white
 impl Display for XYZNumberPrettyPrint {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let xyz = self.0 .1.map(|coor| coor.to_num::<f64>() * 100.0);
         write!(
             f,
             "({:X}) {:.2} {:.2} {:.2}",
-            self.0 .0, self.0 .1[0], self.0 .1[1], self.0 .1[2]
+            self.0 .0, xyz[0], xyz[1], xyz[2]
         )
     }
 }
@@ -92,13 +99,108 @@ impl Display for Lut16 {
 
 pub type Lut = (Option<Lut8>, Option<Lut16>);
 
-pub type DescType = (String, String);
+// textDescriptionType : the same text in ASCII, Unicode and Macintosh ScriptCode
+#[derive(Clone)]
+pub struct TextDescription {
+    pub ascii: String,
+    pub unicode_lang: u32,          // Unicode language code
+    pub unicode: Option<String>,    // None when the Unicode count is 0
+    pub scriptcode_code: u16,       // ScriptCode code
+    pub scriptcode_count: u8,       // # ScriptCode bytes used in the field
+    pub scriptcode_field: [u8; 67], // ScriptCode field, always 67 bytes long
+    pub issues: Vec<String>,        // Non-conformities tolerated while decoding
+}
+
+impl TextDescription {
+    pub fn new(text: &str) -> TextDescription {
+        TextDescription {
+            ascii: text.to_string(),
+            unicode_lang: 0,
+            unicode: Some(text.to_string()),
+            scriptcode_code: 0,
+            scriptcode_count: 0,
+            scriptcode_field: [0u8; 67],
+            issues: vec![],
+        }
+    }
 
-pub struct DescTypePrettyPrint(pub DescType);
+    // ScriptCode bytes are Macintosh encoded, only their ASCII subset is kept as is
+    pub fn scriptcode(&self) -> Option<String> {
+        let count = usize::from(self.scriptcode_count).min(67);
+        if count == 0 {
+            return None;
+        }
+        let text: String = self.scriptcode_field[..count]
+            .iter()
+            .take_while(|byte| **byte != 0)
+            .map(|byte| if byte.is_ascii() { *byte as char } else { '?' })
+            .collect();
+        Some(text)
+    }
 
-impl Display for DescTypePrettyPrint {
+    // Decoding issues and disagreements between the ASCII, Unicode and ScriptCode texts
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = self.issues.clone();
+        if let Some(unicode) = &self.unicode {
+            if !unicode.is_empty() && *unicode != self.ascii {
+                warnings.push(format!(
+                    "ASCII '{}' and Unicode '{}' descriptions differ",
+                    self.ascii, unicode
+                ));
+            }
+        }
+        if let Some(scriptcode) = self.scriptcode() {
+            if !scriptcode.is_empty() && scriptcode != self.ascii {
+                warnings.push(format!(
+                    "ASCII '{}' and ScriptCode '{}' descriptions differ",
+                    self.ascii, scriptcode
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+impl Display for TextDescription {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ASCII : {} \n      UTF16 : {}", self.0 .0, self.0 .1)
+        write!(f, "ASCII : {}", self.ascii)?;
+        match &self.unicode {
+            Some(unicode) if self.unicode_lang == 0 => write!(f, "\n      Unicode : {}", unicode)?,
+            Some(unicode) => write!(
+                f,
+                "\n      Unicode ({}) : {}",
+                sig_or_hex(self.unicode_lang),
+                unicode
+            )?,
+            None => write!(f, "\n      Unicode : None")?,
+        }
+        match self.scriptcode() {
+            Some(scriptcode) => write!(
+                f,
+                "\n      ScriptCode ({}) : {}",
+                self.scriptcode_code, scriptcode
+            )?,
+            None => write!(f, "\n      ScriptCode : None")?,
+        }
+        for warning in self.warnings() {
+            write!(f, "\n      ! {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+// Language codes are usually 2 ASCII letters (e.g. "en"), some tools store numbers
+fn sig_or_hex(code: u32) -> String {
+    let bytes = code.to_be_bytes();
+    let text: String = bytes
+        .iter()
+        .filter(|byte| **byte != 0)
+        .map(|byte| *byte as char)
+        .collect();
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric()) {
+        text
+    } else {
+        format!("0x{:X}", code)
     }
 }
 
@@ -124,6 +226,7 @@ impl Display for TxtTypeTi3PrettyPrinter {
 //    b_curve:
 //}
 
+#[derive(Clone)]
 pub struct Curve {
     pub identity: bool,
     pub gamma: Option<U8Fixed8Number>,
@@ -154,33 +257,857 @@ impl Display for Curve {
 
 pub struct Chrm {
     pub phs_col_type: String,
-    pub chans_num: u16,
-    pub chan_1: Option<(U16Fixed16Number, U16Fixed16Number)>,
-    pub chan_2: Option<(U16Fixed16Number, U16Fixed16Number)>,
-    pub chan_3: Option<(U16Fixed16Number, U16Fixed16Number)>,
+    pub phs_col_code: u16,
+    pub channels: Vec<(U16Fixed16Number, U16Fixed16Number)>, // CIE xy of each channel
+}
+
+impl Chrm {
+    pub fn xy(&self) -> Vec<(f64, f64)> {
+        self.channels
+            .iter()
+            .map(|(x, y)| (x.to_num(), y.to_num()))
+            .collect()
+    }
+
+    // Red, green and blue primaries, for a 3 channels tag
+    pub fn rgb_primaries(&self) -> Option<[(f64, f64); 3]> {
+        self.xy().try_into().ok()
+    }
 }
 
 impl Display for Chrm {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.chans_num {
-        1 => write!(f, "      Encoded value of phosphor or colorant type : {}\n      CIE xy channel 1 : {:?}", self.phs_col_type, self.chan_1.unwrap()),
-        2 => write!(f, "      Encoded value of phosphor or colorant type : {}\n      CIE xy channel 1 : {:?}\n      CIE xy channel 2 : {:?}", self.phs_col_type, self.chan_1.unwrap(), self.chan_2.unwrap()),
-        3 => write!(f, "      Encoded value of phosphor or colorant type : {}\n      CIE xy channel 1 : {:?}\n      CIE xy channel 2 : {:?}\n      CIE xy channel 3 : {:?}", self.phs_col_type, self.chan_1.unwrap(), self.chan_2.unwrap(),self.chan_3.unwrap()),
-        _ => panic!("Chromaticity channels numbers > 3")
+        write!(
+            f,
+            "      Encoded value of phosphor or colorant type : {}",
+            self.phs_col_type
+        )?;
+        if self.phs_col_code > 0 && self.channels.len() != 3 {
+            write!(
+                f,
+                "\n      ! {} channels, the colorant type has 3 primaries",
+                self.channels.len()
+            )?;
         }
+        for (chan, (x, y)) in self.xy().iter().enumerate() {
+            write!(f, "\n      CIE xy channel {} : {:.4} {:.4}", chan + 1, x, y)?;
+        }
+        Ok(())
     }
 }
 
-pub type MmodType = (u16, u16);
+pub struct ParametricCurve {
+    pub function_type: u16,
+    pub params: Vec<S15Fixed16Number>,
+}
 
-pub struct MmodTypePrettyPrint(pub MmodType);
+impl Display for ParametricCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "      Curve is a parametric curve (function type {}) : {:?}",
+            self.function_type, self.params
+        )
+    }
+}
 
-impl Display for MmodTypePrettyPrint {
+pub enum LutCurve {
+    Curv(Curve),
+    Para(ParametricCurve),
+}
+
+impl Display for LutCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LutCurve::Curv(curve) => write!(f, "{}", curve),
+            LutCurve::Para(para) => write!(f, "{}", para),
+        }
+    }
+}
+
+pub struct Clut {
+    pub grid_pts: Vec<u8>, // # of grid points for each input channel
+    pub precision: u8,     // 1 (8 bits) or 2 (16 bits) bytes per value
+    pub table: Vec<u16>,   // CLUT values, 8 bits values are kept unscaled
+}
+
+pub struct LutAB {
+    pub lut_sig: u32,                          // 'mAB ' or 'mBA '
+    pub in_chans_num: u8,                      // # Inputs channels
+    pub out_chans_num: u8,                     // # Outputs channels
+    pub b_curves: Vec<LutCurve>,               // B curves
+    pub matrix: Option<Vec<S15Fixed16Number>>, // 3x3 matrix + 3 offsets
+    pub m_curves: Option<Vec<LutCurve>>,       // M curves
+    pub clut: Option<Clut>,                    // CLUT
+    pub a_curves: Option<Vec<LutCurve>>,       // A curves
+}
+
+impl Display for LutAB {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let curves_num = |curves: &Option<Vec<LutCurve>>| match curves {
+            Some(curves) => format!("{} curves", curves.len()),
+            None => "None".to_string(),
+        };
+        let clut = match &self.clut {
+            Some(clut) => format!(
+                "{:?} grid points, {} bits, [{} values]",
+                clut.grid_pts,
+                clut.precision * 8,
+                clut.table.len()
+            ),
+            None => "None".to_string(),
+        };
+        write!(
+            f,
+            "      # Inputs channels : {}\n      # Outputs channels : {}\n      B curves : {} curves\n      Matrix : {:?}\n      M curves : {}\n      CLut : {}\n      A curves : {}",
+            self.in_chans_num,
+            self.out_chans_num,
+            self.b_curves.len(),
+            self.matrix,
+            curves_num(&self.m_curves),
+            clut,
+            curves_num(&self.a_curves),
+        )
+    }
+}
+
+// (language code, country code, string)
+pub type MlucRecord = (String, String, String);
+
+#[derive(Clone, Default)]
+pub struct MultiLocalizedText {
+    pub records: Vec<MlucRecord>,
+}
+
+// "fr-FR", "fr_FR" or "fr" into (language code, country code)
+pub fn split_lang(lang: &str) -> (String, String) {
+    let mut codes = lang.split(['-', '_']);
+    let lang_code = codes.next().unwrap_or_default().to_lowercase();
+    let country_code = codes.next().unwrap_or_default().to_uppercase();
+    (lang_code, country_code)
+}
+
+impl MultiLocalizedText {
+    pub fn new(lang: &str, text: &str) -> MultiLocalizedText {
+        let mut mluc = MultiLocalizedText::default();
+        mluc.set(lang, text);
+        mluc
+    }
+
+    // Fallback : exact language and country, same language, en-US, first record
+    pub fn localized(&self, lang: Option<&str>) -> Option<&MlucRecord> {
+        let (lang_code, country_code) = split_lang(lang.unwrap_or("en-US"));
+
+        self.records
+            .iter()
+            .find(|record| record.0 == lang_code && record.1 == country_code)
+            .or_else(|| self.records.iter().find(|record| record.0 == lang_code))
+            .or_else(|| {
+                self.records
+                    .iter()
+                    .find(|record| record.0 == "en" && record.1 == "US")
+            })
+            .or_else(|| self.records.first())
+    }
+
+    pub fn text(&self, lang: Option<&str>) -> Option<&str> {
+        self.localized(lang).map(|record| record.2.as_str())
+    }
+
+    // Adds a translation, or replaces the one with the same language and country
+    pub fn set(&mut self, lang: &str, text: &str) {
+        let (lang_code, country_code) = split_lang(lang);
+        match self
+            .records
+            .iter_mut()
+            .find(|record| record.0 == lang_code && record.1 == country_code)
+        {
+            Some(record) => record.2 = text.to_string(),
+            None => self
+                .records
+                .push((lang_code, country_code, text.to_string())),
+        }
+    }
+
+    pub fn remove(&mut self, lang: &str) -> Option<MlucRecord> {
+        let (lang_code, country_code) = split_lang(lang);
+        let pos = self
+            .records
+            .iter()
+            .position(|record| record.0 == lang_code && record.1 == country_code)?;
+        Some(self.records.remove(pos))
+    }
+}
+
+pub struct MlucRecordPrettyPrint<'a>(pub &'a MlucRecord);
+
+impl Display for MlucRecordPrettyPrint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}/{}) {}", self.0 .0, self.0 .1, self.0 .2)
+    }
+}
+
+impl Display for MultiLocalizedText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let records: Vec<String> = self
+            .records
+            .iter()
+            .map(|record| format!("      {}", MlucRecordPrettyPrint(record)))
+            .collect();
+        write!(f, "{}", records.join("\n"))
+    }
+}
+
+pub struct DictEntry {
+    pub name: Vec<u8>,                  // Name string (raw bytes)
+    pub value: Option<Vec<u8>>,         // Value string (raw bytes)
+    pub display_name: Option<Vec<u8>>,  // Display name (raw mluc type)
+    pub display_value: Option<Vec<u8>>, // Display value (raw mluc type)
+}
+
+pub struct DictType {
+    pub element_size: usize, // 16, 24 or 32 bytes
+    pub entries: Vec<DictEntry>,
+}
+
+// Processing element of a multiProcessElementsType, the values are not decoded
+pub struct ProcessElement {
+    pub element_sig: u32,          // cvst, matf, clut, bACS or eACS
+    pub in_chans_num: u16,         // # Inputs channels
+    pub out_chans_num: u16,        // # Outputs channels
+    pub grid_pts: Option<Vec<u8>>, // # of grid points for each input channel of a clut
+}
+
+// multiProcessElementsType (mpet) : the floating point D2Bx and B2Dx tables
+pub struct MultiProcessElements {
+    pub in_chans_num: u16,
+    pub out_chans_num: u16,
+    pub elements: Vec<ProcessElement>,
+}
+
+impl Display for MultiProcessElements {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "      # Inputs channels : {}\n      # Outputs channels : {}\n      # Processing elements : {}",
+            self.in_chans_num,
+            self.out_chans_num,
+            self.elements.len()
+        )?;
+        for (element_idx, element) in self.elements.iter().enumerate() {
+            write!(
+                f,
+                "\n      {}. {} : {} → {}",
+                element_idx + 1,
+                signature_name(&ICC_PROCESS_ELEMENTS, element.element_sig),
+                element.in_chans_num,
+                element.out_chans_num
+            )?;
+            if let Some(grid_pts) = &element.grid_pts {
+                write!(f, ", {:?} grid points", grid_pts)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Dict entry with the UTF-16BE name and value decoded, and the localized display name and value
+pub struct DictRecord {
+    pub name: String,
+    pub value: Option<String>,
+    pub display_name: Option<MultiLocalizedText>,
+    pub display_value: Option<MultiLocalizedText>,
+}
+
+const DICT_VALUE_SHOWN: usize = 64;
+
+// Dict records as a table, display names and values in the language if any entry has them
+pub struct DictRecordsPrettyPrint<'a>(pub &'a [DictRecord], pub Option<&'a str>);
+
+impl Display for DictRecordsPrettyPrint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shorten = |text: &str| match text.char_indices().nth(DICT_VALUE_SHOWN) {
+            Some((end, _)) => format!("{}…", &text[..end]),
+            None => text.to_string(),
+        };
+        let localized = |mluc: &Option<MultiLocalizedText>| {
+            mluc.as_ref()
+                .and_then(|mluc| mluc.text(self.1))
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let mut rows: Vec<Vec<String>> = vec![vec!["Name".to_string(), "Value".to_string()]];
+        let display_names = self.0.iter().any(|record| record.display_name.is_some());
+        let display_values = self.0.iter().any(|record| record.display_value.is_some());
+        if display_names {
+            rows[0].push("Display name".to_string());
+        }
+        if display_values {
+            rows[0].push("Display value".to_string());
+        }
+        for record in self.0.iter() {
+            let mut row = vec![
+                record.name.clone(),
+                shorten(record.value.as_deref().unwrap_or("(none)")),
+            ];
+            if display_names {
+                row.push(localized(&record.display_name));
+            }
+            if display_values {
+                row.push(shorten(&localized(&record.display_value)));
+            }
+            rows.push(row);
+        }
+
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(widths.iter())
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect();
+                format!("      {}", cells.join(" | ").trim_end())
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl DictEntry {
+    pub fn new(name: &str, value: &str) -> Self {
+        DictEntry {
+            name: write_utf16(name),
+            value: Some(write_utf16(value)),
+            display_name: None,
+            display_value: None,
+        }
+    }
+}
+
+pub type VcgtFormula = [S15Fixed16Number; 9]; // (gamma, min, max) for R, G and B
+
+// Apple make and model
+pub struct MmodType {
+    pub manufacturer: u32,
+    pub model: u32,
+    pub serial_number: u32, // 0 when not set
+    pub date: u32,          // Manufacture date, 0 when not set
+    pub reserved: Vec<u8>,  // Remaining bytes of the tag, kept as is
+}
+
+impl Display for MmodType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "      Manufacturer : 0x{:X?}\n      Model : 0x{:X?}",
-            self.0 .0, self.0 .1
+            self.manufacturer, self.model
+        )?;
+        if self.serial_number != 0 {
+            write!(f, "\n      Serial number : {}", self.serial_number)?;
+        }
+        if self.date != 0 {
+            write!(f, "\n      Manufacture date : 0x{:08X}", self.date)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StandardObserver {
+    Unknown,
+    Cie1931, // 2 degrees
+    Cie1964, // 10 degrees
+    Other(u32),
+}
+
+impl StandardObserver {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => StandardObserver::Unknown,
+            1 => StandardObserver::Cie1931,
+            2 => StandardObserver::Cie1964,
+            code => StandardObserver::Other(code),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            StandardObserver::Unknown => 0,
+            StandardObserver::Cie1931 => 1,
+            StandardObserver::Cie1964 => 2,
+            StandardObserver::Other(code) => *code,
+        }
+    }
+}
+
+impl Display for StandardObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StandardObserver::Unknown => write!(f, "Unknown"),
+            StandardObserver::Cie1931 => write!(f, "CIE 1931 standard colorimetric observer (2°)"),
+            StandardObserver::Cie1964 => write!(f, "CIE 1964 standard colorimetric observer (10°)"),
+            StandardObserver::Other(code) => write!(f, "Invalid (0x{:X})", code),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MeasurementGeometry {
+    Unknown,
+    Geometry45, // 0°:45° or 45°:0°
+    Geometry0d, // 0°:d or d:0°
+    Other(u32),
+}
+
+impl MeasurementGeometry {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => MeasurementGeometry::Unknown,
+            1 => MeasurementGeometry::Geometry45,
+            2 => MeasurementGeometry::Geometry0d,
+            code => MeasurementGeometry::Other(code),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            MeasurementGeometry::Unknown => 0,
+            MeasurementGeometry::Geometry45 => 1,
+            MeasurementGeometry::Geometry0d => 2,
+            MeasurementGeometry::Other(code) => *code,
+        }
+    }
+}
+
+impl Display for MeasurementGeometry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeasurementGeometry::Unknown => write!(f, "Unknown"),
+            MeasurementGeometry::Geometry45 => write!(f, "0°:45° or 45°:0°"),
+            MeasurementGeometry::Geometry0d => write!(f, "0°:d or d:0°"),
+            MeasurementGeometry::Other(code) => write!(f, "Invalid (0x{:X})", code),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StandardIlluminant {
+    Unknown,
+    D50,
+    D65,
+    D93,
+    F2,
+    D55,
+    A,
+    E, // Equi-Power
+    F8,
+    Other(u32),
+}
+
+impl StandardIlluminant {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => StandardIlluminant::Unknown,
+            1 => StandardIlluminant::D50,
+            2 => StandardIlluminant::D65,
+            3 => StandardIlluminant::D93,
+            4 => StandardIlluminant::F2,
+            5 => StandardIlluminant::D55,
+            6 => StandardIlluminant::A,
+            7 => StandardIlluminant::E,
+            8 => StandardIlluminant::F8,
+            code => StandardIlluminant::Other(code),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            StandardIlluminant::Unknown => 0,
+            StandardIlluminant::D50 => 1,
+            StandardIlluminant::D65 => 2,
+            StandardIlluminant::D93 => 3,
+            StandardIlluminant::F2 => 4,
+            StandardIlluminant::D55 => 5,
+            StandardIlluminant::A => 6,
+            StandardIlluminant::E => 7,
+            StandardIlluminant::F8 => 8,
+            StandardIlluminant::Other(code) => *code,
+        }
+    }
+
+    // CIE 1931 xy chromaticity of the illuminant
+    pub fn white_xy(&self) -> Option<(f64, f64)> {
+        match self {
+            StandardIlluminant::D50 => Some((0.3457, 0.3585)),
+            StandardIlluminant::D65 => Some((0.3127, 0.3290)),
+            StandardIlluminant::D93 => Some((0.2831, 0.2971)),
+            StandardIlluminant::F2 => Some((0.3721, 0.3751)),
+            StandardIlluminant::D55 => Some((0.3324, 0.3474)),
+            StandardIlluminant::A => Some((0.4476, 0.4074)),
+            StandardIlluminant::E => Some((1.0 / 3.0, 1.0 / 3.0)),
+            StandardIlluminant::F8 => Some((0.3458, 0.3586)),
+            StandardIlluminant::Unknown | StandardIlluminant::Other(..) => None,
+        }
+    }
+}
+
+impl Display for StandardIlluminant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StandardIlluminant::Unknown => write!(f, "Unknown"),
+            StandardIlluminant::E => write!(f, "Equi-Power (E)"),
+            StandardIlluminant::Other(code) => write!(f, "Invalid (0x{:X})", code),
+            illuminant => write!(f, "{:?}", illuminant),
+        }
+    }
+}
+
+// XYZ values are kept unscaled : backing in 0..1, viewing conditions in cd/m²
+pub struct Measurement {
+    pub observer: StandardObserver,
+    pub backing: [f64; 3],
+    pub geometry: MeasurementGeometry,
+    pub flare: f64, // 0..1
+    pub illuminant: StandardIlluminant,
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "      Observer : {}", self.observer)?;
+        writeln!(
+            f,
+            "      Backing : XYZ {:.4} {:.4} {:.4}",
+            self.backing[0], self.backing[1], self.backing[2]
+        )?;
+        writeln!(f, "      Geometry : {}", self.geometry)?;
+        writeln!(f, "      Flare : {:.1} %", self.flare * 100.0)?;
+        write!(f, "      Illuminant : {}", self.illuminant)
+    }
+}
+
+pub struct ViewingConditions {
+    pub illuminant: [f64; 3],
+    pub surround: [f64; 3],
+    pub illuminant_type: StandardIlluminant,
+}
+
+impl Display for ViewingConditions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "      Illuminant : XYZ {:.4} {:.4} {:.4} cd/m²",
+            self.illuminant[0], self.illuminant[1], self.illuminant[2]
+        )?;
+        writeln!(
+            f,
+            "      Surround : XYZ {:.4} {:.4} {:.4} cd/m²",
+            self.surround[0], self.surround[1], self.surround[2]
+        )?;
+        write!(f, "      Illuminant type : {}", self.illuminant_type)
+    }
+}
+
+// dataType : ASCII text (flags 0, NUL terminated) or binary data (flags 1)
+#[derive(Clone)]
+pub struct DataType {
+    pub flags: u32,
+    pub data: Vec<u8>,
+}
+
+impl DataType {
+    pub fn is_ascii(&self) -> bool {
+        self.flags & 1 == 0
+    }
+}
+
+// Descriptions embedded in other types : 'desc' in version 2, 'mluc' in version 4
+#[derive(Clone)]
+pub enum TextTag {
+    Desc(TextDescription),
+    Mluc(MultiLocalizedText),
+}
+
+impl TextTag {
+    pub fn text(&self, lang: Option<&str>) -> String {
+        match self {
+            TextTag::Desc(desc) => desc.ascii.clone(),
+            TextTag::Mluc(mluc) => mluc.text(lang).unwrap_or_default().to_string(),
+        }
+    }
+}
+
+pub struct ProfileDescription {
+    pub manufacturer: u32, // Device manufacturer signature
+    pub model: u32,        // Device model signature
+    pub attributes: u64,   // Device attributes
+    pub technology: u32,   // Technology signature, 0 if unknown
+    pub manufacturer_desc: TextTag,
+    pub model_desc: TextTag,
+}
+
+pub struct ProfileIdentifier {
+    pub id: [u8; 16], // Profile ID, zero if not computed
+    pub desc: MultiLocalizedText,
+}
+
+// Signature text, "none" for the 0 signature of unknown devices
+fn sig_or_none(sig: u32) -> String {
+    match sig {
+        0 => "none".to_string(),
+        sig => format!(
+            "'{}'",
+            String::from_utf8_lossy(&sig.to_be_bytes()).trim_end()
+        ),
+    }
+}
+
+// One profile of a pseq chain : position, description and language of the texts
+pub struct ProfileDescriptionPrettyPrint<'a>(
+    pub usize,
+    pub &'a ProfileDescription,
+    pub Option<&'a str>,
+);
+
+impl Display for ProfileDescriptionPrettyPrint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let desc = self.1;
+        writeln!(
+            f,
+            "      {}. {} / {}",
+            self.0,
+            desc.manufacturer_desc.text(self.2),
+            desc.model_desc.text(self.2)
+        )?;
+        writeln!(
+            f,
+            "         Manufacturer : {}, model : {}",
+            sig_or_none(desc.manufacturer),
+            sig_or_none(desc.model)
+        )?;
+        writeln!(
+            f,
+            "         Attributes : {}",
+            attributes_str(desc.attributes)
+        )?;
+        write!(
+            f,
+            "         Technology : {}",
+            signature_name(&ICC_TECHNOLOGY, desc.technology)
+        )
+    }
+}
+
+pub struct ProfileIdentifierPrettyPrint<'a>(
+    pub usize,
+    pub &'a ProfileIdentifier,
+    pub Option<&'a str>,
+);
+
+impl Display for ProfileIdentifierPrettyPrint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = if self.1.id == [0u8; 16] {
+            "not computed".to_string()
+        } else {
+            ProfileIdPrettyPrint(self.1.id).to_string()
+        };
+        write!(
+            f,
+            "      {}. {}\n         ID : {}",
+            self.0,
+            self.1.desc.text(self.2).unwrap_or_default(),
+            id
+        )
+    }
+}
+
+pub struct NamedColor {
+    pub name: String,     // Color root name
+    pub pcs: [u16; 3],    // PCS coordinates, 16 bits encoded
+    pub device: Vec<u16>, // Device coordinates, 16 bits encoded
+}
+
+pub struct NamedColors {
+    pub flags: u32,               // Vendor specific flags
+    pub prefix: String,           // Prefix of every color name
+    pub suffix: String,           // Suffix of every color name
+    pub device_coords_num: usize, // # Device coordinates for each color
+    pub colors: Vec<NamedColor>,
+}
+
+impl NamedColors {
+    pub fn full_name(&self, color: &NamedColor) -> String {
+        format!("{}{}{}", self.prefix, color.name, self.suffix)
+    }
+
+    // Case insensitive search in the full color names
+    pub fn find(&self, name: &str) -> Vec<&NamedColor> {
+        let name = name.to_lowercase();
+        self.colors
+            .iter()
+            .filter(|color| self.full_name(color).to_lowercase().contains(&name))
+            .collect()
+    }
+}
+
+// Lab uses the legacy 16 bits encoding (0xFF00 = 100), XYZ the u1Fixed15 one
+fn pcs_u16_values(pcs: &[u16; 3], pcs_lab: bool) -> [f64; 3] {
+    let pcs = pcs.map(|val| val as f64);
+    if pcs_lab {
+        [
+            pcs[0] * 100.0 / 65280.0,
+            pcs[1] * 255.0 / 65280.0 - 128.0,
+            pcs[2] * 255.0 / 65280.0 - 128.0,
+        ]
+    } else {
+        pcs.map(|val| val / 32768.0)
+    }
+}
+
+impl NamedColor {
+    pub fn pcs_values(&self, pcs_lab: bool) -> [f64; 3] {
+        pcs_u16_values(&self.pcs, pcs_lab)
+    }
+
+    // Device values in percent
+    pub fn device_values(&self) -> Vec<f64> {
+        self.device
+            .iter()
+            .map(|val| *val as f64 * 100.0 / 65535.0)
+            .collect()
+    }
+}
+
+// Response of the device channels measured with one unit (Status A, DIN...)
+pub struct ResponseCurve {
+    pub unit: u32,                       // Measurement unit signature
+    pub max_colorant: Vec<[f64; 3]>,     // XYZ of each channel at its maximum
+    pub responses: Vec<Vec<(u16, f64)>>, // Device value and measurement, per channel
+}
+
+impl Display for ResponseCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "      {} :",
+            signature_name(&ICC_RESPONSE_UNITS, self.unit)
+        )?;
+        for (chan, (xyz, values)) in self
+            .max_colorant
+            .iter()
+            .zip(self.responses.iter())
+            .enumerate()
+        {
+            let measurements = values.iter().map(|(_, measurement)| *measurement);
+            write!(
+                f,
+                "\n        Channel {} : max colorant XYZ {:.4} {:.4} {:.4}, {} measurements from {:.3} to {:.3}",
+                chan + 1,
+                xyz[0],
+                xyz[1],
+                xyz[2],
+                values.len(),
+                measurements.clone().fold(f64::INFINITY, f64::min),
+                measurements.fold(f64::NEG_INFINITY, f64::max)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// responseCurveSet16Type : one curve set per measurement unit
+pub struct ResponseCurveSet {
+    pub channels_num: u16,
+    pub curves: Vec<ResponseCurve>,
+}
+
+// Colorant of a clrt or clot tag, in device channel order
+pub struct Colorant {
+    pub name: String,
+    pub pcs: [u16; 3], // PCS values of the full colorant
+}
+
+impl Colorant {
+    pub fn pcs_values(&self, pcs_lab: bool) -> [f64; 3] {
+        pcs_u16_values(&self.pcs, pcs_lab)
+    }
+}
+
+pub struct ColorantPrettyPrint<'a>(pub usize, pub &'a Colorant, pub bool);
+
+impl Display for ColorantPrettyPrint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pcs = self.1.pcs_values(self.2);
+        write!(
+            f,
+            "      {}. {} : {} {:.2} {:.2} {:.2}",
+            self.0,
+            self.1.name,
+            if self.2 { "Lab" } else { "XYZ" },
+            pcs[0],
+            pcs[1],
+            pcs[2]
+        )
+    }
+}
+
+pub struct NamedColorPrettyPrint<'a>(pub &'a NamedColors, pub &'a NamedColor, pub bool);
+
+impl Display for NamedColorPrettyPrint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pcs = self.1.pcs_values(self.2);
+        let device: Vec<String> = self
+            .1
+            .device_values()
+            .iter()
+            .map(|val| format!("{:.1}", val))
+            .collect();
+        write!(
+            f,
+            "      {} : {} {:.2} {:.2} {:.2}, device [{}]",
+            self.0.full_name(self.1),
+            if self.2 { "Lab" } else { "XYZ" },
+            pcs[0],
+            pcs[1],
+            pcs[2],
+            device.join(" ")
         )
     }
 }
+
+pub enum ProfileIdStatus {
+    Valid,
+    Mismatched([u8; 16]), // Computed ID
+    Absent,
+}
+
+impl Display for ProfileIdStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileIdStatus::Valid => write!(f, "ID valid"),
+            ProfileIdStatus::Mismatched(computed) => write!(
+                f,
+                "ID mismatched (computed {})",
+                ProfileIdPrettyPrint(*computed)
+            ),
+            ProfileIdStatus::Absent => write!(f, "ID absent"),
+        }
+    }
+}
+
+pub struct ProfileIdPrettyPrint(pub [u8; 16]);
+
+impl Display for ProfileIdPrettyPrint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
@@ -1 +1,4 @@
+pub mod cgats;
+pub mod edid;
 pub mod icc;
+pub mod profile;
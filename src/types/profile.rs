@@ -0,0 +1,163 @@
+use crate::types::icc::{
+    Chrm, Colorant, Curve, DataType, DictType, Lut16, Lut8, LutAB, Measurement, MmodType,
+    MultiLocalizedText, NamedColors, ParametricCurve, ProfileDescription, ProfileIdentifier,
+    ResponseCurveSet, S15Fixed16Array, TextDescription, U16Fixed16Number, Vcgt, VcgtFormula,
+    ViewingConditions, XYZNumber,
+};
+use crate::utils::bytes::str_to_sig;
+
+// Creation date of the built profiles : a fixed date keeps the output, and so the profile
+// ID, identical from one run to the next
+pub const DEFAULT_CREATION_DATE: [u16; 6] = [2024, 1, 1, 0, 0, 0];
+
+pub struct ProfileHeader {
+    pub size: u32,             // Profile size, computed when serialized
+    pub cmm: u32,              // Preferred CMM type
+    pub version: u32,          // Profile version (major, minor.fix, 0, 0)
+    pub class: u32,            // Profile/Device class
+    pub color_space: u32,      // Color space of data
+    pub pcs: u32,              // Profile Connection Space
+    pub date: [u16; 6],        // Creation date (year, month, day, hours, minutes, seconds)
+    pub platform: u32,         // Primary platform
+    pub flags: u32,            // Profile flags
+    pub manufacturer: u32,     // Device manufacturer
+    pub model: u32,            // Device model
+    pub attributes: u64,       // Device attributes
+    pub rendering_intent: u32, // Rendering intent
+    pub illuminant: XYZNumber, // PCS illuminant
+    pub creator: u32,          // Profile creator
+    pub id: [u8; 16],          // Profile ID
+}
+
+impl ProfileHeader {
+    pub fn new(class: &str, color_space: &str, pcs: &str, illuminant: XYZNumber) -> ProfileHeader {
+        ProfileHeader {
+            size: 0,
+            cmm: 0,
+            version: 0x02400000,
+            class: str_to_sig(class),
+            color_space: str_to_sig(color_space),
+            pcs: str_to_sig(pcs),
+            date: DEFAULT_CREATION_DATE,
+            platform: 0,
+            flags: 0,
+            manufacturer: 0,
+            model: 0,
+            attributes: 0,
+            rendering_intent: 0,
+            illuminant,
+            creator: 0,
+            id: [0; 16],
+        }
+    }
+}
+
+// Unicode description first, ASCII otherwise
+fn to_mluc(data: &TagData) -> Option<MultiLocalizedText> {
+    match data {
+        TagData::Desc(desc) => Some(MultiLocalizedText::new(
+            "en-US",
+            desc.unicode.as_deref().unwrap_or(&desc.ascii),
+        )),
+        TagData::Text(text) => Some(MultiLocalizedText::new("en-US", text)),
+        _ => None,
+    }
+}
+
+pub enum TagData {
+    Text(String),                  // 'text'
+    Desc(TextDescription),         // 'desc'
+    Mluc(MultiLocalizedText),      // 'mluc'
+    XYZ(XYZNumber),                // 'XYZ '
+    Curve(Curve),                  // 'curv'
+    Para(ParametricCurve),         // 'para'
+    Sf32(S15Fixed16Array),         // 'sf32'
+    Lut8(Lut8),                    // 'mft1'
+    Lut16(Lut16),                  // 'mft2'
+    LutAB(LutAB),                  // 'mAB ' and 'mBA '
+    Dict(DictType),                // 'dict'
+    Vcgt(Vcgt),                    // 'vcgt' table
+    VcgtFormula(VcgtFormula),      // 'vcgt' formula
+    Chrm(Chrm),                    // 'chrm'
+    Mmod(MmodType),                // 'mmod'
+    Ncl2(NamedColors),             // 'ncl2'
+    Pseq(Vec<ProfileDescription>), // 'pseq'
+    Psid(Vec<ProfileIdentifier>),  // 'psid'
+    Sig(u32),                      // 'sig '
+    DateTime([u16; 6]),            // 'dtim'
+    UInt8(Vec<u8>),                // 'ui08'
+    UInt16(Vec<u16>),              // 'ui16'
+    UInt32(Vec<u32>),              // 'ui32'
+    UInt64(Vec<u64>),              // 'ui64'
+    Uf32(Vec<U16Fixed16Number>),   // 'uf32'
+    Data(DataType),                // 'data'
+    Clrt(Vec<Colorant>),           // 'clrt'
+    Clro(Vec<u8>),                 // 'clro'
+    Rcs2(ResponseCurveSet),        // 'rcs2'
+    Meas(Measurement),             // 'meas'
+    View(ViewingConditions),       // 'view'
+    Unknown(Vec<u8>),              // Any other type, kept as raw bytes (type signature included)
+}
+
+pub struct Profile {
+    pub header: ProfileHeader,
+    pub tags: Vec<(u32, TagData)>, // (tag signature, tag data) in tag table order
+}
+
+impl Profile {
+    pub fn new(header: ProfileHeader) -> Profile {
+        Profile {
+            header,
+            tags: vec![],
+        }
+    }
+
+    pub fn tag(&self, sig: &str) -> Option<&TagData> {
+        let sig = str_to_sig(sig);
+        self.tags
+            .iter()
+            .find(|(tag_sig, _)| *tag_sig == sig)
+            .map(|(_, data)| data)
+    }
+
+    pub fn set_tag(&mut self, sig: &str, data: TagData) {
+        let sig = str_to_sig(sig);
+        match self.tags.iter_mut().find(|(tag_sig, _)| *tag_sig == sig) {
+            Some(tag) => tag.1 = data,
+            None => self.tags.push((sig, data)),
+        }
+    }
+
+    // Text tags become mluc tags (version 4 type), previous text is kept as en-US.
+    // mluc is not a version 2 type, and a version 2 profile cannot be relabeled as
+    // version 4 without converting its white point, adaptation and other tags
+    pub fn set_localized_text(&mut self, sig: &str, lang: &str, text: &str) -> Result<(), String> {
+        if self.header.version < 0x04000000 {
+            return Err(format!(
+                "Version {}.{} profile : translations (mluc) require a version 4 profile",
+                self.header.version >> 24,
+                (self.header.version >> 20) & 0x0F
+            ));
+        }
+
+        let mut mluc = match self.tag(sig) {
+            Some(TagData::Mluc(mluc)) => mluc.clone(),
+            Some(data) => to_mluc(data).unwrap_or_default(),
+            None => MultiLocalizedText::default(),
+        };
+        mluc.set(lang, text);
+        self.set_tag(sig, TagData::Mluc(mluc));
+        Ok(())
+    }
+
+    pub fn description(&self) -> String {
+        match self.tag("desc") {
+            Some(TagData::Desc(desc)) => desc.ascii.clone(),
+            Some(TagData::Text(text)) => text.clone(),
+            Some(TagData::Mluc(mluc)) if !mluc.records.is_empty() => {
+                mluc.text(None).unwrap_or_default().to_string()
+            }
+            _ => "Unknown".to_string(),
+        }
+    }
+}
@@ -84,3 +84,84 @@ pub fn read_utf16(slice: &[u8]) -> Option<String> {
         .collect::<Result<String, _>>()
         .ok()
 }
+
+// UTF-16BE string without terminator, invalid code units are replaced
+pub fn utf16_be_to_string(slice: &[u8]) -> String {
+    let iter = slice
+        .chunks_exact(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+
+    std::char::decode_utf16(iter)
+        .map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER))
+        .collect::<String>()
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+pub fn str_to_sig(sig: &str) -> u32 {
+    let mut sig_arr = [b' '; 4];
+    for (idx, byte) in sig.bytes().take(4).enumerate() {
+        sig_arr[idx] = byte;
+    }
+    u32::from_be_bytes(sig_arr)
+}
+
+pub fn sig_to_str(sig: u32) -> String {
+    String::from_utf8_lossy(&sig.to_be_bytes()).to_string()
+}
+
+pub fn pad_to_u32(bytes: &mut Vec<u8>) {
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(0);
+    }
+}
+
+pub fn write_utf16(string: &str) -> Vec<u8> {
+    string
+        .encode_utf16()
+        .flat_map(|c| c.to_be_bytes())
+        .collect()
+}
+
+// Hexadecimal text, optionally "0x" prefixed, whitespace between digits ignored
+pub fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = text
+        .trim()
+        .trim_start_matches("0x")
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .chunks_exact(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+// Standard base64 text, padding optional, whitespace ignored
+pub fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for byte in text.bytes().filter(|byte| !byte.is_ascii_whitespace()) {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    (!bytes.is_empty()).then_some(bytes)
+}
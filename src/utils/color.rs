@@ -0,0 +1,221 @@
+use crate::types::icc::{S15Fixed16Number, XYZNumber};
+
+pub type Matrix3 = [[f64; 3]; 3];
+
+// ICC PCS illuminant (D50) as encoded in the header
+pub const D50_XYZ: [f64; 3] = [0.9642, 1.0, 0.8249];
+pub const D50_XY: (f64, f64) = (0.3457, 0.3585);
+pub const D65_XY: (f64, f64) = (0.3127, 0.3290);
+
+pub const BRADFORD: Matrix3 = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+pub fn xy_to_xyz(xy: (f64, f64)) -> [f64; 3] {
+    [xy.0 / xy.1, 1.0, (1.0 - xy.0 - xy.1) / xy.1]
+}
+
+pub fn xyz_to_xy(xyz: &[f64; 3]) -> (f64, f64) {
+    let sum = xyz[0] + xyz[1] + xyz[2];
+    if sum == 0.0 {
+        return (0.0, 0.0);
+    }
+    (xyz[0] / sum, xyz[1] / sum)
+}
+
+// CIE 1976 UCS chromaticity
+pub fn xy_to_uv_prime(xy: (f64, f64)) -> (f64, f64) {
+    let denom = -2.0 * xy.0 + 12.0 * xy.1 + 3.0;
+    if denom == 0.0 {
+        return (0.0, 0.0);
+    }
+    (4.0 * xy.0 / denom, 9.0 * xy.1 / denom)
+}
+
+pub fn xyz_number_to_f64(xyz: &XYZNumber) -> [f64; 3] {
+    xyz.map(|coor| coor.to_num::<f64>())
+}
+
+pub fn f64_to_xyz_number(xyz: &[f64; 3]) -> XYZNumber {
+    xyz.map(S15Fixed16Number::from_num)
+}
+
+pub fn mat_mul(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut res = [[0.0; 3]; 3];
+    for (row, res_row) in res.iter_mut().enumerate() {
+        for (col, res_val) in res_row.iter_mut().enumerate() {
+            *res_val = (0..3).map(|k| a[row][k] * b[k][col]).sum();
+        }
+    }
+    res
+}
+
+pub fn mat_vec(m: &Matrix3, v: &[f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+pub fn mat_inv(m: &Matrix3) -> Result<Matrix3, String> {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+
+    if det.abs() < 1e-12 {
+        return Err("Matrix is not invertible".to_string());
+    }
+
+    Ok([
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) / det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) / det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) / det,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) / det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) / det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) / det,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) / det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) / det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) / det,
+        ],
+    ])
+}
+
+pub fn bradford_adaptation(src_white: &[f64; 3], dst_white: &[f64; 3]) -> Result<Matrix3, String> {
+    let src_cone = mat_vec(&BRADFORD, src_white);
+    let dst_cone = mat_vec(&BRADFORD, dst_white);
+    let scale: Matrix3 = [
+        [dst_cone[0] / src_cone[0], 0.0, 0.0],
+        [0.0, dst_cone[1] / src_cone[1], 0.0],
+        [0.0, 0.0, dst_cone[2] / src_cone[2]],
+    ];
+    let bradford_inv = mat_inv(&BRADFORD)?;
+
+    Ok(mat_mul(&bradford_inv, &mat_mul(&scale, &BRADFORD)))
+}
+
+// Columns are the XYZ of the red, green and blue primaries for the given white
+pub fn rgb_to_xyz_matrix(
+    red: (f64, f64),
+    green: (f64, f64),
+    blue: (f64, f64),
+    white: (f64, f64),
+) -> Result<Matrix3, String> {
+    let r = xy_to_xyz(red);
+    let g = xy_to_xyz(green);
+    let b = xy_to_xyz(blue);
+    let primaries: Matrix3 = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+    let scale = mat_vec(&mat_inv(&primaries)?, &xy_to_xyz(white));
+
+    Ok([
+        [r[0] * scale[0], g[0] * scale[1], b[0] * scale[2]],
+        [r[1] * scale[0], g[1] * scale[1], b[1] * scale[2]],
+        [r[2] * scale[0], g[2] * scale[1], b[2] * scale[2]],
+    ])
+}
+
+// CIE L*a*b* relative to the given white (XYZ with Y = 1)
+pub fn xyz_to_lab(xyz: &[f64; 3], white: &[f64; 3]) -> [f64; 3] {
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let fx = f(xyz[0] / white[0]);
+    let fy = f(xyz[1] / white[1]);
+    let fz = f(xyz[2] / white[2]);
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+pub fn lab_to_xyz(lab: &[f64; 3], white: &[f64; 3]) -> [f64; 3] {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+    let f_inv = |t: f64| {
+        if t > 6.0 / 29.0 {
+            t.powi(3)
+        } else {
+            (116.0 * t - 16.0) * 27.0 / 24389.0
+        }
+    };
+
+    [
+        f_inv(fx) * white[0],
+        f_inv(fy) * white[1],
+        f_inv(fz) * white[2],
+    ]
+}
+
+// CIEDE2000 color difference (kL = kC = kH = 1)
+pub fn delta_e_2000(lab1: &[f64; 3], lab2: &[f64; 3]) -> f64 {
+    let c1 = lab1[1].hypot(lab1[2]);
+    let c2 = lab2[1].hypot(lab2[2]);
+    let c_mean = (c1 + c2) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt());
+
+    let a1 = lab1[1] * (1.0 + g);
+    let a2 = lab2[1] * (1.0 + g);
+    let c1 = a1.hypot(lab1[2]);
+    let c2 = a2.hypot(lab2[2]);
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(lab1[2], a1);
+    let h2 = hue(lab2[2], a2);
+
+    let delta_l = lab2[0] - lab1[0];
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h.to_radians() / 2.0).sin();
+
+    let l_mean = (lab1[0] + lab2[0]) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt();
+    let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    ((delta_l / s_l).powi(2)
+        + (delta_c / s_c).powi(2)
+        + (delta_h / s_h).powi(2)
+        + r_t * (delta_c / s_c) * (delta_h / s_h))
+        .sqrt()
+}
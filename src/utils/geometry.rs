@@ -0,0 +1,77 @@
+pub type Point2 = (f64, f64);
+
+// Shoelace formula, positive for counterclockwise polygons
+pub fn signed_area(polygon: &[Point2]) -> f64 {
+    let mut area = 0.0;
+    for (idx, point) in polygon.iter().enumerate() {
+        let next = polygon[(idx + 1) % polygon.len()];
+        area += point.0 * next.1 - next.0 * point.1;
+    }
+    area / 2.0
+}
+
+pub fn polygon_area(polygon: &[Point2]) -> f64 {
+    signed_area(polygon).abs()
+}
+
+// Side of the point relative to the edge a → b : positive on the left
+fn cross(a: Point2, b: Point2, point: Point2) -> f64 {
+    (b.0 - a.0) * (point.1 - a.1) - (b.1 - a.1) * (point.0 - a.0)
+}
+
+fn edge_intersection(p1: Point2, p2: Point2, a: Point2, b: Point2) -> Point2 {
+    let (d1, d2) = (cross(a, b, p1), cross(a, b, p2));
+    let t = d1 / (d1 - d2);
+    (p1.0 + t * (p2.0 - p1.0), p1.1 + t * (p2.1 - p1.1))
+}
+
+// Sutherland–Hodgman : part of a polygon inside a convex polygon, of any orientation
+pub fn clip_polygon(subject: &[Point2], clip: &[Point2]) -> Vec<Point2> {
+    let orientation = signed_area(clip).signum();
+    let mut output = subject.to_vec();
+
+    for (idx, a) in clip.iter().enumerate() {
+        let b = clip[(idx + 1) % clip.len()];
+        let inside = |point: Point2| cross(*a, b, point) * orientation >= 0.0;
+        let input = std::mem::take(&mut output);
+        for (point_idx, point) in input.iter().enumerate() {
+            let prev = input[(point_idx + input.len() - 1) % input.len()];
+            match (inside(*point), inside(prev)) {
+                (true, true) => output.push(*point),
+                (true, false) => {
+                    output.push(edge_intersection(prev, *point, *a, b));
+                    output.push(*point);
+                }
+                (false, true) => output.push(edge_intersection(prev, *point, *a, b)),
+                (false, false) => (),
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+pub type Point3 = [f64; 3];
+
+pub fn sub3(a: &Point3, b: &Point3) -> Point3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub fn dot3(a: &Point3, b: &Point3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn cross3(a: &Point3, b: &Point3) -> Point3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub fn distance3(a: &Point3, b: &Point3) -> f64 {
+    let diff = sub3(a, b);
+    dot3(&diff, &diff).sqrt()
+}
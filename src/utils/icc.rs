@@ -1,8 +1,24 @@
+use crate::types::icc::ProfileIdStatus;
+use crate::utils::bytes::sig_to_str;
+
 pub const ICC_PF_COLOR_SPACE: [&str; 25] = [
     "XYZ", "Lab", "Luv", "YCbr", "Yxy", "RGB", "GRAY", "HSV", "HLS", "CMYK", "CMY", "2CLR", "3CLR",
     "4CLR", "5CLR", "6CLR", "7CLR", "8CLR", "9CLR", "ACLR", "BCLR", "CCLR", "DCLR", "ECLR", "FCLR",
 ];
 
+// Number of channels of a data or PCS color space signature
+pub fn color_space_channels(space: u32) -> Option<usize> {
+    match sig_to_str(space).trim_end() {
+        "GRAY" => Some(1),
+        "XYZ" | "Lab" | "Luv" | "YCbr" | "Yxy" | "RGB" | "HSV" | "HLS" | "CMY" => Some(3),
+        "CMYK" => Some(4),
+        space if space.len() == 4 && space.ends_with("CLR") => {
+            usize::from_str_radix(&space[..1], 16).ok()
+        }
+        _ => None,
+    }
+}
+
 pub const ICC_CLASS_PROFILE: [(&str, &str); 7] = [
     ("scnr", "Input Device"),
     ("mntr", "Display Device"),
@@ -22,7 +38,102 @@ pub const ICC_PRIM_PLATFORM: [(&str, &str); 6] = [
     ("*nix", "UNIX and Derivatives - *nix"),
 ];
 
-pub const ICC_CHRM_TYPE: [(&str, (f64, f64), (f64, f64), (f64, f64)); 6] = [
+// Technology signatures of the tech tag and of the pseq descriptions
+pub const ICC_TECHNOLOGY: [(&str, &str); 27] = [
+    ("fscn", "Film Scanner"),
+    ("dcam", "Digital Camera"),
+    ("rscn", "Reflective Scanner"),
+    ("ijet", "Ink Jet Printer"),
+    ("twax", "Thermal Wax Printer"),
+    ("epho", "Electrophotographic Printer"),
+    ("esta", "Electrostatic Printer"),
+    ("dsub", "Dye Sublimation Printer"),
+    ("rpho", "Photographic Paper Printer"),
+    ("fprn", "Film Writer"),
+    ("vidm", "Video Monitor"),
+    ("vidc", "Video Camera"),
+    ("pjtv", "Projection Television"),
+    ("CRT ", "Cathode Ray Tube Display"),
+    ("PMD ", "Passive Matrix Display"),
+    ("AMD ", "Active Matrix Display"),
+    ("LCD ", "Liquid Crystal Display"),
+    ("KPCD", "Photo CD"),
+    ("imgs", "Photographic Image Setter"),
+    ("grav", "Gravure"),
+    ("offs", "Offset Lithography"),
+    ("silk", "Silkscreen"),
+    ("flex", "Flexography"),
+    ("mpfs", "Motion Picture Film Scanner"),
+    ("mpfr", "Motion Picture Film Recorder"),
+    ("dmpc", "Digital Motion Picture Camera"),
+    ("dcpj", "Digital Cinema Projector"),
+];
+
+// Perceptual and saturation rendering intent gamuts (rig0, rig2)
+pub const ICC_RENDERING_INTENT_GAMUT: [(&str, &str); 1] =
+    [("prmg", "Perceptual Reference Medium Gamut")];
+
+// Colorimetric intent image states (ciis)
+pub const ICC_IMAGE_STATE: [(&str, &str); 5] = [
+    ("scoe", "Scene colorimetry estimates"),
+    ("sape", "Scene appearance estimates"),
+    ("fpce", "Focal plane colorimetry estimates"),
+    ("rhoc", "Reflection hardcopy original colorimetry"),
+    ("rpoc", "Reflection print output colorimetry"),
+];
+
+// Measurement units of the response curves (rcs2)
+pub const ICC_RESPONSE_UNITS: [(&str, &str); 9] = [
+    ("StaA", "Status A (ANSI PH2.18)"),
+    ("StaE", "Status E (ANSI PH2.18)"),
+    ("StaI", "Status I (ANSI PH2.18)"),
+    ("StaT", "Status T (ISO 5-3)"),
+    ("StaM", "Status M (ANSI PH2.18)"),
+    ("DN  ", "DIN E, no polarizing filter"),
+    ("DN P", "DIN E, with polarizing filter"),
+    ("DNN ", "DIN I, no polarizing filter"),
+    ("DNNP", "DIN I, with polarizing filter"),
+];
+
+// Rendering intents of the header and of the A2Bx, B2Ax, D2Bx, B2Dx and prex tables
+pub const ICC_RENDERING_INTENTS: [&str; 4] = [
+    "Perceptual",
+    "Media-Relative Colorimetric",
+    "Saturation",
+    "ICC-Absolute Colorimetric",
+];
+
+pub fn intent_name(intent: u32) -> &'static str {
+    match usize::try_from(intent) {
+        Ok(intent) if intent < ICC_RENDERING_INTENTS.len() => ICC_RENDERING_INTENTS[intent],
+        _ => "Unknown",
+    }
+}
+
+// Processing elements of the multiProcessElementsType (mpet) used by D2Bx and B2Dx
+pub const ICC_PROCESS_ELEMENTS: [(&str, &str); 5] = [
+    ("cvst", "Curve set"),
+    ("matf", "Matrix"),
+    ("clut", "CLUT"),
+    ("bACS", "Begin ACS (ignored)"),
+    ("eACS", "End ACS (ignored)"),
+];
+
+// Name of a signature in one of the lookup tables, the signature itself if unknown
+pub fn signature_name(table: &[(&str, &str)], sig: u32) -> String {
+    let sig_str = sig_to_str(sig);
+    match table.iter().find(|(table_sig, _)| *table_sig == sig_str) {
+        Some((_, name)) => name.to_string(),
+        None if sig == 0 => "Unknown".to_string(),
+        None => format!("Unknown signature '{}'", sig_str.trim_end()),
+    }
+}
+
+pub type ChrmPrimaries = (&'static str, (f64, f64), (f64, f64), (f64, f64));
+
+// Phosphor or colorant types of the chrm tag, xy of red, green and blue.
+// BT.2020 values are those of Rec. ITU-R BT.2020 system colorimetry (red x is 0.708)
+pub const ICC_CHRM_TYPE: [ChrmPrimaries; 6] = [
     (
         "ITU-R BT.709-2",
         (0.640, 0.330),
@@ -45,12 +156,29 @@ pub const ICC_CHRM_TYPE: [(&str, (f64, f64), (f64, f64), (f64, f64)); 6] = [
     ("P3", (0.680, 0.320), (0.265, 0.690), (0.150, 0.060)),
     (
         "ITU-R BT.2020",
-        (0.780, 0.292),
+        (0.708, 0.292),
         (0.170, 0.797),
         (0.131, 0.046),
     ),
 ];
 
+// Primaries of the Adobe RGB and ProPhoto RGB built-in profiles
+pub const ADOBE_RGB_PRIMARIES: ChrmPrimaries = (
+    "Adobe RGB (1998)",
+    (0.640, 0.330),
+    (0.210, 0.710),
+    (0.150, 0.060),
+);
+pub const PROPHOTO_RGB_PRIMARIES: ChrmPrimaries = (
+    "ProPhoto RGB (ROMM)",
+    (0.7347, 0.2653),
+    (0.1596, 0.8404),
+    (0.0366, 0.0001),
+);
+
+// Other common RGB encodings, matched with ICC_CHRM_TYPE to find the nearest standard
+pub const RGB_PRIMARIES: [ChrmPrimaries; 2] = [ADOBE_RGB_PRIMARIES, PROPHOTO_RGB_PRIMARIES];
+
 pub fn get_date_time_number(date: &[u8; 12]) -> String {
     let year = u16::from_be_bytes(date[0..=1].try_into().unwrap()).to_string();
     let month = u16::from_be_bytes(date[2..=3].try_into().unwrap()).to_string();
@@ -90,3 +218,243 @@ pub fn itu_r_bt1886(input_signal: f32, white_lum: f32, black_lum: f32) -> Result
 
     Ok(lum)
 }
+
+// MD5 of the whole profile with the profile flags, rendering intent and profile ID zeroed
+pub fn compute_profile_id(icc_raw_data: &[u8]) -> [u8; 16] {
+    let mut id_data = icc_raw_data.to_vec();
+    id_data[44..=47].fill(0);
+    id_data[64..=67].fill(0);
+    id_data[84..=99].fill(0);
+
+    md5::compute(&id_data).0
+}
+
+pub fn check_profile_id(icc_raw_data: &[u8]) -> ProfileIdStatus {
+    let pf_id: [u8; 16] = icc_raw_data[84..=99].try_into().unwrap();
+
+    if pf_id == [0u8; 16] {
+        return ProfileIdStatus::Absent;
+    }
+
+    // The ID covers the profile size declared in the header, not trailing bytes
+    let pf_size = u32::from_be_bytes(icc_raw_data[0..=3].try_into().unwrap()) as usize;
+    let computed_id = compute_profile_id(&icc_raw_data[..pf_size.clamp(128, icc_raw_data.len())]);
+
+    if computed_id == pf_id {
+        ProfileIdStatus::Valid
+    } else {
+        ProfileIdStatus::Mismatched(computed_id)
+    }
+}
+
+// (tag, allowed types in version 2, allowed types in version 4)
+pub type TagTypes = (
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+pub const ICC_TAG_TYPES: [TagTypes; 50] = [
+    ("A2B0", &["mft1", "mft2"], &["mft1", "mft2", "mAB "]),
+    ("A2B1", &["mft1", "mft2"], &["mft1", "mft2", "mAB "]),
+    ("A2B2", &["mft1", "mft2"], &["mft1", "mft2", "mAB "]),
+    ("B2A0", &["mft1", "mft2"], &["mft1", "mft2", "mBA "]),
+    ("B2A1", &["mft1", "mft2"], &["mft1", "mft2", "mBA "]),
+    ("B2A2", &["mft1", "mft2"], &["mft1", "mft2", "mBA "]),
+    ("gamt", &["mft1", "mft2"], &["mft1", "mft2", "mBA "]),
+    ("pre0", &["mft1", "mft2"], &["mft1", "mft2", "mAB ", "mBA "]),
+    ("pre1", &["mft1", "mft2"], &["mft1", "mft2", "mBA "]),
+    ("pre2", &["mft1", "mft2"], &["mft1", "mft2", "mBA "]),
+    ("D2B0", &[], &["mpet"]),
+    ("D2B1", &[], &["mpet"]),
+    ("D2B2", &[], &["mpet"]),
+    ("D2B3", &[], &["mpet"]),
+    ("B2D0", &[], &["mpet"]),
+    ("B2D1", &[], &["mpet"]),
+    ("B2D2", &[], &["mpet"]),
+    ("B2D3", &[], &["mpet"]),
+    ("rXYZ", &["XYZ "], &["XYZ "]),
+    ("gXYZ", &["XYZ "], &["XYZ "]),
+    ("bXYZ", &["XYZ "], &["XYZ "]),
+    ("wtpt", &["XYZ "], &["XYZ "]),
+    ("bkpt", &["XYZ "], &["XYZ "]),
+    ("lumi", &["XYZ "], &["XYZ "]),
+    ("rTRC", &["curv"], &["curv", "para"]),
+    ("gTRC", &["curv"], &["curv", "para"]),
+    ("bTRC", &["curv"], &["curv", "para"]),
+    ("kTRC", &["curv"], &["curv", "para"]),
+    ("desc", &["desc"], &["mluc"]),
+    ("dmnd", &["desc"], &["mluc"]),
+    ("dmdd", &["desc"], &["mluc"]),
+    ("vued", &["desc"], &["mluc"]),
+    ("cprt", &["text"], &["mluc"]),
+    ("calt", &["dtim"], &["dtim"]),
+    ("targ", &["text"], &["text"]),
+    ("chad", &["sf32"], &["sf32"]),
+    ("chrm", &["chrm"], &["chrm"]),
+    ("clrt", &["clrt"], &["clrt"]),
+    ("clot", &[], &["clrt"]),
+    ("clro", &["clro"], &["clro"]),
+    ("resp", &[], &["rcs2"]),
+    ("meas", &["meas"], &["meas"]),
+    ("view", &["view"], &["view"]),
+    ("tech", &["sig "], &["sig "]),
+    ("rig0", &[], &["sig "]),
+    ("rig2", &[], &["sig "]),
+    ("ciis", &[], &["sig "]),
+    ("pseq", &["pseq"], &["pseq"]),
+    ("psid", &[], &["psid"]),
+    ("ncl2", &["ncl2"], &["ncl2"]),
+];
+
+// Label of the tags in the tag listing, the tag signature is shown for the others
+pub const ICC_TAG_NAMES: [(&str, &str); 56] = [
+    ("desc", "Description"),
+    ("cprt", "Copyright"),
+    ("dmnd", "Device Manufacturer name"),
+    ("dmdd", "Device model description"),
+    ("vued", "Viewing conditions description"),
+    ("wtpt", "Media white point illuminant"),
+    ("bkpt", "Media black point illuminant"),
+    ("lumi", "Luminance"),
+    ("rXYZ", "Red matrix coordinates"),
+    ("gXYZ", "Green matrix coordinates"),
+    ("bXYZ", "Blue matrix coordinates"),
+    ("rTRC", "rTRC, red tone reproduction curve"),
+    ("gTRC", "gTRC, green tone reproduction curve"),
+    ("bTRC", "bTRC, blue tone reproduction curve"),
+    ("kTRC", "kTRC, gray tone reproduction curve"),
+    ("A2B0", "A2B0 Table, device to PCS, perceptual"),
+    ("A2B1", "A2B1 Table, device to PCS, colorimetric"),
+    ("A2B2", "A2B2 Table, device to PCS, saturation"),
+    ("B2A0", "B2A0 Table, PCS to device, perceptual"),
+    ("B2A1", "B2A1 Table, PCS to device, colorimetric"),
+    ("B2A2", "B2A2 Table, PCS to device, saturation"),
+    (
+        "D2B0",
+        "D2B0 Table, device to PCS (floating point), perceptual",
+    ),
+    (
+        "D2B1",
+        "D2B1 Table, device to PCS (floating point), media-relative colorimetric",
+    ),
+    (
+        "D2B2",
+        "D2B2 Table, device to PCS (floating point), saturation",
+    ),
+    (
+        "D2B3",
+        "D2B3 Table, device to PCS (floating point), ICC-absolute colorimetric",
+    ),
+    (
+        "B2D0",
+        "B2D0 Table, PCS to device (floating point), perceptual",
+    ),
+    (
+        "B2D1",
+        "B2D1 Table, PCS to device (floating point), media-relative colorimetric",
+    ),
+    (
+        "B2D2",
+        "B2D2 Table, PCS to device (floating point), saturation",
+    ),
+    (
+        "B2D3",
+        "B2D3 Table, PCS to device (floating point), ICC-absolute colorimetric",
+    ),
+    ("pre0", "Preview, PCS to proofed PCS, perceptual"),
+    ("pre1", "Preview, PCS to proofed PCS, colorimetric"),
+    ("pre2", "Preview, PCS to proofed PCS, saturation"),
+    (
+        "gamt",
+        "Gamut Table (PCS to 0 in gamut, up to 1 out of gamut)",
+    ),
+    ("chad", "Chromatic adaptation"),
+    (
+        "arts",
+        "Absolute to media relative transform (Bradford, ICC Recommendation)",
+    ),
+    ("chrm", "Chromaticity"),
+    ("calt", "Calibration date and time"),
+    ("DevD", "Characterizaton device values (ti3 file)"),
+    ("CIED", "Characterizaton measurement values (ti3 file)"),
+    ("targ", "Characterizaton target (ti3 file)"),
+    ("ncl2", "Named colors"),
+    ("tech", "Technology"),
+    ("rig0", "Perceptual rendering intent gamut"),
+    ("rig2", "Saturation rendering intent gamut"),
+    ("ciis", "Colorimetric intent image state"),
+    ("clrt", "Colorant table"),
+    ("clot", "Colorant table out"),
+    ("clro", "Colorant order (first printed first)"),
+    ("resp", "Output response"),
+    ("meas", "Measurement"),
+    ("view", "Viewing conditions"),
+    ("pseq", "Profile sequence"),
+    ("psid", "Profile sequence identifiers"),
+    ("meta", "Metadatas"),
+    ("vcgt", "Video Card Gamma Table"),
+    ("mmod", "APPLE Make and Model"),
+];
+
+pub fn tag_name(tag: &str) -> &str {
+    match ICC_TAG_NAMES.iter().find(|(tag_sig, _)| *tag_sig == tag) {
+        Some((_, name)) => name,
+        None => tag,
+    }
+}
+
+// Required tags per profile class, any one of the alternatives must be complete.
+// desc, cprt and wtpt (not for DeviceLink) are required for every class.
+pub type RequiredTags = (&'static str, &'static [&'static [&'static str]]);
+
+pub const ICC_REQUIRED_TAGS: [RequiredTags; 7] = [
+    (
+        "scnr",
+        &[
+            &["A2B0"],
+            &["rXYZ", "gXYZ", "bXYZ", "rTRC", "gTRC", "bTRC"],
+            &["kTRC"],
+        ],
+    ),
+    (
+        "mntr",
+        &[
+            &["A2B0", "B2A0"],
+            &["rXYZ", "gXYZ", "bXYZ", "rTRC", "gTRC", "bTRC"],
+            &["kTRC"],
+        ],
+    ),
+    (
+        "prtr",
+        &[
+            &["A2B0", "A2B1", "A2B2", "B2A0", "B2A1", "B2A2", "gamt"],
+            &["kTRC"],
+        ],
+    ),
+    ("link", &[&["pseq", "A2B0"]]),
+    ("spac", &[&["A2B0", "B2A0"]]),
+    ("abst", &[&["A2B0"]]),
+    ("nmcl", &[&["ncl2"]]),
+];
+
+// Device attributes bits 0 to 3 (ICC.1 7.2.14)
+pub fn attributes_str(attributes: u64) -> String {
+    [
+        ("Reflective", "Transparency"),
+        ("Glossy", "Matte"),
+        ("Positive", "Negative"),
+        ("Color", "B&W"),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(bit, (unset, set))| {
+        if attributes >> bit & 1 == 1 {
+            *set
+        } else {
+            *unset
+        }
+    })
+    .collect::<Vec<&str>>()
+    .join(", ")
+}
@@ -1,3 +1,5 @@
 pub mod bytes;
+pub mod color;
+pub mod geometry;
 pub mod icc;
 pub mod print;
@@ -1,3 +1,45 @@
 pub fn print_usage(args: &[String]) {
-    println!("Usage : {} icc_profile_file_name [number of point to extract from vcgt] [correction scale in your monitor (+/- this value)]", args[0]);
+    println!("Usage : {} icc_profile_file_name [number of point to extract from vcgt] [correction scale in your monitor (+/- this value)] [--lang <language>[-<COUNTRY>]]", args[0]);
+    println!(
+        "        {} write <icc_profile_file_name> <output_file_name>",
+        args[0]
+    );
+    println!(
+        "        {} translate <icc_profile_file_name> <output_file_name> <tag> <language>-<COUNTRY> <text>",
+        args[0]
+    );
+    println!(
+        "        {} ti3 <icc_profile_file_name> <output_file_name> [targ|DevD|CIED]",
+        args[0]
+    );
+    println!("        {} validate <icc_profile_file_name>", args[0]);
+    println!(
+        "        {} verify <icc_profile_file_name> [number of worst patches]",
+        args[0]
+    );
+    println!(
+        "        {} ncl2 <icc_profile_file_name> [color name] [--export <output_file_name.csv|.json>]",
+        args[0]
+    );
+    println!(
+        "        {} resp <icc_profile_file_name> [--export <output_file_name.csv>]",
+        args[0]
+    );
+    println!(
+        "        {} dict <icc_profile_file_name> [meta|<dict tag>] [--export <output_file_name.json>]",
+        args[0]
+    );
+    println!("        {} edid <icc_profile_file_name>", args[0]);
+    println!("        {} coverage <icc_profile_file_name>", args[0]);
+    println!(
+        "        {} volume <icc_profile_file_name> [other_icc_profile_file_name] [--export <output_file_name.wrl|.x3d|.obj>]",
+        args[0]
+    );
+    println!(
+        "        {} gamut <icc_profile_file_name> <lab|xyz> <values...|colors.csv>",
+        args[0]
+    );
+    println!("        {} layout <icc_profile_file_name>", args[0]);
+    println!();
+    println!("Profile file names can be replaced by a built-in profile : std:sRGB, std:Display-P3, std:Adobe-RGB, std:Rec.2020, std:ProPhoto, std:Lab-D50, std:XYZ-D50");
 }
@@ -0,0 +1,30 @@
+use crate::types::cgats::Cgats;
+
+// NUMBER_OF_FIELDS and NUMBER_OF_SETS are computed from the tables content
+pub fn write_cgats(cgats: &Cgats) -> String {
+    let mut text = String::new();
+
+    for table in cgats.tables.iter() {
+        text.push_str(&format!("{}\n\n", table.file_type));
+        for (key, value) in table.keywords.iter() {
+            if key == "NUMBER_OF_FIELDS" || key == "NUMBER_OF_SETS" {
+                continue;
+            }
+            text.push_str(&format!("{} {}\n", key, value));
+        }
+
+        text.push_str(&format!("\nNUMBER_OF_FIELDS {}\n", table.fields.len()));
+        text.push_str("BEGIN_DATA_FORMAT\n");
+        text.push_str(&format!("{}\n", table.fields.join(" ")));
+        text.push_str("END_DATA_FORMAT\n");
+
+        text.push_str(&format!("\nNUMBER_OF_SETS {}\n", table.sets.len()));
+        text.push_str("BEGIN_DATA\n");
+        for set in table.sets.iter() {
+            text.push_str(&format!("{}\n", set.join(" ")));
+        }
+        text.push_str("END_DATA\n\n");
+    }
+
+    text
+}
@@ -0,0 +1,32 @@
+use json::{object, JsonValue};
+
+use crate::types::icc::{DictRecord, MultiLocalizedText};
+
+// Translations keyed by "language-COUNTRY"
+fn mluc_json(mluc: &MultiLocalizedText) -> JsonValue {
+    let mut translations = JsonValue::new_object();
+    for (lang, country, text) in mluc.records.iter() {
+        translations[format!("{}-{}", lang, country)] = text.clone().into();
+    }
+    translations
+}
+
+// One object per entry name, with its value and the translations of its display name and value
+pub fn write_dict_json(records: &[DictRecord]) -> String {
+    let mut dict = JsonValue::new_object();
+
+    for record in records.iter() {
+        let mut entry = object! {
+            value: record.value.clone(),
+        };
+        if let Some(display_name) = &record.display_name {
+            entry["display_name"] = mluc_json(display_name);
+        }
+        if let Some(display_value) = &record.display_value {
+            entry["display_value"] = mluc_json(display_value);
+        }
+        dict[record.name.as_str()] = entry;
+    }
+
+    dict.pretty(2)
+}
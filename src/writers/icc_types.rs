@@ -0,0 +1,521 @@
+use crate::types::icc::*;
+use crate::types::profile::TagData;
+use crate::utils::bytes::{pad_to_u32, write_utf16};
+
+fn type_header(type_sig: u32) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(8);
+    bytes.extend(type_sig.to_be_bytes());
+    bytes.extend([0u8; 4]);
+    bytes
+}
+
+fn write_sf32(bytes: &mut Vec<u8>, values: &[S15Fixed16Number]) {
+    values
+        .iter()
+        .for_each(|value| bytes.extend(value.to_be_bytes()));
+}
+
+fn write_u16(bytes: &mut Vec<u8>, values: &[u16]) {
+    values
+        .iter()
+        .for_each(|value| bytes.extend(value.to_be_bytes()));
+}
+
+fn sig_code(code: &str) -> [u8; 2] {
+    let mut code_arr = [0u8; 2];
+    for (idx, byte) in code.bytes().take(2).enumerate() {
+        code_arr[idx] = byte;
+    }
+    code_arr
+}
+
+pub fn write_text_type(text: &str) -> Vec<u8> {
+    let mut bytes = type_header(0x74657874);
+    bytes.extend(text.as_bytes());
+    bytes.push(0);
+    bytes
+}
+
+pub fn write_desc_type(desc: &TextDescription) -> Vec<u8> {
+    let mut bytes = type_header(0x64657363);
+
+    bytes.extend((desc.ascii.len() as u32 + 1).to_be_bytes());
+    bytes.extend(desc.ascii.as_bytes());
+    bytes.push(0);
+
+    bytes.extend(desc.unicode_lang.to_be_bytes());
+    match &desc.unicode {
+        Some(unicode) => {
+            let utf16 = write_utf16(unicode);
+            bytes.extend((utf16.len() as u32 / 2 + 1).to_be_bytes());
+            bytes.extend(utf16);
+            bytes.extend([0u8; 2]);
+        }
+        None => bytes.extend(0u32.to_be_bytes()),
+    }
+
+    bytes.extend(desc.scriptcode_code.to_be_bytes());
+    bytes.push(desc.scriptcode_count);
+    bytes.extend(desc.scriptcode_field);
+    bytes
+}
+
+pub fn write_mluc_type(mluc: &MultiLocalizedText) -> Vec<u8> {
+    let records = &mluc.records;
+    let mut bytes = type_header(0x6D6C7563);
+    bytes.extend((records.len() as u32).to_be_bytes());
+    bytes.extend(12u32.to_be_bytes());
+
+    let mut str_offset = 16 + 12 * records.len();
+    let mut strings: Vec<u8> = vec![];
+
+    for (lang_code, country_code, string) in records {
+        let utf16 = write_utf16(string);
+        bytes.extend(sig_code(lang_code));
+        bytes.extend(sig_code(country_code));
+        bytes.extend((utf16.len() as u32).to_be_bytes());
+        bytes.extend((str_offset as u32).to_be_bytes());
+        str_offset += utf16.len();
+        strings.extend(utf16);
+    }
+
+    bytes.extend(strings);
+    bytes
+}
+
+pub fn write_xyz_type(xyz: &XYZNumber) -> Vec<u8> {
+    let mut bytes = type_header(0x58595A20);
+    write_sf32(&mut bytes, xyz);
+    bytes
+}
+
+pub fn write_curve_type(curve: &Curve) -> Vec<u8> {
+    let mut bytes = type_header(0x63757276);
+
+    if let Some(gamma) = curve.gamma {
+        bytes.extend(1u32.to_be_bytes());
+        bytes.extend(gamma.to_be_bytes());
+    } else if let Some(table) = &curve.curve {
+        bytes.extend((table.len() as u32).to_be_bytes());
+        write_u16(&mut bytes, table);
+    } else {
+        bytes.extend(0u32.to_be_bytes());
+    }
+
+    bytes
+}
+
+pub fn write_para_type(para: &ParametricCurve) -> Vec<u8> {
+    let mut bytes = type_header(0x70617261);
+    bytes.extend(para.function_type.to_be_bytes());
+    bytes.extend([0u8; 2]);
+    write_sf32(&mut bytes, &para.params);
+    bytes
+}
+
+pub fn write_sf32_type(values: &[S15Fixed16Number]) -> Vec<u8> {
+    let mut bytes = type_header(0x73663332);
+    write_sf32(&mut bytes, values);
+    bytes
+}
+
+pub fn write_lut8_type(lut8: &Lut8) -> Vec<u8> {
+    let mut bytes = type_header(0x6D667431);
+    bytes.extend([lut8.in_chans_num, lut8.out_chans_num, lut8.grid_pts_num, 0]);
+    write_sf32(&mut bytes, &lut8.e_params);
+    bytes.extend(&lut8.in_table);
+    bytes.extend(&lut8.clut_table);
+    bytes.extend(&lut8.out_table);
+    bytes
+}
+
+pub fn write_lut16_type(lut16: &Lut16) -> Vec<u8> {
+    let mut bytes = type_header(0x6D667432);
+    bytes.extend([
+        lut16.in_chans_num,
+        lut16.out_chans_num,
+        lut16.grid_pts_num,
+        0,
+    ]);
+    write_sf32(&mut bytes, &lut16.e_params);
+    bytes.extend(lut16.in_table_entries_num.to_be_bytes());
+    bytes.extend(lut16.out_table_entries_num.to_be_bytes());
+    write_u16(&mut bytes, &lut16.in_table);
+    write_u16(&mut bytes, &lut16.clut_table);
+    write_u16(&mut bytes, &lut16.out_table);
+    bytes
+}
+
+pub fn write_lut_curve(curve: &LutCurve) -> Vec<u8> {
+    match curve {
+        LutCurve::Curv(curve) => write_curve_type(curve),
+        LutCurve::Para(para) => write_para_type(para),
+    }
+}
+
+fn write_lut_curves(curves: &[LutCurve]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    for curve in curves {
+        bytes.extend(write_lut_curve(curve));
+        pad_to_u32(&mut bytes);
+    }
+    bytes
+}
+
+fn write_clut(clut: &Clut) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![0; 16];
+    bytes[..clut.grid_pts.len()].copy_from_slice(&clut.grid_pts);
+    bytes.extend([clut.precision, 0, 0, 0]);
+    match clut.precision {
+        1 => bytes.extend(clut.table.iter().map(|val| *val as u8)),
+        _ => write_u16(&mut bytes, &clut.table),
+    }
+    pad_to_u32(&mut bytes);
+    bytes
+}
+
+// Elements are written in the order of their offsets in the tag : B, matrix, M, CLUT, A
+pub fn write_lutab_type(lut: &LutAB) -> Vec<u8> {
+    let mut bytes = type_header(lut.lut_sig);
+    bytes.extend([lut.in_chans_num, lut.out_chans_num, 0, 0]);
+
+    let mut elements: Vec<u8> = vec![];
+    let mut offsets = [0u32; 5];
+    let elements_start = 32;
+
+    let matrix = lut.matrix.as_ref().map(|matrix| {
+        let mut bytes: Vec<u8> = vec![];
+        write_sf32(&mut bytes, matrix);
+        bytes
+    });
+
+    let element_bytes = [
+        Some(write_lut_curves(&lut.b_curves)),
+        matrix,
+        lut.m_curves.as_ref().map(|curves| write_lut_curves(curves)),
+        lut.clut.as_ref().map(write_clut),
+        lut.a_curves.as_ref().map(|curves| write_lut_curves(curves)),
+    ];
+
+    for (element_idx, element) in element_bytes.into_iter().enumerate() {
+        if let Some(element) = element {
+            offsets[element_idx] = (elements_start + elements.len()) as u32;
+            elements.extend(element);
+        }
+    }
+
+    offsets
+        .iter()
+        .for_each(|offset| bytes.extend(offset.to_be_bytes()));
+    bytes.extend(elements);
+    bytes
+}
+
+pub fn write_dict_type(dict: &DictType) -> Vec<u8> {
+    let mut bytes = type_header(0x64696374);
+    bytes.extend((dict.entries.len() as u32).to_be_bytes());
+    bytes.extend((dict.element_size as u32).to_be_bytes());
+
+    let mut strings: Vec<u8> = vec![];
+    let strings_start = 16 + dict.element_size * dict.entries.len();
+
+    for entry in dict.entries.iter() {
+        let fields = [
+            Some(&entry.name),
+            entry.value.as_ref(),
+            entry.display_name.as_ref(),
+            entry.display_value.as_ref(),
+        ];
+
+        for field in fields.iter().take(dict.element_size / 8) {
+            match field {
+                Some(field) => {
+                    bytes.extend(((strings_start + strings.len()) as u32).to_be_bytes());
+                    bytes.extend((field.len() as u32).to_be_bytes());
+                    strings.extend(field.iter());
+                    pad_to_u32(&mut strings);
+                }
+                None => bytes.extend([0u8; 8]),
+            }
+        }
+    }
+
+    bytes.extend(strings);
+    bytes
+}
+
+pub fn write_vcgt_type(vcgt: &Vcgt) -> Vec<u8> {
+    let mut bytes = type_header(0x76636774);
+    bytes.extend(0u32.to_be_bytes());
+    bytes.extend((vcgt.6 as u16).to_be_bytes());
+    bytes.extend((vcgt.7 as u16).to_be_bytes());
+    bytes.extend(2u16.to_be_bytes());
+    write_u16(&mut bytes, &vcgt.1);
+    write_u16(&mut bytes, &vcgt.2);
+    write_u16(&mut bytes, &vcgt.3);
+    bytes
+}
+
+pub fn write_vcgt_formula(formula: &VcgtFormula) -> Vec<u8> {
+    let mut bytes = type_header(0x76636774);
+    bytes.extend(1u32.to_be_bytes());
+    write_sf32(&mut bytes, formula);
+    bytes
+}
+
+pub fn write_chrm_type(chrm: &Chrm) -> Vec<u8> {
+    let mut bytes = type_header(0x6368726D);
+    bytes.extend((chrm.channels.len() as u16).to_be_bytes());
+    bytes.extend(chrm.phs_col_code.to_be_bytes());
+
+    for (x, y) in &chrm.channels {
+        bytes.extend(x.to_be_bytes());
+        bytes.extend(y.to_be_bytes());
+    }
+
+    bytes
+}
+
+pub fn write_mmod_type(mmod: &MmodType) -> Vec<u8> {
+    let mut bytes = type_header(0x6D6D6F64);
+    bytes.extend(mmod.manufacturer.to_be_bytes());
+    bytes.extend(mmod.model.to_be_bytes());
+    bytes.extend(mmod.serial_number.to_be_bytes());
+    bytes.extend(mmod.date.to_be_bytes());
+    bytes.extend(&mmod.reserved);
+    bytes
+}
+
+fn write_ascii32(bytes: &mut Vec<u8>, text: &str) {
+    let mut field = [0u8; 32];
+    for (idx, byte) in text.bytes().take(31).enumerate() {
+        field[idx] = byte;
+    }
+    bytes.extend(field);
+}
+
+pub fn write_ncl2_type(named_colors: &NamedColors) -> Vec<u8> {
+    let mut bytes = type_header(0x6E636C32);
+    bytes.extend(named_colors.flags.to_be_bytes());
+    bytes.extend((named_colors.colors.len() as u32).to_be_bytes());
+    bytes.extend((named_colors.device_coords_num as u32).to_be_bytes());
+    write_ascii32(&mut bytes, &named_colors.prefix);
+    write_ascii32(&mut bytes, &named_colors.suffix);
+
+    for color in named_colors.colors.iter() {
+        write_ascii32(&mut bytes, &color.name);
+        color
+            .pcs
+            .iter()
+            .chain(color.device.iter())
+            .for_each(|coord| bytes.extend(coord.to_be_bytes()));
+    }
+
+    bytes
+}
+
+pub fn write_sig_type(sig: u32) -> Vec<u8> {
+    let mut bytes = type_header(0x73696720);
+    bytes.extend(sig.to_be_bytes());
+    bytes
+}
+
+pub fn write_clrt_type(colorants: &[Colorant]) -> Vec<u8> {
+    let mut bytes = type_header(0x636C7274);
+    bytes.extend((colorants.len() as u32).to_be_bytes());
+
+    for colorant in colorants {
+        write_ascii32(&mut bytes, &colorant.name);
+        write_u16(&mut bytes, &colorant.pcs);
+    }
+
+    bytes
+}
+
+pub fn write_clro_type(order: &[u8]) -> Vec<u8> {
+    let mut bytes = type_header(0x636C726F);
+    bytes.extend((order.len() as u32).to_be_bytes());
+    bytes.extend(order);
+    bytes
+}
+
+// Curve structures follow the offsets table, in the order of the set
+pub fn write_rcs2_type(response: &ResponseCurveSet) -> Vec<u8> {
+    let mut bytes = type_header(0x72637332);
+    bytes.extend(response.channels_num.to_be_bytes());
+    bytes.extend((response.curves.len() as u16).to_be_bytes());
+
+    let mut curves: Vec<u8> = vec![];
+    let curves_offset = 12 + 4 * response.curves.len();
+    for curve in response.curves.iter() {
+        bytes.extend(((curves_offset + curves.len()) as u32).to_be_bytes());
+
+        curves.extend(curve.unit.to_be_bytes());
+        for values in curve.responses.iter() {
+            curves.extend((values.len() as u32).to_be_bytes());
+        }
+        for xyz in curve.max_colorant.iter() {
+            write_sf32_xyz(&mut curves, xyz);
+        }
+        for (device, measurement) in curve.responses.iter().flatten() {
+            curves.extend(device.to_be_bytes());
+            curves.extend([0u8; 2]);
+            write_sf32(&mut curves, &[S15Fixed16Number::from_num(*measurement)]);
+        }
+    }
+
+    bytes.extend(curves);
+    bytes
+}
+
+pub fn write_dtim_type(date: &[u16; 6]) -> Vec<u8> {
+    let mut bytes = type_header(0x6474696D);
+    write_u16(&mut bytes, date);
+    bytes
+}
+
+// ui08, ui16, ui32, ui64 and uf32 arrays : the type header and the big-endian values
+pub fn write_array_type<const N: usize>(type_sig: u32, values: &[[u8; N]]) -> Vec<u8> {
+    let mut bytes = type_header(type_sig);
+    values.iter().for_each(|value| bytes.extend(value));
+    bytes
+}
+
+pub fn write_data_type(data: &DataType) -> Vec<u8> {
+    let mut bytes = type_header(0x64617461);
+    bytes.extend(data.flags.to_be_bytes());
+    bytes.extend(&data.data);
+    bytes
+}
+
+fn write_sf32_xyz(bytes: &mut Vec<u8>, xyz: &[f64; 3]) {
+    write_sf32(bytes, &xyz.map(S15Fixed16Number::from_num));
+}
+
+pub fn write_meas_type(meas: &Measurement) -> Vec<u8> {
+    let mut bytes = type_header(0x6D656173);
+    bytes.extend(meas.observer.code().to_be_bytes());
+    write_sf32_xyz(&mut bytes, &meas.backing);
+    bytes.extend(meas.geometry.code().to_be_bytes());
+    bytes.extend(U16Fixed16Number::from_num(meas.flare).to_be_bytes());
+    bytes.extend(meas.illuminant.code().to_be_bytes());
+    bytes
+}
+
+pub fn write_view_type(view: &ViewingConditions) -> Vec<u8> {
+    let mut bytes = type_header(0x76696577);
+    write_sf32_xyz(&mut bytes, &view.illuminant);
+    write_sf32_xyz(&mut bytes, &view.surround);
+    bytes.extend(view.illuminant_type.code().to_be_bytes());
+    bytes
+}
+
+fn write_text_tag(text: &TextTag) -> Vec<u8> {
+    match text {
+        TextTag::Desc(desc) => write_desc_type(desc),
+        TextTag::Mluc(mluc) => write_mluc_type(mluc),
+    }
+}
+
+// Embedded description tags follow each other without padding
+pub fn write_pseq_type(descs: &[ProfileDescription]) -> Vec<u8> {
+    let mut bytes = type_header(0x70736571);
+    bytes.extend((descs.len() as u32).to_be_bytes());
+
+    for desc in descs {
+        bytes.extend(desc.manufacturer.to_be_bytes());
+        bytes.extend(desc.model.to_be_bytes());
+        bytes.extend(desc.attributes.to_be_bytes());
+        bytes.extend(desc.technology.to_be_bytes());
+        bytes.extend(write_text_tag(&desc.manufacturer_desc));
+        bytes.extend(write_text_tag(&desc.model_desc));
+    }
+
+    bytes
+}
+
+pub fn write_psid_type(ids: &[ProfileIdentifier]) -> Vec<u8> {
+    let mut bytes = type_header(0x70736964);
+    bytes.extend((ids.len() as u32).to_be_bytes());
+
+    let mut elems: Vec<u8> = vec![];
+    let elems_offset = 12 + 8 * ids.len();
+    for id in ids {
+        let mut elem = id.id.to_vec();
+        elem.extend(write_mluc_type(&id.desc));
+        bytes.extend(((elems_offset + elems.len()) as u32).to_be_bytes());
+        bytes.extend((elem.len() as u32).to_be_bytes());
+        pad_to_u32(&mut elem);
+        elems.extend(elem);
+    }
+
+    bytes.extend(elems);
+    bytes
+}
+
+pub fn write_tag_data(tag_data: &TagData) -> Vec<u8> {
+    match tag_data {
+        TagData::Text(text) => write_text_type(text),
+        TagData::Desc(desc) => write_desc_type(desc),
+        TagData::Mluc(mluc) => write_mluc_type(mluc),
+        TagData::XYZ(xyz) => write_xyz_type(xyz),
+        TagData::Curve(curve) => write_curve_type(curve),
+        TagData::Para(para) => write_para_type(para),
+        TagData::Sf32(values) => write_sf32_type(values),
+        TagData::Lut8(lut8) => write_lut8_type(lut8),
+        TagData::Lut16(lut16) => write_lut16_type(lut16),
+        TagData::LutAB(lut) => write_lutab_type(lut),
+        TagData::Dict(dict) => write_dict_type(dict),
+        TagData::Vcgt(vcgt) => write_vcgt_type(vcgt),
+        TagData::VcgtFormula(formula) => write_vcgt_formula(formula),
+        TagData::Chrm(chrm) => write_chrm_type(chrm),
+        TagData::Mmod(mmod) => write_mmod_type(mmod),
+        TagData::Ncl2(named_colors) => write_ncl2_type(named_colors),
+        TagData::Sig(sig) => write_sig_type(*sig),
+        TagData::Clrt(colorants) => write_clrt_type(colorants),
+        TagData::Clro(order) => write_clro_type(order),
+        TagData::Rcs2(response) => write_rcs2_type(response),
+        TagData::DateTime(date) => write_dtim_type(date),
+        TagData::UInt8(values) => write_array_type(
+            0x75693038,
+            &values
+                .iter()
+                .map(|value| [*value])
+                .collect::<Vec<[u8; 1]>>(),
+        ),
+        TagData::UInt16(values) => write_array_type(
+            0x75693136,
+            &values
+                .iter()
+                .map(|value| value.to_be_bytes())
+                .collect::<Vec<[u8; 2]>>(),
+        ),
+        TagData::UInt32(values) => write_array_type(
+            0x75693332,
+            &values
+                .iter()
+                .map(|value| value.to_be_bytes())
+                .collect::<Vec<[u8; 4]>>(),
+        ),
+        TagData::UInt64(values) => write_array_type(
+            0x75693634,
+            &values
+                .iter()
+                .map(|value| value.to_be_bytes())
+                .collect::<Vec<[u8; 8]>>(),
+        ),
+        TagData::Uf32(values) => write_array_type(
+            0x75663332,
+            &values
+                .iter()
+                .map(|value| value.to_be_bytes())
+                .collect::<Vec<[u8; 4]>>(),
+        ),
+        TagData::Data(data) => write_data_type(data),
+        TagData::Meas(meas) => write_meas_type(meas),
+        TagData::View(view) => write_view_type(view),
+        TagData::Pseq(descs) => write_pseq_type(descs),
+        TagData::Psid(ids) => write_psid_type(ids),
+        TagData::Unknown(raw) => raw.clone(),
+    }
+}
@@ -0,0 +1,124 @@
+use crate::analysis::volume::GamutHull;
+use crate::utils::color::{
+    bradford_adaptation, lab_to_xyz, mat_inv, mat_mul, mat_vec, rgb_to_xyz_matrix, xy_to_xyz,
+    D50_XYZ, D65_XY,
+};
+use crate::utils::icc::ICC_CHRM_TYPE;
+
+// Hull vertices are written as x = a*, y = L*, z = b*, so that L* is up in mesh viewers
+
+// sRGB display color of each vertex, out of gamut colors clipped
+fn vertex_colors(hull: &GamutHull) -> Result<Vec<[f64; 3]>, String> {
+    let bt709 = ICC_CHRM_TYPE[0];
+    let d50_to_d65 = bradford_adaptation(&D50_XYZ, &xy_to_xyz(D65_XY))?;
+    let xyz_to_rgb = mat_inv(&rgb_to_xyz_matrix(bt709.1, bt709.2, bt709.3, D65_XY)?)?;
+    let matrix = mat_mul(&xyz_to_rgb, &d50_to_d65);
+
+    Ok(hull
+        .vertices
+        .iter()
+        .map(|lab| {
+            mat_vec(&matrix, &lab_to_xyz(lab, &D50_XYZ)).map(|linear| {
+                let linear = linear.clamp(0.0, 1.0);
+                match linear <= 0.0031308 {
+                    true => 12.92 * linear,
+                    false => 1.055 * linear.powf(1.0 / 2.4) - 0.055,
+                }
+            })
+        })
+        .collect())
+}
+
+fn points_str(hull: &GamutHull) -> Vec<String> {
+    hull.vertices
+        .iter()
+        .map(|lab| format!("{:.4} {:.4} {:.4}", lab[1], lab[0], lab[2]))
+        .collect()
+}
+
+fn colors_str(hull: &GamutHull) -> Result<Vec<String>, String> {
+    Ok(vertex_colors(hull)?
+        .iter()
+        .map(|rgb| format!("{:.4} {:.4} {:.4}", rgb[0], rgb[1], rgb[2]))
+        .collect())
+}
+
+// Triangles as "i j k -1" face indexes of VRML and X3D
+fn coord_index_str(hull: &GamutHull) -> Vec<String> {
+    hull.faces
+        .iter()
+        .map(|face| format!("{} {} {} -1", face[0], face[1], face[2]))
+        .collect()
+}
+
+// VRML 2.0 indexed face set
+pub fn write_hull_wrl(hull: &GamutHull, name: &str) -> Result<String, String> {
+    Ok(format!(
+        "#VRML V2.0 utf8\n\
+         # Gamut hull of {}, L*a*b* D50 (x = a*, y = L*, z = b*)\n\
+         Shape {{\n\
+         \x20 geometry IndexedFaceSet {{\n\
+         \x20   solid FALSE\n\
+         \x20   colorPerVertex TRUE\n\
+         \x20   coord Coordinate {{\n\
+         \x20     point [\n\
+         \x20       {}\n\
+         \x20     ]\n\
+         \x20   }}\n\
+         \x20   color Color {{\n\
+         \x20     color [\n\
+         \x20       {}\n\
+         \x20     ]\n\
+         \x20   }}\n\
+         \x20   coordIndex [\n\
+         \x20     {}\n\
+         \x20   ]\n\
+         \x20 }}\n\
+         }}\n",
+        name,
+        points_str(hull).join(",\n        "),
+        colors_str(hull)?.join(",\n        "),
+        coord_index_str(hull).join(",\n      ")
+    ))
+}
+
+// X3D (XML encoding) indexed face set
+pub fn write_hull_x3d(hull: &GamutHull, name: &str) -> Result<String, String> {
+    Ok(format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <X3D profile=\"Interchange\" version=\"3.3\">\n\
+         \x20 <head>\n\
+         \x20   <meta name=\"description\" content=\"Gamut hull of {}, L*a*b* D50 (x = a*, y = L*, z = b*)\"/>\n\
+         \x20 </head>\n\
+         \x20 <Scene>\n\
+         \x20   <Shape>\n\
+         \x20     <IndexedFaceSet solid=\"false\" colorPerVertex=\"true\" coordIndex=\"{}\">\n\
+         \x20       <Coordinate point=\"{}\"/>\n\
+         \x20       <Color color=\"{}\"/>\n\
+         \x20     </IndexedFaceSet>\n\
+         \x20   </Shape>\n\
+         \x20 </Scene>\n\
+         </X3D>\n",
+        name.replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;"),
+        coord_index_str(hull).join(" "),
+        points_str(hull).join(", "),
+        colors_str(hull)?.join(", ")
+    ))
+}
+
+// Wavefront OBJ, vertex colors after the coordinates, 1-based face indexes
+pub fn write_hull_obj(hull: &GamutHull, name: &str) -> Result<String, String> {
+    let mut obj = format!(
+        "# Gamut hull of {}, L*a*b* D50 (x = a*, y = L*, z = b*)\no gamut\n",
+        name
+    );
+    for (point, color) in points_str(hull).iter().zip(colors_str(hull)?) {
+        obj += &format!("v {} {}\n", point, color);
+    }
+    for face in hull.faces.iter() {
+        obj += &format!("f {} {} {}\n", face[0] + 1, face[1] + 1, face[2] + 1);
+    }
+    Ok(obj)
+}
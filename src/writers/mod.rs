@@ -0,0 +1,7 @@
+pub mod cgats;
+pub mod dict;
+pub mod icc_types;
+pub mod mesh;
+pub mod named_colors;
+pub mod profile;
+pub mod response;
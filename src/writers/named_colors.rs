@@ -0,0 +1,78 @@
+use json::{object, JsonValue};
+
+use crate::types::icc::{NamedColor, NamedColors};
+
+fn pcs_fields(pcs_lab: bool) -> [&'static str; 3] {
+    if pcs_lab {
+        ["L", "a", "b"]
+    } else {
+        ["X", "Y", "Z"]
+    }
+}
+
+// One line per color : name, PCS values and device values in percent
+pub fn write_named_colors_csv(
+    named_colors: &NamedColors,
+    colors: &[&NamedColor],
+    pcs_lab: bool,
+) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    let mut header: Vec<String> = vec!["name".to_string()];
+    header.extend(pcs_fields(pcs_lab).map(|field| field.to_string()));
+    header.extend((1..=named_colors.device_coords_num).map(|coord| format!("device_{}", coord)));
+    writer
+        .write_record(&header)
+        .map_err(|err| err.to_string())?;
+
+    for color in colors.iter() {
+        let mut record: Vec<String> = vec![named_colors.full_name(color)];
+        record.extend(color.pcs_values(pcs_lab).map(|val| format!("{:.4}", val)));
+        record.extend(
+            color
+                .device_values()
+                .iter()
+                .map(|val| format!("{:.4}", val)),
+        );
+        writer
+            .write_record(&record)
+            .map_err(|err| err.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|err| err.to_string())?;
+    String::from_utf8(bytes).map_err(|err| err.to_string())
+}
+
+pub fn write_named_colors_json(
+    named_colors: &NamedColors,
+    colors: &[&NamedColor],
+    pcs_lab: bool,
+) -> String {
+    let fields = pcs_fields(pcs_lab);
+    let mut colors_json = JsonValue::new_array();
+
+    for color in colors.iter() {
+        let pcs = color.pcs_values(pcs_lab);
+        let mut pcs_json = JsonValue::new_object();
+        for (field, val) in fields.iter().zip(pcs.iter()) {
+            pcs_json[*field] = (*val).into();
+        }
+        // Pushing into an array cannot fail
+        let _ = colors_json.push(object! {
+            name: named_colors.full_name(color),
+            root_name: color.name.clone(),
+            pcs: pcs_json,
+            device: color.device_values(),
+        });
+    }
+
+    let library = object! {
+        prefix: named_colors.prefix.clone(),
+        suffix: named_colors.suffix.clone(),
+        pcs: if pcs_lab { "Lab" } else { "XYZ" },
+        device_coordinates: named_colors.device_coords_num,
+        colors: colors_json,
+    };
+
+    library.pretty(2)
+}
@@ -0,0 +1,80 @@
+use crate::types::profile::{Profile, ProfileHeader};
+use crate::utils::bytes::pad_to_u32;
+use crate::utils::icc::compute_profile_id;
+use crate::writers::icc_types::write_tag_data;
+
+fn write_header(header: &ProfileHeader, size: u32, tags_num: u32) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(132);
+
+    bytes.extend(size.to_be_bytes());
+    bytes.extend(header.cmm.to_be_bytes());
+    bytes.extend(header.version.to_be_bytes());
+    bytes.extend(header.class.to_be_bytes());
+    bytes.extend(header.color_space.to_be_bytes());
+    bytes.extend(header.pcs.to_be_bytes());
+    header
+        .date
+        .iter()
+        .for_each(|date_val| bytes.extend(date_val.to_be_bytes()));
+    bytes.extend(0x61637370u32.to_be_bytes()); // 'acsp'
+    bytes.extend(header.platform.to_be_bytes());
+    bytes.extend(header.flags.to_be_bytes());
+    bytes.extend(header.manufacturer.to_be_bytes());
+    bytes.extend(header.model.to_be_bytes());
+    bytes.extend(header.attributes.to_be_bytes());
+    bytes.extend(header.rendering_intent.to_be_bytes());
+    header
+        .illuminant
+        .iter()
+        .for_each(|coor| bytes.extend(coor.to_be_bytes()));
+    bytes.extend(header.creator.to_be_bytes());
+    bytes.extend(header.id);
+    bytes.extend([0u8; 28]);
+    bytes.extend(tags_num.to_be_bytes());
+
+    bytes
+}
+
+impl Profile {
+    // Tags whose encoded data are identical share the same bytes in the file,
+    // every tag data starts on a 4 bytes boundary and the profile size is padded too.
+    // The profile ID is computed for version 4 profiles (reserved bytes in version 2)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let tags_num = self.tags.len();
+        let data_start = 132 + 12 * tags_num;
+
+        let mut tag_table: Vec<u8> = Vec::with_capacity(12 * tags_num);
+        let mut tags_data: Vec<u8> = vec![];
+        let mut written: Vec<(Vec<u8>, usize)> = vec![];
+
+        for (tag_sig, tag_data) in self.tags.iter() {
+            let encoded = write_tag_data(tag_data);
+            let offset = match written.iter().find(|(bytes, _)| *bytes == encoded) {
+                Some((_, offset)) => *offset,
+                None => {
+                    let offset = data_start + tags_data.len();
+                    tags_data.extend(&encoded);
+                    pad_to_u32(&mut tags_data);
+                    written.push((encoded.clone(), offset));
+                    offset
+                }
+            };
+
+            tag_table.extend(tag_sig.to_be_bytes());
+            tag_table.extend((offset as u32).to_be_bytes());
+            tag_table.extend((encoded.len() as u32).to_be_bytes());
+        }
+
+        let size = (data_start + tags_data.len()) as u32;
+        let mut bytes = write_header(&self.header, size, tags_num as u32);
+        bytes.extend(tag_table);
+        bytes.extend(tags_data);
+
+        if self.header.version >= 0x04000000 {
+            let pf_id = compute_profile_id(&bytes);
+            bytes[84..=99].copy_from_slice(&pf_id);
+        }
+
+        bytes
+    }
+}
@@ -0,0 +1,46 @@
+use crate::types::icc::ResponseCurveSet;
+use crate::utils::bytes::sig_to_str;
+
+// One line per measurement : unit, channel, device value in percent and measurement,
+// the maximum colorant XYZ repeated on each line of its channel
+pub fn write_response_csv(response: &ResponseCurveSet) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer
+        .write_record([
+            "unit",
+            "channel",
+            "device",
+            "measurement",
+            "max_X",
+            "max_Y",
+            "max_Z",
+        ])
+        .map_err(|err| err.to_string())?;
+
+    for curve in response.curves.iter() {
+        let unit = sig_to_str(curve.unit).trim_end().to_string();
+        for (chan, (values, xyz)) in curve
+            .responses
+            .iter()
+            .zip(curve.max_colorant.iter())
+            .enumerate()
+        {
+            for (device, measurement) in values.iter() {
+                writer
+                    .write_record([
+                        unit.clone(),
+                        (chan + 1).to_string(),
+                        format!("{:.4}", *device as f64 * 100.0 / 65535.0),
+                        format!("{:.4}", measurement),
+                        format!("{:.4}", xyz[0]),
+                        format!("{:.4}", xyz[1]),
+                        format!("{:.4}", xyz[2]),
+                    ])
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+    }
+
+    let bytes = writer.into_inner().map_err(|err| err.to_string())?;
+    String::from_utf8(bytes).map_err(|err| err.to_string())
+}
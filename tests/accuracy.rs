@@ -0,0 +1,147 @@
+use icc_reader::analysis::accuracy::verify_profile;
+use icc_reader::standard_profiles::srgb;
+use icc_reader::types::profile::TagData;
+use icc_reader::utils::bytes::{sig_to_str, str_to_sig};
+use icc_reader::utils::color::{delta_e_2000, mat_vec, rgb_to_xyz_matrix, D65_XY};
+
+fn srgb_eotf(val: f64) -> f64 {
+    if val <= 0.04045 {
+        val / 12.92
+    } else {
+        ((val + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+// Display measurements of an ideal sRGB monitor, D65 white at 120 cd/m²
+fn srgb_ti3(shifted_patch: usize) -> String {
+    let matrix = rgb_to_xyz_matrix((0.64, 0.33), (0.30, 0.60), (0.15, 0.06), D65_XY).unwrap();
+    let mut sets: Vec<String> = vec![];
+    let steps = [0.0, 25.0, 50.0, 75.0, 100.0];
+    for r in steps {
+        for g in steps {
+            for b in steps {
+                let linear = [r, g, b].map(|val| srgb_eotf(val / 100.0));
+                let mut xyz = mat_vec(&matrix, &linear).map(|val| val * 120.0);
+                if sets.len() + 1 == shifted_patch {
+                    xyz[0] *= 1.1;
+                }
+                sets.push(format!(
+                    "{} {} {} {} {} {} {}",
+                    sets.len() + 1,
+                    r,
+                    g,
+                    b,
+                    xyz[0],
+                    xyz[1],
+                    xyz[2]
+                ));
+            }
+        }
+    }
+
+    format!(
+        "CTI3\n\nDEVICE_CLASS \"DISPLAY\"\nCOLOR_REP \"RGB_XYZ\"\n\nNUMBER_OF_FIELDS 7\nBEGIN_DATA_FORMAT\nSAMPLE_ID RGB_R RGB_G RGB_B XYZ_X XYZ_Y XYZ_Z\nEND_DATA_FORMAT\n\nNUMBER_OF_SETS {}\nBEGIN_DATA\n{}\nEND_DATA\n",
+        sets.len(),
+        sets.join("\n")
+    )
+}
+
+#[test]
+fn delta_e_2000_matches_reference_data() {
+    // Sharma, Wu and Dalal test data
+    let pairs = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.2644,
+        ),
+        (
+            [22.7233, 20.0904, -46.6940],
+            [23.0331, 14.9730, -42.5619],
+            2.0373,
+        ),
+    ];
+    for (lab1, lab2, delta_e) in pairs {
+        assert!((delta_e_2000(&lab1, &lab2) - delta_e).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn srgb_measurements_match_the_srgb_profile() {
+    let mut profile = srgb().unwrap();
+    profile.set_tag("targ", TagData::Text(srgb_ti3(0)));
+    let report = verify_profile(&profile).unwrap();
+
+    assert_eq!(report.patches.len(), 125);
+    assert!(report.transform.starts_with("Matrix/TRC"));
+    assert!(report.max() < 0.5, "max ΔE2000 {}", report.max());
+
+    profile.set_tag("targ", TagData::Text(srgb_ti3(42)));
+    let report = verify_profile(&profile).unwrap();
+    let worst = report.worst(1);
+    assert_eq!(worst[0].id, "42");
+    assert!(worst[0].delta_e > 2.0);
+    assert!(report.percentile(95.0) < 0.5);
+}
+
+#[test]
+fn lut_tables_are_evaluated() {
+    let profile = icc_reader::standard_profiles::standard_profile("Lab-D50")
+        .unwrap()
+        .unwrap();
+    let transform = profile.device_to_pcs(1).unwrap();
+    assert!(transform.name().starts_with("A2B0"));
+
+    // Legacy 16 bits Lab encoding of L* 50, a* 0, b* 0
+    let device = [32640.0, 32768.0, 32768.0].map(|val| val / 65535.0);
+    let xyz = transform.eval(&device).unwrap();
+    let lab = icc_reader::utils::color::xyz_to_lab(&xyz, &icc_reader::utils::color::D50_XYZ);
+    assert!(
+        (lab[0] - 50.0).abs() < 0.01 && lab[1].abs() < 0.01 && lab[2].abs() < 0.01,
+        "{:?}",
+        lab
+    );
+}
+
+#[test]
+fn tables_are_selected_by_intent() {
+    let load = || {
+        icc_reader::standard_profiles::standard_profile("Lab-D50")
+            .unwrap()
+            .unwrap()
+    };
+    let mut profile = load();
+    let a2b0 = load()
+        .tags
+        .into_iter()
+        .find(|(sig, _)| *sig == str_to_sig("A2B0"));
+    profile.set_tag("A2B2", a2b0.unwrap().1);
+
+    let table = |prefix: &str, intent: u32| {
+        profile
+            .intent_table(prefix, intent)
+            .map(|(tag, _)| sig_to_str(tag))
+    };
+    assert_eq!(table("A2B", 2).as_deref(), Some("A2B2"));
+    assert_eq!(table("A2B", 1).as_deref(), Some("A2B0"));
+    assert_eq!(table("A2B", 3).as_deref(), Some("A2B0"));
+    assert_eq!(table("D2B", 0), None);
+    assert!(profile.device_to_pcs(2).unwrap().name().starts_with("A2B2"));
+    assert!(profile.pcs_to_device(0).unwrap().name().starts_with("B2A0"));
+}
+
+#[test]
+fn matrix_trc_is_inverted() {
+    let profile = srgb().unwrap();
+    let device = [0.2, 0.5, 0.8];
+    let xyz = profile.device_to_pcs(0).unwrap().eval(&device).unwrap();
+    let transform = profile.pcs_to_device(0).unwrap();
+    assert!(transform.name().starts_with("Inverse Matrix/TRC"));
+
+    let back = transform.eval(&xyz).unwrap();
+    for (val, expected) in back.iter().zip(device) {
+        assert!((val - expected).abs() < 1e-3, "{:?}", back);
+    }
+}
@@ -0,0 +1,87 @@
+use icc_reader::analysis::appearance::{AppearanceConditions, Surround};
+use icc_reader::standard_profiles::srgb;
+use icc_reader::types::icc::{
+    Measurement, MeasurementGeometry, StandardIlluminant, StandardObserver, ViewingConditions,
+};
+use icc_reader::types::profile::{Profile, TagData};
+use icc_reader::utils::color::{f64_to_xyz_number, D50_XYZ};
+
+fn assert_close(val: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (val - expected).abs() < tolerance,
+        "{} differs from {}",
+        val,
+        expected
+    );
+}
+
+#[test]
+fn ciecam02_matches_the_cie_159_example() {
+    let conditions = AppearanceConditions {
+        white: [98.88, 90.0, 32.03],
+        adapting_luminance: 200.0,
+        background: 18.0,
+        surround: Surround::Average,
+    };
+    let jch = conditions.jch(&[19.31, 23.93, 10.14]);
+
+    assert_close(jch[0], 48.0314, 0.001);
+    assert_close(jch[1], 38.7789, 0.001);
+    assert_close(jch[2], 191.0452, 0.001);
+}
+
+#[test]
+fn viewing_conditions_drive_the_appearance_model() {
+    let mut profile = srgb().unwrap();
+    let conditions = profile.appearance_conditions();
+    assert_close(conditions.white[0], D50_XYZ[0] * 100.0, 0.05);
+    assert_eq!(conditions.surround, Surround::Average);
+
+    profile.set_tag(
+        "meas",
+        TagData::Meas(Measurement {
+            observer: StandardObserver::Cie1931,
+            backing: [0.0; 3],
+            geometry: MeasurementGeometry::Unknown,
+            flare: 0.01,
+            illuminant: StandardIlluminant::D65,
+        }),
+    );
+    assert_close(profile.appearance_conditions().white[2], 108.88, 0.05);
+
+    profile.set_tag(
+        "view",
+        TagData::View(ViewingConditions {
+            illuminant: [76.036, 80.0, 87.125],
+            surround: [0.0; 3],
+            illuminant_type: StandardIlluminant::D65,
+        }),
+    );
+    let conditions = profile.appearance_conditions();
+    assert_close(conditions.adapting_luminance, 16.0, 1e-9);
+    assert_eq!(conditions.surround, Surround::Dark);
+
+    // The media white is seen as the adopted white
+    let jch = profile.pcs_to_jch(&D50_XYZ).unwrap();
+    assert_close(jch[0], 100.0, 0.01);
+
+    let bytes = profile.to_bytes();
+    let parsed = Profile::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.to_bytes(), bytes);
+    let meas = parsed.measurement().unwrap();
+    assert_eq!(meas.illuminant, StandardIlluminant::D65);
+    assert_close(meas.flare, 0.01, 1e-4);
+    assert_eq!(parsed.viewing_conditions().unwrap().surround, [0.0; 3]);
+}
+
+#[test]
+fn absolute_intent_scales_by_the_media_white() {
+    let mut profile = srgb().unwrap();
+    profile.set_tag("wtpt", TagData::XYZ(f64_to_xyz_number(&[0.9, 0.95, 0.7])));
+
+    let absolute = profile.to_absolute(&D50_XYZ);
+    assert_close(absolute[0], 0.9, 1e-4);
+    assert_close(absolute[1], 0.95, 1e-4);
+    let relative = profile.to_relative(&absolute);
+    assert_close(relative[2], D50_XYZ[2], 1e-4);
+}
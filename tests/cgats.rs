@@ -0,0 +1,81 @@
+use icc_reader::parsers::cgats::parse_cgats;
+use icc_reader::standard_profiles::srgb;
+use icc_reader::types::profile::{Profile, TagData};
+use icc_reader::writers::cgats::write_cgats;
+
+const TI3: &str = r#"CTI3
+
+DESCRIPTOR "Argyll Calibration Target chart information 3"
+ORIGINATOR "Argyll dispread"
+CREATED "Sat Jan  6 12:00:00 2024"
+KEYWORD "DEVICE_CLASS"
+DEVICE_CLASS "DISPLAY"
+KEYWORD "COLOR_REP"
+COLOR_REP "RGB_XYZ"
+
+NUMBER_OF_FIELDS 8
+BEGIN_DATA_FORMAT
+SAMPLE_ID SAMPLE_NAME RGB_R RGB_G RGB_B XYZ_X XYZ_Y XYZ_Z
+END_DATA_FORMAT
+
+NUMBER_OF_SETS 3
+BEGIN_DATA
+1 "White patch" 100.00 100.00 100.00 95.047 100.00 108.88
+2 "Red" 100.00 0.0000 0.0000 41.240 21.260 1.9330
+3 "Black" 0.0000 0.0000 0.0000 0.2000 0.2100 0.2300
+END_DATA
+
+CAL
+
+KEYWORD "DEVICE_CLASS"
+DEVICE_CLASS "DISPLAY"
+NUMBER_OF_FIELDS 4
+BEGIN_DATA_FORMAT
+RGB_I RGB_R RGB_G RGB_B
+END_DATA_FORMAT
+NUMBER_OF_SETS 2
+BEGIN_DATA
+0.0 0.0 0.0 0.0
+1.0 1.0 0.98 0.97
+END_DATA
+"#;
+
+#[test]
+fn ti3_tables_are_parsed() {
+    let cgats = parse_cgats(TI3).unwrap();
+    assert_eq!(cgats.tables.len(), 2);
+
+    let ti3 = &cgats.tables[0];
+    assert_eq!(ti3.file_type, "CTI3");
+    assert_eq!(ti3.keyword("COLOR_REP"), Some("RGB_XYZ"));
+    assert_eq!(ti3.sets.len(), 3);
+    assert_eq!(ti3.sets[0][1], "\"White patch\"");
+    assert_eq!(ti3.device_fields(), vec!["RGB_R", "RGB_G", "RGB_B"]);
+    assert_eq!(ti3.device_values().unwrap()[1], vec![100.0, 0.0, 0.0]);
+    assert_eq!(ti3.xyz().unwrap()[0], [95.047, 100.0, 108.88]);
+    assert!(ti3.lab().is_none());
+
+    let cal = cgats.table_with(&["RGB_I"]).unwrap();
+    assert_eq!(cal.file_type, "CAL");
+    assert_eq!(cal.column("RGB_G").unwrap(), vec![0.0, 0.98]);
+}
+
+#[test]
+fn ti3_survives_write_and_profile_embedding() {
+    let mut profile = srgb().unwrap();
+    profile.set_tag("targ", TagData::Text(TI3.to_string()));
+    let parsed = Profile::from_bytes(&profile.to_bytes()).unwrap();
+    let cgats = parsed.cgats("targ").unwrap();
+
+    let rewritten = parse_cgats(&write_cgats(&cgats)).unwrap();
+    assert_eq!(rewritten.tables.len(), 2);
+    for (table, other) in cgats.tables.iter().zip(rewritten.tables.iter()) {
+        assert_eq!(table.file_type, other.file_type);
+        assert_eq!(table.fields, other.fields);
+        assert_eq!(table.sets, other.sets);
+    }
+    assert!(parse_cgats(
+        "CTI3\nBEGIN_DATA_FORMAT\nA B\nEND_DATA_FORMAT\nBEGIN_DATA\n1 2 3\nEND_DATA"
+    )
+    .is_err());
+}
@@ -0,0 +1,53 @@
+use icc_reader::standard_profiles::{display_p3, srgb};
+use icc_reader::types::profile::TagData;
+use icc_reader::utils::bytes::str_to_sig;
+use icc_reader::utils::color::{f64_to_xyz_number, xy_to_xyz, D65_XY};
+
+fn close(value: f64, expected: f64, tolerance: f64) -> bool {
+    (value - expected).abs() < tolerance
+}
+
+#[test]
+fn coverage_is_computed_in_both_diagrams() {
+    let report = display_p3().unwrap().primaries_report().unwrap();
+    assert!(close(report.primaries[0].0, 0.680, 1e-4));
+    assert!(close(report.white.0, D65_XY.0, 1e-4));
+    assert!(close(report.white.1, D65_XY.1, 1e-4));
+
+    let coverage = |reference: &str| {
+        report
+            .coverages
+            .iter()
+            .find(|coverage| coverage.reference == reference)
+            .unwrap()
+    };
+    // P3 contains sRGB, and covers a part of the Adobe RGB greens only
+    assert!(close(coverage("sRGB").xy.coverage, 1.0, 1e-4));
+    assert!(close(coverage("sRGB").uv.coverage, 1.0, 1e-4));
+    assert!(close(coverage("sRGB").xy.area, 1.357, 1e-3));
+    assert!(close(coverage("DCI-P3").uv.area, 1.0, 1e-3));
+    assert!(close(coverage("Adobe RGB").xy.coverage, 0.883, 1e-3));
+    assert!(close(coverage("Rec.2020").xy.coverage, 0.717, 1e-3));
+    assert!(coverage("Rec.2020").uv.coverage > coverage("Rec.2020").xy.coverage);
+}
+
+#[test]
+fn v2_profiles_use_the_measured_white_point() {
+    // No chad tag, media white point of the display in wtpt
+    let mut profile = srgb().unwrap();
+    profile.tags.retain(|(sig, _)| *sig != str_to_sig("chad"));
+    let d65 = xy_to_xyz(D65_XY);
+    profile.set_tag("wtpt", TagData::XYZ(f64_to_xyz_number(&d65)));
+
+    let report = profile.primaries_report().unwrap();
+    assert!(close(report.white.0, D65_XY.0, 1e-4));
+    for (primary, expected) in report
+        .primaries
+        .iter()
+        .zip([(0.64, 0.33), (0.3, 0.6), (0.15, 0.06)])
+    {
+        assert!(close(primary.0, expected.0, 1e-3));
+        assert!(close(primary.1, expected.1, 1e-3));
+    }
+    assert!(close(report.coverages[0].xy.coverage, 1.0, 1e-3));
+}
@@ -0,0 +1,189 @@
+use icc_reader::parsers::edid::{find_edid, parse_edid, EDID_HEADER};
+use icc_reader::parsers::profile_info::{edid_summary, parse_profile_info};
+use icc_reader::standard_profiles::srgb;
+use icc_reader::types::icc::{DataType, DictEntry, DictType};
+use icc_reader::types::profile::{Profile, TagData};
+use icc_reader::utils::color::f64_to_xyz_number;
+
+const SRGB_PRIMARIES: [(f64, f64); 3] = [(0.64, 0.33), (0.30, 0.60), (0.15, 0.06)];
+const P3_PRIMARIES: [(f64, f64); 3] = [(0.68, 0.32), (0.265, 0.69), (0.15, 0.06)];
+
+// EDID 1.4 base block of a "DEL" display, 2019 week 40, gamma 2.2, D65 white
+fn edid_block(primaries: [(f64, f64); 3]) -> Vec<u8> {
+    let mut edid = EDID_HEADER.to_vec();
+    let mnft = (4u16 << 10) | (5 << 5) | 12; // D E L
+    edid.extend(mnft.to_be_bytes());
+    edid.extend(0xA0F1u16.to_le_bytes());
+    edid.extend(12345u32.to_le_bytes());
+    edid.extend([40, 29, 1, 4]);
+    edid.extend([0xB5, 0x3C, 0x22, 120, 0x3A]);
+
+    // 10 bits chromaticities, 2 low bits of each packed in bytes 25 and 26
+    let xy: Vec<u16> = primaries
+        .iter()
+        .chain([(0.3127, 0.3290)].iter())
+        .flat_map(|(x, y)| [(x * 1024.0).round() as u16, (y * 1024.0).round() as u16])
+        .collect();
+    let low = |values: &[u16]| {
+        values
+            .iter()
+            .fold(0u8, |byte, value| (byte << 2) | (value & 0x03) as u8)
+    };
+    edid.push(low(&xy[0..4]));
+    edid.push(low(&xy[4..8]));
+    edid.extend(xy.iter().map(|value| (value >> 2) as u8));
+    edid.resize(54, 0);
+
+    for (tag, text) in [(0xFC, "DELL U2720Q"), (0xFF, "7RJ1XK2")] {
+        edid.extend([0, 0, 0, tag, 0]);
+        let mut text = text.as_bytes().to_vec();
+        text.push(0x0A);
+        text.resize(13, b' ');
+        edid.extend(text);
+    }
+    edid.resize(127, 0);
+    let sum = edid.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    edid.push(0u8.wrapping_sub(sum));
+    edid
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+fn with_meta(profile: &mut Profile, entries: &[(&str, &str)]) {
+    profile.set_tag(
+        "meta",
+        TagData::Dict(DictType {
+            element_size: 16,
+            entries: entries
+                .iter()
+                .map(|(name, value)| DictEntry::new(name, value))
+                .collect(),
+        }),
+    );
+}
+
+#[test]
+fn edid_block_is_decoded() {
+    let edid = parse_edid(&edid_block(SRGB_PRIMARIES)).unwrap();
+    assert_eq!(edid.manufacturer_id, "DEL");
+    assert_eq!(edid.product_code, Some(0xA0F1));
+    assert_eq!(edid.serial_number, Some(12345));
+    assert_eq!(edid.serial.as_deref(), Some("7RJ1XK2"));
+    assert_eq!(edid.model.as_deref(), Some("DELL U2720Q"));
+    assert_eq!((edid.year, edid.week), (Some(2019), Some(40)));
+    assert!((edid.gamma.unwrap() - 2.2).abs() < 1e-9);
+    assert!(edid.checksum_ok);
+
+    let primaries = edid.primaries.unwrap();
+    for (decoded, expected) in primaries.iter().zip(SRGB_PRIMARIES) {
+        assert!((decoded.0 - expected.0).abs() < 1.0 / 1024.0);
+        assert!((decoded.1 - expected.1).abs() < 1.0 / 1024.0);
+    }
+    let white = edid.white.unwrap();
+    assert!((white.0 - 0.3127).abs() < 1.0 / 1024.0);
+
+    let mut corrupted = edid_block(SRGB_PRIMARIES);
+    corrupted[20] ^= 0xFF;
+    assert!(!parse_edid(&corrupted).unwrap().checksum_ok);
+    assert!(parse_edid(&corrupted[1..]).is_err());
+}
+
+#[test]
+fn edid_blobs_are_found_as_hex_or_base64() {
+    let block = edid_block(SRGB_PRIMARIES);
+    assert!(find_edid(hex(&block).as_bytes()).is_some());
+    assert!(find_edid(b"not an EDID").is_none());
+
+    // Base64 of the block, as some tools store it in the meta dict
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let base64: String = block
+        .chunks(3)
+        .flat_map(|chunk| {
+            let bits = chunk
+                .iter()
+                .chain([0, 0].iter())
+                .take(3)
+                .fold(0u32, |acc, byte| (acc << 8) | *byte as u32);
+            (0..=chunk.len())
+                .map(move |idx| ALPHABET[(bits >> (18 - 6 * idx)) as usize & 63] as char)
+        })
+        .collect();
+    let edid = find_edid(base64.as_bytes()).unwrap();
+    assert_eq!(edid.model.as_deref(), Some("DELL U2720Q"));
+}
+
+#[test]
+fn edid_is_checked_against_the_profile_primaries() {
+    // Block in a private data tag of an sRGB profile : same display
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "Edid",
+        TagData::Data(DataType {
+            flags: 1,
+            data: edid_block(SRGB_PRIMARIES),
+        }),
+    );
+    let profile = Profile::from_bytes(&profile.to_bytes()).unwrap();
+    let edid = profile.edid().unwrap();
+    let comparisons = profile.edid_comparisons(&edid);
+    assert_eq!(comparisons.len(), 1);
+    assert!(comparisons[0].max().1 < 0.002);
+    assert!(comparisons[0].warning().is_none());
+
+    // OpenICC keys of a wide gamut display in the meta dict
+    let mut profile = srgb().unwrap();
+    let keys: Vec<(String, String)> = ["red", "green", "blue"]
+        .iter()
+        .zip(P3_PRIMARIES)
+        .flat_map(|(color, (x, y))| {
+            [
+                (format!("EDID_{}_x", color), x.to_string()),
+                (format!("EDID_{}_y", color), y.to_string()),
+            ]
+        })
+        .collect();
+    let mut entries: Vec<(&str, &str)> = keys
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    entries.extend([("EDID_mnft", "APP"), ("EDID_date", "2020-T12")]);
+    with_meta(&mut profile, &entries);
+    let profile = Profile::from_bytes(&profile.to_bytes()).unwrap();
+
+    let edid = profile.edid().unwrap();
+    assert_eq!(edid.manufacturer_id, "APP");
+    assert_eq!((edid.year, edid.week), (Some(2020), Some(12)));
+    let comparison = &profile.edid_comparisons(&edid)[0];
+    assert_eq!(comparison.max().0, 0);
+    assert!(comparison.mismatch());
+    assert!(comparison.warning().unwrap().contains("different display"));
+}
+
+#[test]
+fn info_reports_the_edid_of_any_profile() {
+    // Luminance above 327.68 cd/m², beyond the old XYZ scaling
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "lumi",
+        TagData::XYZ(f64_to_xyz_number(&[380.0, 400.0, 435.0])),
+    );
+    profile.set_tag(
+        "Edid",
+        TagData::Data(DataType {
+            flags: 1,
+            data: edid_block(SRGB_PRIMARIES),
+        }),
+    );
+    let bytes = profile.to_bytes();
+    parse_profile_info(&bytes);
+    let summary = edid_summary(&bytes);
+    assert_eq!(summary.len(), 1);
+    assert!(summary[0].starts_with("EDID : DEL"));
+
+    // Tag table beyond the end of file : no EDID line, the info is still printed
+    let truncated = &bytes[..bytes.len() - 64];
+    assert!(edid_summary(truncated).is_empty());
+    parse_profile_info(truncated);
+}
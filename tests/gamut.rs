@@ -0,0 +1,63 @@
+use icc_reader::types::icc::{Lut16, S15Fixed16Number};
+use icc_reader::types::profile::{Profile, ProfileHeader, TagData};
+use icc_reader::utils::color::{f64_to_xyz_number, lab_to_xyz, D50_XYZ};
+
+// Printer gamut of neutral colors only : out of gamut away from the a* = b* = 0 grid nodes
+fn neutral_gamut_profile() -> Profile {
+    let identity = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0].map(S15Fixed16Number::from_num);
+    let clut_table: Vec<u16> = (0..27)
+        .map(|node| {
+            let (a_idx, b_idx) = (node / 3 % 3, node % 3);
+            if a_idx == 1 && b_idx == 1 {
+                0
+            } else {
+                65535
+            }
+        })
+        .collect();
+
+    let mut profile = Profile::new(ProfileHeader::new(
+        "prtr",
+        "CMYK",
+        "Lab ",
+        f64_to_xyz_number(&D50_XYZ),
+    ));
+    profile.set_tag(
+        "gamt",
+        TagData::Lut16(Lut16 {
+            lut_sig: 0x6D667432,
+            in_chans_num: 3,
+            out_chans_num: 1,
+            grid_pts_num: 3,
+            e_params: identity.to_vec(),
+            in_table_entries_num: 2,
+            out_table_entries_num: 2,
+            in_table: vec![0, 65535, 0, 65535, 0, 65535],
+            clut_table,
+            out_table: vec![0, 65535],
+        }),
+    );
+    profile
+}
+
+#[test]
+fn gamut_tag_tells_printable_colors() {
+    let profile = Profile::from_bytes(&neutral_gamut_profile().to_bytes()).unwrap();
+
+    assert!(profile.in_gamut(&[50.0, 0.0, 0.0]).unwrap());
+    assert!(profile.in_gamut(&[80.0, 20.0, -10.0]).unwrap());
+    assert!(!profile.in_gamut(&[50.0, 100.0, 0.0]).unwrap());
+    assert!(!profile.in_gamut(&[50.0, 0.0, -110.0]).unwrap());
+
+    let value = profile
+        .gamut_value(&lab_to_xyz(&[50.0, 0.0, 0.0], &D50_XYZ))
+        .unwrap();
+    assert!(value < 0.01, "neutral gray value {}", value);
+}
+
+#[test]
+fn gamut_check_needs_a_gamut_tag() {
+    let mut profile = neutral_gamut_profile();
+    profile.tags.clear();
+    assert!(profile.in_gamut(&[50.0, 0.0, 0.0]).is_err());
+}
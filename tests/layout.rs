@@ -0,0 +1,94 @@
+use icc_reader::analysis::layout::analyze_layout;
+use icc_reader::analysis::layout::GapKind;
+use icc_reader::analysis::validate::validate_profile;
+use icc_reader::parsers::tags::read_tag_table;
+use icc_reader::standard_profiles::srgb;
+use icc_reader::utils::bytes::str_to_sig;
+
+// Points a tag table entry to other data
+fn move_tag(icc_raw_data: &mut [u8], tag: &str, offset: u32) {
+    let tag_num = read_tag_table(icc_raw_data)
+        .unwrap()
+        .iter()
+        .position(|(sig, _, _)| *sig == str_to_sig(tag))
+        .unwrap();
+    let entry = 132 + 12 * tag_num;
+    icc_raw_data[entry + 4..entry + 8].copy_from_slice(&offset.to_be_bytes());
+}
+
+#[test]
+fn shared_tags_and_padding_are_found() {
+    let layout = analyze_layout(&srgb().unwrap().to_bytes()).unwrap();
+
+    let shared: Vec<&Vec<u32>> = layout.shared_blocks().map(|block| &block.tags).collect();
+    assert_eq!(
+        shared,
+        vec![&vec![
+            str_to_sig("rTRC"),
+            str_to_sig("gTRC"),
+            str_to_sig("bTRC")
+        ]]
+    );
+    assert!(layout.overlaps.is_empty());
+    assert!(layout.warnings().is_empty());
+    // 3 zero bytes aligning the tag after the 33 Bytes copyright text
+    assert_eq!(layout.gaps.len(), 1);
+    assert!(matches!(layout.gaps[0].kind, GapKind::Padding));
+    assert_eq!(layout.gaps[0].size, 3);
+    assert_eq!(layout.unused_bytes(), 0);
+}
+
+#[test]
+fn overlapping_tags_are_reported() {
+    let mut icc_raw_data = srgb().unwrap().to_bytes();
+    let (_, rxyz_offset, _) = read_tag_table(&icc_raw_data)
+        .unwrap()
+        .into_iter()
+        .find(|(sig, _, _)| *sig == str_to_sig("rXYZ"))
+        .unwrap();
+    move_tag(&mut icc_raw_data, "gXYZ", rxyz_offset as u32 + 12);
+
+    let layout = analyze_layout(&icc_raw_data).unwrap();
+    assert_eq!(
+        layout.overlaps,
+        vec![(str_to_sig("rXYZ"), str_to_sig("gXYZ"), 8)]
+    );
+    assert!(layout.warnings()[0].contains("Tag rXYZ overlaps tag gXYZ on 8 Bytes"));
+    assert!(layout.byte_map().contains('X'));
+    // The bytes gXYZ no longer uses are left unused
+    assert_eq!(layout.unused_bytes(), 8);
+}
+
+#[test]
+fn unused_bytes_are_counted() {
+    let mut icc_raw_data = srgb().unwrap().to_bytes();
+    let padding = analyze_layout(&icc_raw_data).unwrap().gaps[0].offset;
+    icc_raw_data[padding + 1] = 0xAA;
+    // 16 Bytes of trailing data, counted in the header profile size
+    icc_raw_data.extend([0xAA; 16]);
+    let pf_size = icc_raw_data.len() as u32;
+    icc_raw_data[0..4].copy_from_slice(&pf_size.to_be_bytes());
+
+    let layout = analyze_layout(&icc_raw_data).unwrap();
+    assert_eq!(layout.gaps.len(), 2);
+    assert!(layout
+        .gaps
+        .iter()
+        .all(|gap| matches!(gap.kind, GapKind::Unused)));
+    assert_eq!(layout.gaps[1].size, 16);
+    assert_eq!(layout.unused_bytes(), 19);
+    assert!(layout.byte_map().contains('.'));
+}
+
+#[test]
+fn truncated_profiles_are_analyzed() {
+    // Tag table complete, most tag data missing
+    let icc_raw_data = srgb().unwrap().to_bytes()[..400].to_vec();
+
+    let layout = analyze_layout(&icc_raw_data).unwrap();
+    let tags_num = read_tag_table(&icc_raw_data).unwrap().len();
+    assert!(!layout.beyond_file.is_empty());
+    assert!(layout.beyond_file.len() < tags_num);
+    assert!(layout.byte_map().contains('!'));
+    assert!(!validate_profile(&icc_raw_data).is_empty());
+}
@@ -0,0 +1,78 @@
+use icc_reader::analysis::metadata::Metadata;
+use icc_reader::standard_profiles::srgb;
+use icc_reader::types::icc::{DictEntry, DictType};
+use icc_reader::types::profile::{Profile, TagData};
+
+fn displaycal_profile(entries: &[(&str, &str)]) -> Profile {
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "meta",
+        TagData::Dict(DictType {
+            element_size: 16,
+            entries: entries
+                .iter()
+                .map(|(name, value)| DictEntry::new(name, value))
+                .collect(),
+        }),
+    );
+    // Decoded from the written profile, as read from a file
+    Profile::from_bytes(&profile.to_bytes()).unwrap()
+}
+
+fn displaycal_metadata(entries: &[(&str, &str)]) -> Metadata {
+    displaycal_profile(entries).metadata().unwrap()
+}
+
+#[test]
+fn displaycal_keys_are_typed() {
+    let metadata = displaycal_metadata(&[
+        ("prefix", "CMF_,EDID_,MEASUREMENT_,ACCURACY_"),
+        ("CMF_product", "DisplayCAL"),
+        ("CMF_version", "3.8.9.3"),
+        ("MEASUREMENT_device", "i1 DisplayPro, ColorMunki Display"),
+        ("OPENICC_automatic_generated", "0"),
+        ("EDID_mnft", "DEL"),
+        ("EDID_model", "DELL U2720Q"),
+        ("EDID_serial", "7RJ1XK2"),
+        ("ACCURACY_dE76_avg", "0.312"),
+        ("ACCURACY_dE76_max", "1.5"),
+    ]);
+
+    assert_eq!(metadata.product(), Some("DisplayCAL"));
+    assert_eq!(metadata.automatic_generated(), Some(false));
+    assert_eq!(metadata.edid_manufacturer(), Some("DEL"));
+    assert_eq!(metadata.accuracy_de76_max(), Some(1.5));
+    assert_eq!(metadata.accuracy_de76_rms(), None);
+    assert_eq!(
+        metadata.namespace("EDID_"),
+        [
+            ("mnft", "DEL"),
+            ("model", "DELL U2720Q"),
+            ("serial", "7RJ1XK2")
+        ]
+    );
+    assert_eq!(
+        metadata.prefixes(),
+        ["CMF_", "EDID_", "MEASUREMENT_", "ACCURACY_"]
+    );
+    assert_eq!(
+        metadata.summary().unwrap(),
+        "Made with DisplayCAL 3.8.9.3, measured with i1 DisplayPro, ColorMunki Display, \
+         EDID serial 7RJ1XK2, avg ΔE 0.31"
+    );
+}
+
+#[test]
+fn partial_metadata_is_summarized() {
+    let metadata = displaycal_metadata(&[
+        ("MEASUREMENT_device", "ColorMunki"),
+        ("GAMUT_volume", "1.02"),
+        ("CMF_version", ""),
+    ]);
+    assert_eq!(metadata.product_version(), None);
+    assert_eq!(metadata.prefixes(), ["CMF_", "MEASUREMENT_", "GAMUT_"]);
+    assert_eq!(metadata.summary().unwrap(), "Measured with ColorMunki");
+
+    assert!(displaycal_metadata(&[("custom", "1")]).summary().is_none());
+    assert!(srgb().unwrap().metadata().is_none());
+}
@@ -0,0 +1,174 @@
+use icc_reader::parsers::registry::{TagContext, TypeRegistry};
+use icc_reader::parsers::tags::read_tag_table;
+use icc_reader::standard_profiles::srgb;
+use icc_reader::types::icc::{Chrm, ParametricCurve, S15Fixed16Number, U16Fixed16Number};
+use icc_reader::types::profile::TagData;
+use icc_reader::utils::bytes::{bytes_to_u32, str_to_sig};
+
+fn context(tag: &str) -> TagContext<'_> {
+    TagContext {
+        tag,
+        lang: None,
+        pt_num: 10,
+        corr_scale: 1,
+        data_space: str_to_sig("RGB "),
+        pcs: str_to_sig("XYZ "),
+        device_channels: None,
+        output_channels: None,
+    }
+}
+
+// Decoded display of a tag, with the decoder of its type signature
+fn decode(registry: &TypeRegistry, icc_raw_data: &[u8], tag: &str) -> Result<String, String> {
+    let (_, offset, size) = read_tag_table(icc_raw_data)?
+        .into_iter()
+        .find(|(sig, _, _)| *sig == str_to_sig(tag))
+        .ok_or(format!("No {} tag", tag))?;
+    let type_sig = bytes_to_u32(&icc_raw_data[offset..offset + 4])?;
+    match registry.decoder(type_sig) {
+        Some(decoder) => decoder(icc_raw_data, offset, size, &context(tag)),
+        None => Err(format!("No decoder for {:X}", type_sig)),
+    }
+}
+
+fn profile_bytes() -> Vec<u8> {
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "kTRC",
+        TagData::Para(ParametricCurve {
+            function_type: 0,
+            params: vec![S15Fixed16Number::from_num(1.8)],
+        }),
+    );
+    profile.set_tag("Priv", TagData::UInt16(vec![3, 5]));
+    profile.to_bytes()
+}
+
+#[test]
+fn tags_are_decoded_by_type_signature() {
+    let registry = TypeRegistry::default();
+    let icc_raw_data = profile_bytes();
+
+    let ktrc = decode(&registry, &icc_raw_data, "kTRC").unwrap();
+    assert!(ktrc.contains("parametric curve (function type 0) : [1.8]"));
+    let private = decode(&registry, &icc_raw_data, "Priv").unwrap();
+    assert_eq!(private, "      2 values : 3 5");
+    assert!(decode(&registry, &icc_raw_data, "rXYZ")
+        .unwrap()
+        .contains("43.60 22.25 1.39"));
+}
+
+#[test]
+fn custom_types_are_registered() {
+    fn decode_sum(data: &[u8], idx: usize, size: usize, _: &TagContext) -> Result<String, String> {
+        let sum: u32 = data[idx + 8..idx + size]
+            .iter()
+            .map(|byte| *byte as u32)
+            .sum();
+        Ok(format!("      Sum : {}", sum))
+    }
+
+    let icc_raw_data = profile_bytes();
+    let mut registry = TypeRegistry::empty();
+    assert!(decode(&registry, &icc_raw_data, "Priv").is_err());
+
+    registry.register("ui16", decode_sum);
+    assert_eq!(
+        decode(&registry, &icc_raw_data, "Priv").unwrap(),
+        "      Sum : 8"
+    );
+}
+
+#[test]
+fn floating_point_tables_are_listed() {
+    // mpet : 3 → 3 channels with a matf then a 2x2x2 clut element, positions from the tag start
+    let mut mpet = b"mpet\0\0\0\0".to_vec();
+    mpet.extend([0, 3, 0, 3, 0, 0, 0, 2]);
+    mpet.extend([0, 0, 0, 32, 0, 0, 0, 12, 0, 0, 0, 44, 0, 0, 0, 28]);
+    mpet.extend(b"matf\0\0\0\0");
+    mpet.extend([0, 3, 0, 3]);
+    mpet.extend(b"clut\0\0\0\0");
+    mpet.extend([0, 3, 0, 3, 2, 2, 2]);
+    mpet.resize(72, 0);
+
+    let mut profile = srgb().unwrap();
+    profile.set_tag("D2B0", TagData::Unknown(mpet));
+    let decoded = decode(&TypeRegistry::default(), &profile.to_bytes(), "D2B0").unwrap();
+    assert!(decoded.contains("# Processing elements : 2"));
+    assert!(decoded.contains("1. Matrix : 3 → 3"));
+    assert!(decoded.contains("2. CLUT : 3 → 3, [2, 2, 2] grid points"));
+}
+
+#[test]
+fn chromaticities_are_matched_to_standards() {
+    let chrm = |code: u16, xy: &[(f64, f64)]| {
+        TagData::Chrm(Chrm {
+            phs_col_type: String::new(),
+            phs_col_code: code,
+            channels: xy
+                .iter()
+                .map(|(x, y)| {
+                    (
+                        U16Fixed16Number::from_num(*x),
+                        U16Fixed16Number::from_num(*y),
+                    )
+                })
+                .collect(),
+        })
+    };
+
+    // Measured primaries of a wide gamut display
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "chrm",
+        chrm(0, &[(0.679, 0.319), (0.268, 0.685), (0.151, 0.058)]),
+    );
+    let decoded = decode(&TypeRegistry::default(), &profile.to_bytes(), "chrm").unwrap();
+    assert!(decoded.contains("CIE xy channel 3 : 0.1510 0.0580"));
+    assert!(decoded.contains("Nearest standard : P3 (Δu′v′ R 0.0"));
+
+    // Undefined colorant type and 4 channels
+    let xy = [(0.64, 0.33), (0.3, 0.6), (0.15, 0.06), (0.2, 0.2)];
+    profile.set_tag("chrm", chrm(9, &xy));
+    let decoded = decode(&TypeRegistry::default(), &profile.to_bytes(), "chrm").unwrap();
+    assert!(decoded.contains("colorant type : Unknown (0x0009)"));
+    assert!(decoded.contains("! 4 channels, the colorant type has 3 primaries"));
+    assert!(decoded.contains("CIE xy channel 4 : 0.2000 0.2000"));
+    assert!(!decoded.contains("Nearest standard"));
+}
+
+#[test]
+fn calibration_ramps_are_listed_per_channel() {
+    // Identity red, half green, full blue
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "vcgt",
+        TagData::Vcgt((
+            0x76636774,
+            (0..256).map(|val| val * 257).collect(),
+            (0..256).map(|val| val * 128).collect(),
+            vec![u16::MAX; 256],
+            vec![],
+            vec![],
+            3,
+            256,
+            16,
+        )),
+    );
+    let icc_raw_data = profile.to_bytes();
+    let (_, offset, size) = read_tag_table(&icc_raw_data)
+        .unwrap()
+        .into_iter()
+        .find(|(sig, _, _)| *sig == str_to_sig("vcgt"))
+        .unwrap();
+
+    let ctx = TagContext {
+        pt_num: 4,
+        corr_scale: 100,
+        ..context("vcgt")
+    };
+    let decoder = TypeRegistry::default().decoder(str_to_sig("vcgt")).unwrap();
+    let vcgt = decoder(&icc_raw_data, offset, size, &ctx).unwrap();
+    assert!(vcgt.contains("      0.00  0, 0, 100"), "{}", vcgt);
+    assert!(vcgt.contains("      0.50  0, -25, 50"), "{}", vcgt);
+}
@@ -0,0 +1,1048 @@
+use icc_reader::analysis::validate::{validate_profile, Severity};
+use icc_reader::parsers::icc_types::{
+    read_curve_type, read_desc_type, read_dict_type, read_lutab_type, read_mluc_type,
+};
+use icc_reader::parsers::profile::read_tag_data;
+use icc_reader::parsers::tags::read_tag_table;
+use icc_reader::standard_profiles::{srgb, STANDARD_PROFILES};
+use icc_reader::types::icc::*;
+use icc_reader::types::profile::{Profile, ProfileHeader, TagData};
+use icc_reader::utils::bytes::{bytes_u32_usize, str_to_sig};
+use icc_reader::utils::color::{f64_to_xyz_number, xyz_number_to_f64};
+use icc_reader::utils::icc::{check_profile_id, signature_name, ICC_TECHNOLOGY};
+use icc_reader::writers::dict::write_dict_json;
+use icc_reader::writers::icc_types::{
+    write_curve_type, write_lutab_type, write_mluc_type, write_tag_data,
+};
+use icc_reader::writers::named_colors::{write_named_colors_csv, write_named_colors_json};
+use icc_reader::writers::response::write_response_csv;
+
+fn sf32(val: f64) -> S15Fixed16Number {
+    S15Fixed16Number::from_num(val)
+}
+
+fn uf16(val: f64) -> U16Fixed16Number {
+    U16Fixed16Number::from_num(val)
+}
+
+fn table_curve(len: usize) -> Curve {
+    Curve {
+        identity: false,
+        gamma: None,
+        curve: Some(
+            (0..len)
+                .map(|idx| (idx * 65535 / (len - 1)) as u16)
+                .collect(),
+        ),
+    }
+}
+
+fn para_curve() -> ParametricCurve {
+    ParametricCurve {
+        function_type: 3,
+        params: vec![sf32(2.4), sf32(0.947), sf32(0.052), sf32(0.077), sf32(0.04)],
+    }
+}
+
+fn lut_ab(lut_sig: u32, in_chans: u8, out_chans: u8) -> LutAB {
+    let (a_chans, b_chans) = if lut_sig == 0x6D414220 {
+        (in_chans, out_chans)
+    } else {
+        (out_chans, in_chans)
+    };
+    let curves = |chans: u8| -> Vec<LutCurve> {
+        (0..chans)
+            .map(|chan| match chan % 3 {
+                0 => LutCurve::Para(para_curve()),
+                1 => LutCurve::Curv(table_curve(5)),
+                _ => LutCurve::Curv(Curve {
+                    identity: false,
+                    gamma: Some(U8Fixed8Number::from_num(1.8)),
+                    curve: None,
+                }),
+            })
+            .collect()
+    };
+    let grid_pts: Vec<u8> = (0..in_chans).map(|chan| 2 + chan).collect();
+    let nodes: usize = grid_pts.iter().map(|pts| usize::from(*pts)).product();
+
+    LutAB {
+        lut_sig,
+        in_chans_num: in_chans,
+        out_chans_num: out_chans,
+        b_curves: curves(b_chans),
+        matrix: Some((0..12).map(|val| sf32(val as f64 / 7.0)).collect()),
+        m_curves: Some(curves(b_chans)),
+        clut: Some(Clut {
+            grid_pts,
+            precision: 1,
+            table: (0..nodes * usize::from(out_chans))
+                .map(|val| (val % 256) as u16)
+                .collect(),
+        }),
+        a_curves: Some(curves(a_chans)),
+    }
+}
+
+fn mluc_bytes(records: &[MlucRecord]) -> Vec<u8> {
+    let mut profile = Profile::new(ProfileHeader::new("mntr", "RGB ", "XYZ ", [sf32(0.0); 3]));
+    profile.set_tag(
+        "desc",
+        TagData::Mluc(MultiLocalizedText {
+            records: records.to_vec(),
+        }),
+    );
+    let bytes = profile.to_bytes();
+    let offset = bytes_u32_usize(&bytes[136..140]).unwrap();
+    let size = bytes_u32_usize(&bytes[140..144]).unwrap();
+    bytes[offset..offset + size].to_vec()
+}
+
+fn named_colors() -> NamedColors {
+    NamedColors {
+        flags: 0,
+        prefix: "ACME ".to_string(),
+        suffix: " C".to_string(),
+        device_coords_num: 4,
+        colors: vec![
+            NamedColor {
+                name: "Warm Red".to_string(),
+                pcs: [0x8000, 0xC000, 0xA000],
+                device: vec![0, 0xD000, 0xC000, 0],
+            },
+            NamedColor {
+                name: "Process Blue".to_string(),
+                pcs: [0x6000, 0x7000, 0x3000],
+                device: vec![0xFFFF, 0x4000, 0, 0x1000],
+            },
+        ],
+    }
+}
+
+fn profile_sequence() -> Vec<ProfileDescription> {
+    vec![
+        ProfileDescription {
+            manufacturer: str_to_sig("APPL"),
+            model: str_to_sig("disp"),
+            attributes: 0,
+            technology: str_to_sig("CRT "),
+            manufacturer_desc: TextTag::Desc(TextDescription::new("Apple")),
+            model_desc: TextTag::Desc(TextDescription::new("Studio Display")),
+        },
+        ProfileDescription {
+            manufacturer: 0,
+            model: 0,
+            attributes: 0b1010,
+            technology: 0,
+            manufacturer_desc: TextTag::Mluc(MultiLocalizedText::new("en-US", "Epson")),
+            model_desc: TextTag::Mluc(MultiLocalizedText::new("en-US", "Stylus Pro")),
+        },
+    ]
+}
+
+fn colorants(names: &[&str]) -> Vec<Colorant> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| Colorant {
+            name: name.to_string(),
+            pcs: [0x1000 * idx as u16, 0x8000, 0x4000],
+        })
+        .collect()
+}
+
+fn response_curves() -> ResponseCurveSet {
+    ResponseCurveSet {
+        channels_num: 2,
+        curves: [("StaT", 3), ("DN P", 2)]
+            .iter()
+            .map(|(unit, count)| ResponseCurve {
+                unit: str_to_sig(unit),
+                max_colorant: vec![[0.2, 0.3, 0.4], [0.5, 0.25, 0.125]],
+                responses: (0..2)
+                    .map(|chan| {
+                        (0..*count)
+                            .map(|step| {
+                                (
+                                    (step * 65535 / (count - 1)) as u16,
+                                    step as f64 * 0.5 + chan as f64,
+                                )
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn all_types_profile() -> Profile {
+    let mut header = ProfileHeader::new(
+        "mntr",
+        "RGB ",
+        "XYZ ",
+        f64_to_xyz_number(&[0.9642, 1.0, 0.8249]),
+    );
+    header.version = 0x04300000;
+    header.platform = str_to_sig("APPL");
+    header.flags = 1;
+    header.manufacturer = 0x1234;
+    header.model = 0x5678;
+    header.attributes = 0x0000000100000003;
+    header.rendering_intent = 1;
+    header.creator = str_to_sig("test");
+    let mut profile = Profile::new(header);
+
+    let records: Vec<MlucRecord> = vec![
+        (
+            "en".to_string(),
+            "US".to_string(),
+            "Round trip profile".to_string(),
+        ),
+        (
+            "fr".to_string(),
+            "FR".to_string(),
+            "Profil aller-retour é".to_string(),
+        ),
+        (
+            "de".to_string(),
+            "DE".to_string(),
+            "Hin und zurück".to_string(),
+        ),
+    ];
+
+    profile.set_tag(
+        "desc",
+        TagData::Mluc(MultiLocalizedText {
+            records: records.clone(),
+        }),
+    );
+    profile.set_tag(
+        "dmnd",
+        TagData::Desc(TextDescription {
+            unicode: Some("Manufacturer ü".to_string()),
+            unicode_lang: u32::from_be_bytes(*b"en\0\0"),
+            ..TextDescription::new("Manufacturer")
+        }),
+    );
+    profile.set_tag(
+        "dmdd",
+        TagData::Desc(TextDescription {
+            unicode: None,
+            ..TextDescription::new("ASCII only")
+        }),
+    );
+    profile.set_tag("cprt", TagData::Text("Public domain".to_string()));
+    profile.set_tag(
+        "wtpt",
+        TagData::XYZ(f64_to_xyz_number(&[0.9642, 1.0, 0.8249])),
+    );
+    profile.set_tag(
+        "rXYZ",
+        TagData::XYZ(f64_to_xyz_number(&[0.4361, 0.2225, 0.0139])),
+    );
+    profile.set_tag("rTRC", TagData::Curve(table_curve(256)));
+    profile.set_tag("gTRC", TagData::Curve(table_curve(256)));
+    profile.set_tag(
+        "bTRC",
+        TagData::Curve(Curve {
+            identity: true,
+            gamma: None,
+            curve: None,
+        }),
+    );
+    profile.set_tag(
+        "kTRC",
+        TagData::Curve(Curve {
+            identity: false,
+            gamma: Some(U8Fixed8Number::from_num(2.2)),
+            curve: None,
+        }),
+    );
+    profile.set_tag("pre0", TagData::Para(para_curve()));
+    profile.set_tag(
+        "chad",
+        TagData::Sf32(vec![
+            sf32(1.0479),
+            sf32(0.0229),
+            sf32(-0.0502),
+            sf32(0.0296),
+            sf32(0.9904),
+            sf32(-0.0171),
+            sf32(-0.0092),
+            sf32(0.0151),
+            sf32(0.7519),
+        ]),
+    );
+    profile.set_tag(
+        "A2B0",
+        TagData::Lut8(Lut8 {
+            lut_sig: 0x6D667431,
+            in_chans_num: 3,
+            out_chans_num: 3,
+            grid_pts_num: 3,
+            e_params: vec![
+                sf32(1.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(1.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(1.0),
+            ],
+            in_table: (0..768).map(|val| (val % 256) as u8).collect(),
+            clut_table: (0..81).map(|val| (val * 3) as u8).collect(),
+            out_table: (0..768).map(|val| (255 - val % 256) as u8).collect(),
+        }),
+    );
+    profile.set_tag(
+        "B2A0",
+        TagData::Lut16(Lut16 {
+            lut_sig: 0x6D667432,
+            in_chans_num: 3,
+            out_chans_num: 4,
+            grid_pts_num: 5,
+            e_params: vec![
+                sf32(1.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(1.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(0.0),
+                sf32(1.0),
+            ],
+            in_table_entries_num: 4,
+            out_table_entries_num: 6,
+            in_table: (0..12).map(|val| val * 1000).collect(),
+            clut_table: (0..500).map(|val| val * 100).collect(),
+            out_table: (0..24).map(|val| val * 2000).collect(),
+        }),
+    );
+    profile.set_tag("A2B1", TagData::LutAB(lut_ab(0x6D414220, 3, 3)));
+    profile.set_tag("B2A1", TagData::LutAB(lut_ab(0x6D424120, 3, 4)));
+    profile.set_tag(
+        "meta",
+        TagData::Dict(DictType {
+            element_size: 32,
+            entries: vec![
+                DictEntry {
+                    name: vec![0, b'k', 0, b'e', 0, b'y'],
+                    value: Some(vec![0, b'v', 0, b'a', 0, b'l']),
+                    display_name: Some(mluc_bytes(&records[0..1])),
+                    display_value: None,
+                },
+                DictEntry {
+                    name: vec![0, b'e', 0, b'm', 0, b'p', 0, b't', 0, b'y'],
+                    value: Some(vec![]),
+                    display_name: None,
+                    display_value: Some(mluc_bytes(&records[1..])),
+                },
+                DictEntry {
+                    name: vec![0, b'n'],
+                    value: None,
+                    display_name: None,
+                    display_value: None,
+                },
+            ],
+        }),
+    );
+    profile.set_tag(
+        "vcgt",
+        TagData::Vcgt((
+            0x76636774,
+            (0..256).map(|val| val * 256).collect(),
+            (0..256).map(|val| val * 250).collect(),
+            (0..256).map(|val| val * 200 + 100).collect(),
+            vec![],
+            vec![],
+            3,
+            256,
+            16,
+        )),
+    );
+    profile.set_tag(
+        "chrm",
+        TagData::Chrm(Chrm {
+            phs_col_type: "ITU-R BT.709-2".to_string(),
+            phs_col_code: 1,
+            channels: vec![
+                (uf16(0.64), uf16(0.33)),
+                (uf16(0.3), uf16(0.6)),
+                (uf16(0.15), uf16(0.06)),
+            ],
+        }),
+    );
+    profile.set_tag(
+        "mmod",
+        TagData::Mmod(MmodType {
+            manufacturer: 0x0100_0610,
+            model: 0x0002_A00D,
+            serial_number: 12345,
+            date: 0x07E4_0A01,
+            reserved: vec![0; 16],
+        }),
+    );
+    profile.set_tag("ncl2", TagData::Ncl2(named_colors()));
+    profile.set_tag(
+        "meas",
+        TagData::Meas(Measurement {
+            observer: StandardObserver::Cie1964,
+            backing: [0.01, 0.02, 0.03],
+            geometry: MeasurementGeometry::Geometry45,
+            flare: 0.0,
+            illuminant: StandardIlluminant::Other(42),
+        }),
+    );
+    profile.set_tag(
+        "view",
+        TagData::View(ViewingConditions {
+            illuminant: [19.6445, 20.3718, 16.8089],
+            surround: [3.92889, 4.07439, 3.36179],
+            illuminant_type: StandardIlluminant::D50,
+        }),
+    );
+    profile.set_tag("tech", TagData::Sig(str_to_sig("AMD ")));
+    profile.set_tag("clrt", TagData::Clrt(colorants(&["Red", "Green", "Blue"])));
+    profile.set_tag("clro", TagData::Clro(vec![2, 1, 0]));
+    profile.set_tag("resp", TagData::Rcs2(response_curves()));
+    profile.set_tag("calt", TagData::DateTime([2026, 10, 19, 8, 5, 3]));
+    profile.set_tag("Pui8", TagData::UInt8(vec![0, 128, 255]));
+    profile.set_tag("Pu16", TagData::UInt16(vec![1, 65535]));
+    profile.set_tag("Pu32", TagData::UInt32(vec![7]));
+    profile.set_tag("Pu64", TagData::UInt64(vec![1 << 40, 3]));
+    profile.set_tag("Puf3", TagData::Uf32(vec![uf16(1.5), uf16(0.25)]));
+    profile.set_tag(
+        "Pdat",
+        TagData::Data(DataType {
+            flags: 1,
+            data: vec![0xDE, 0xAD, 0xBE],
+        }),
+    );
+    profile.set_tag("pseq", TagData::Pseq(profile_sequence()));
+    profile.set_tag(
+        "psid",
+        TagData::Psid(vec![
+            ProfileIdentifier {
+                id: [7; 16],
+                desc: MultiLocalizedText::new("en-US", "Display"),
+            },
+            ProfileIdentifier {
+                id: [0; 16],
+                desc: MultiLocalizedText::new("en-US", "Paper"),
+            },
+        ]),
+    );
+    profile.set_tag(
+        "priv",
+        TagData::Unknown(b"zzzz\0\0\0\0private data".to_vec()),
+    );
+
+    profile
+}
+
+fn assert_round_trip(profile: &Profile) -> (Vec<u8>, Profile) {
+    let bytes = profile.to_bytes();
+    let parsed = Profile::from_bytes(&bytes).expect("written profile must parse");
+    let rewritten = parsed.to_bytes();
+
+    assert_eq!(bytes, rewritten, "parse -> write must be lossless");
+    assert_eq!(parsed.tags.len(), profile.tags.len());
+    (bytes, parsed)
+}
+
+fn tag_entries(bytes: &[u8]) -> Vec<(u32, usize, usize)> {
+    let tags_num = bytes_u32_usize(&bytes[128..132]).unwrap();
+    (0..tags_num)
+        .map(|tag_num| {
+            let idx = 132 + 12 * tag_num;
+            (
+                u32::from_be_bytes(bytes[idx..idx + 4].try_into().unwrap()),
+                bytes_u32_usize(&bytes[idx + 4..idx + 8]).unwrap(),
+                bytes_u32_usize(&bytes[idx + 8..idx + 12]).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn every_tag_type_round_trips() {
+    let profile = all_types_profile();
+    let (_, parsed) = assert_round_trip(&profile);
+
+    for ((sig, original), (parsed_sig, parsed)) in profile.tags.iter().zip(parsed.tags.iter()) {
+        assert_eq!(sig, parsed_sig);
+        assert_eq!(
+            std::mem::discriminant(original),
+            std::mem::discriminant(parsed),
+            "tag {:X} decoded as another type",
+            sig
+        );
+    }
+}
+
+#[test]
+fn decoded_values_survive() {
+    let (_, parsed) = assert_round_trip(&all_types_profile());
+
+    match parsed.tag("desc") {
+        Some(TagData::Mluc(mluc)) => {
+            assert_eq!(mluc.records.len(), 3);
+            assert_eq!(mluc.records[1].0, "fr");
+            assert_eq!(mluc.records[1].2, "Profil aller-retour é");
+        }
+        _ => panic!("desc must be a mluc"),
+    }
+    match parsed.tag("dmnd") {
+        Some(TagData::Desc(desc)) => {
+            assert_eq!(desc.ascii, "Manufacturer");
+            assert_eq!(desc.unicode.as_deref(), Some("Manufacturer ü"));
+            assert_eq!(&desc.unicode_lang.to_be_bytes()[..2], b"en");
+            assert!(desc.issues.is_empty());
+        }
+        _ => panic!("dmnd must be a desc"),
+    }
+    match parsed.tag("rTRC") {
+        Some(TagData::Curve(curve)) => assert_eq!(curve.curve.as_ref().unwrap().len(), 256),
+        _ => panic!("rTRC must be a curv"),
+    }
+    match parsed.tag("B2A0") {
+        Some(TagData::Lut16(lut16)) => {
+            assert_eq!(lut16.clut_table.len(), 500);
+            assert_eq!(lut16.out_table.len(), 24);
+        }
+        _ => panic!("B2A0 must be a mft2"),
+    }
+    match parsed.tag("B2A1") {
+        Some(TagData::LutAB(lut)) => {
+            assert_eq!(lut.b_curves.len(), 3);
+            assert_eq!(lut.a_curves.as_ref().unwrap().len(), 4);
+            assert_eq!(lut.clut.as_ref().unwrap().table.len(), 2 * 3 * 4 * 4);
+        }
+        _ => panic!("B2A1 must be a mBA"),
+    }
+    match parsed.tag("meta") {
+        Some(TagData::Dict(dict)) => {
+            assert_eq!(dict.entries.len(), 3);
+            assert!(dict.entries[2].value.is_none());
+            assert_eq!(dict.entries[1].value.as_ref().unwrap().len(), 0);
+        }
+        _ => panic!("meta must be a dict"),
+    }
+    match parsed.tag("tech") {
+        Some(TagData::Sig(sig)) => {
+            assert_eq!(
+                signature_name(&ICC_TECHNOLOGY, *sig),
+                "Active Matrix Display"
+            )
+        }
+        _ => panic!("tech must be a sig"),
+    }
+    assert_eq!(parsed.header.creator, str_to_sig("test"));
+    assert_eq!(parsed.header.attributes, 0x0000000100000003);
+}
+
+#[test]
+fn layout_is_aligned_shared_and_sized() {
+    let (bytes, _) = assert_round_trip(&all_types_profile());
+    let entries = tag_entries(&bytes);
+
+    assert_eq!(bytes_u32_usize(&bytes[0..4]).unwrap(), bytes.len());
+    assert_eq!(bytes.len() % 4, 0);
+
+    for (sig, offset, size) in entries.iter() {
+        assert_eq!(offset % 4, 0, "tag {:X} is not aligned", sig);
+        assert!(offset + size <= bytes.len());
+    }
+
+    let offset_of = |sig: &str| {
+        entries
+            .iter()
+            .find(|(tag_sig, _, _)| *tag_sig == str_to_sig(sig))
+            .unwrap()
+            .1
+    };
+    assert_eq!(offset_of("rTRC"), offset_of("gTRC"));
+    assert_ne!(offset_of("rTRC"), offset_of("bTRC"));
+}
+
+#[test]
+fn standard_profiles_round_trip() {
+    for (name, build) in STANDARD_PROFILES.iter() {
+        let profile = build().unwrap_or_else(|err| panic!("{} : {}", name, err));
+        let (_, parsed) = assert_round_trip(&profile);
+        assert!(
+            parsed
+                .tags
+                .iter()
+                .all(|(_, data)| !matches!(data, TagData::Unknown(_))),
+            "{} has undecoded tags",
+            name
+        );
+    }
+}
+
+#[test]
+fn profile_id_is_filled_for_v4() {
+    let (bytes, _) = assert_round_trip(&all_types_profile());
+    assert!(matches!(check_profile_id(&bytes), ProfileIdStatus::Valid));
+
+    let mut tampered = bytes.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 0xFF;
+    assert!(matches!(
+        check_profile_id(&tampered),
+        ProfileIdStatus::Mismatched(_)
+    ));
+
+    // Flags and rendering intent are excluded from the ID
+    let mut intent = bytes.clone();
+    intent[67] = 3;
+    assert!(matches!(check_profile_id(&intent), ProfileIdStatus::Valid));
+
+    // Reserved bytes in version 2 : left zero, and reported when they are not
+    let mut v2_bytes = srgb().unwrap().to_bytes();
+    assert!(v2_bytes[84..=127].iter().all(|byte| *byte == 0));
+    assert!(matches!(
+        check_profile_id(&v2_bytes),
+        ProfileIdStatus::Absent
+    ));
+    let reserved = |bytes: &[u8]| {
+        validate_profile(bytes)
+            .into_iter()
+            .filter(|finding| finding.message.starts_with("Reserved header bytes"))
+            .map(|finding| finding.severity)
+            .collect::<Vec<Severity>>()
+    };
+    assert!(reserved(&v2_bytes).is_empty());
+    v2_bytes[84] = 1;
+    assert!(matches!(reserved(&v2_bytes)[..], [Severity::Warning]));
+    v2_bytes[110] = 1;
+    assert!(matches!(reserved(&v2_bytes)[..], [Severity::Error]));
+}
+
+#[test]
+fn mmod_payload_round_trips() {
+    let mut tag = b"mmod\0\0\0\0".to_vec();
+    for field in [0x0100_0610u32, 0x0002_A00D, 0x00C0_FFEE, 0x07E4_0A01] {
+        tag.extend(field.to_be_bytes());
+    }
+    tag.extend(1u8..=16);
+
+    let data = read_tag_data(&tag, 0, tag.len()).unwrap();
+    match &data {
+        TagData::Mmod(mmod) => {
+            assert_eq!(mmod.manufacturer, 0x0100_0610);
+            assert_eq!(mmod.model, 0x0002_A00D);
+            assert_eq!(mmod.serial_number, 0x00C0_FFEE);
+            assert_eq!(mmod.date, 0x07E4_0A01);
+        }
+        _ => panic!("mmod must be decoded as Mmod"),
+    }
+    assert_eq!(write_tag_data(&data), tag);
+
+    let mut profile = all_types_profile();
+    profile.set_tag("mmod", data);
+    let (bytes, _) = assert_round_trip(&profile);
+    let (_, offset, size) = tag_entries(&bytes)
+        .into_iter()
+        .find(|(sig, _, _)| *sig == str_to_sig("mmod"))
+        .unwrap();
+    assert_eq!(&bytes[offset..offset + size], tag.as_slice());
+}
+
+#[test]
+fn large_xyz_values_round_trip() {
+    // Luminance in cd/m², and the largest s15Fixed16Number
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "lumi",
+        TagData::XYZ(f64_to_xyz_number(&[380.5, 400.0, 435.25])),
+    );
+    profile.set_tag("bkpt", TagData::XYZ([S15Fixed16Number::MAX; 3]));
+
+    let (bytes, parsed) = assert_round_trip(&profile);
+    let Some(TagData::XYZ(lumi)) = parsed.tag("lumi") else {
+        panic!("lumi must be a XYZ");
+    };
+    assert_eq!(xyz_number_to_f64(lumi), [380.5, 400.0, 435.25]);
+    assert!(
+        matches!(parsed.tag("bkpt"), Some(TagData::XYZ(xyz)) if xyz[1] == S15Fixed16Number::MAX)
+    );
+
+    // Stored as is : 400.0 in s15Fixed16
+    let encoded = (400u32 << 16).to_be_bytes();
+    assert!(bytes.windows(4).any(|window| window == encoded));
+}
+
+#[test]
+fn translations_are_selected_and_replaced() {
+    let mut profile = all_types_profile();
+    profile
+        .set_localized_text("desc", "fr-FR", "Profil remplacé")
+        .unwrap();
+    profile
+        .set_localized_text("desc", "es-ES", "Perfil")
+        .unwrap();
+    profile
+        .set_localized_text("cprt", "fr-FR", "Domaine public")
+        .unwrap();
+
+    let (_, parsed) = assert_round_trip(&profile);
+    let Some(TagData::Mluc(desc)) = parsed.tag("desc") else {
+        panic!("desc must be a mluc");
+    };
+    assert_eq!(desc.records.len(), 4);
+    assert_eq!(desc.text(Some("fr-FR")), Some("Profil remplacé"));
+    assert_eq!(desc.text(Some("fr-CA")), Some("Profil remplacé"));
+    assert_eq!(desc.text(Some("es")), Some("Perfil"));
+    assert_eq!(desc.text(Some("ja-JP")), Some("Round trip profile"));
+    assert_eq!(desc.text(None), Some("Round trip profile"));
+
+    let Some(TagData::Mluc(cprt)) = parsed.tag("cprt") else {
+        panic!("cprt must become a mluc");
+    };
+    assert_eq!(cprt.text(Some("en-US")), Some("Public domain"));
+    assert_eq!(cprt.text(Some("fr")), Some("Domaine public"));
+}
+
+#[test]
+fn translations_require_version_4() {
+    // Refused for version 2 profiles, which are left untouched
+    let mut profile = srgb().unwrap();
+    let before = profile.to_bytes();
+    let err = profile
+        .set_localized_text("desc", "fr-FR", "Profil sRGB")
+        .unwrap_err();
+    assert!(err.contains("require a version 4 profile"), "{}", err);
+    assert_eq!(profile.to_bytes(), before);
+
+    // A version 2 desc of a version 4 profile keeps its Unicode text as en-US
+    let mut profile = all_types_profile();
+    profile
+        .set_localized_text("dmnd", "fr-FR", "Fabricant")
+        .unwrap();
+    let (bytes, parsed) = assert_round_trip(&profile);
+    let Some(TagData::Mluc(dmnd)) = parsed.tag("dmnd") else {
+        panic!("dmnd must become a mluc");
+    };
+    assert_eq!(dmnd.text(None), Some("Manufacturer ü"));
+    assert_eq!(dmnd.text(Some("fr")), Some("Fabricant"));
+    assert!(!validate_profile(&bytes)
+        .iter()
+        .any(|finding| finding.message.starts_with("Tag 'dmnd'")));
+}
+
+#[test]
+fn broken_descriptions_are_tolerated() {
+    // ASCII without NUL, Unicode count in bytes and no ScriptCode part
+    let mut tag: Vec<u8> = b"desc\0\0\0\0".to_vec();
+    tag.extend(4u32.to_be_bytes());
+    tag.extend(b"Test");
+    tag.extend(b"en\0\0");
+    tag.extend(10u32.to_be_bytes());
+    tag.extend([0, b'T', 0, b'e', 0, b's', 0, b'T', 0, 0]);
+
+    let desc = read_desc_type(&tag, 0, tag.len()).unwrap();
+    assert_eq!(desc.ascii, "Test");
+    assert_eq!(desc.unicode.as_deref(), Some("TesT"));
+    assert_eq!(desc.scriptcode(), None);
+    assert_eq!(desc.issues.len(), 3);
+    // The 3 issues and the ASCII/Unicode disagreement
+    assert_eq!(desc.warnings().len(), 4);
+
+    let mut profile = Profile::new(ProfileHeader::new("mntr", "RGB ", "XYZ ", [sf32(0.0); 3]));
+    profile.set_tag("desc", TagData::Desc(desc));
+    let (_, parsed) = assert_round_trip(&profile);
+    let Some(TagData::Desc(desc)) = parsed.tag("desc") else {
+        panic!("desc must be a desc");
+    };
+    assert!(desc.issues.is_empty());
+}
+
+#[test]
+fn named_colors_are_searched_and_exported() {
+    let (_, parsed) = assert_round_trip(&all_types_profile());
+    let Some(TagData::Ncl2(named_colors)) = parsed.tag("ncl2") else {
+        panic!("ncl2 must be decoded");
+    };
+    assert_eq!(named_colors.colors.len(), 2);
+
+    let found = named_colors.find("process");
+    assert_eq!(found.len(), 1);
+    assert_eq!(named_colors.full_name(found[0]), "ACME Process Blue C");
+    assert!((found[0].pcs_values(true)[0] - 37.647).abs() < 0.001);
+    assert_eq!(found[0].device_values()[0], 100.0);
+
+    let csv = write_named_colors_csv(named_colors, &found, true).unwrap();
+    assert!(csv.starts_with("name,L,a,b,device_1,device_2,device_3,device_4\n"));
+    assert!(csv.contains("ACME Process Blue C,37.6471,"));
+
+    let all: Vec<&NamedColor> = named_colors.colors.iter().collect();
+    let exported = json::parse(&write_named_colors_json(named_colors, &all, true)).unwrap();
+    assert_eq!(exported["colors"].len(), 2);
+    assert_eq!(exported["colors"][0]["name"], "ACME Warm Red C");
+    assert_eq!(exported["device_coordinates"], 4);
+}
+
+#[test]
+fn profile_sequence_keeps_its_order() {
+    let (_, parsed) = assert_round_trip(&all_types_profile());
+    let Some(TagData::Pseq(descs)) = parsed.tag("pseq") else {
+        panic!("pseq must be decoded");
+    };
+    assert_eq!(descs.len(), 2);
+    assert_eq!(descs[0].manufacturer, str_to_sig("APPL"));
+    assert_eq!(descs[0].technology, str_to_sig("CRT "));
+    assert_eq!(descs[0].model_desc.text(None), "Studio Display");
+    assert_eq!(descs[1].attributes, 0b1010);
+    assert_eq!(descs[1].manufacturer_desc.text(None), "Epson");
+
+    let Some(TagData::Psid(ids)) = parsed.tag("psid") else {
+        panic!("psid must be decoded");
+    };
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids[0].id, [7; 16]);
+    assert_eq!(ids[1].desc.text(None), Some("Paper"));
+}
+
+#[test]
+fn colorants_are_checked_against_the_color_space() {
+    let (bytes, parsed) = assert_round_trip(&all_types_profile());
+    let Some(TagData::Clrt(clrt)) = parsed.tag("clrt") else {
+        panic!("clrt must be decoded");
+    };
+    assert_eq!(clrt[1].name, "Green");
+    assert_eq!(clrt[1].pcs_values(false)[1], 1.0);
+    let colorant_errors = |bytes: &[u8]| {
+        validate_profile(bytes)
+            .into_iter()
+            .filter(|finding| finding.message.contains("colorant"))
+            .count()
+    };
+    assert_eq!(colorant_errors(&bytes), 0);
+
+    let mut profile = all_types_profile();
+    profile.set_tag("clrt", TagData::Clrt(colorants(&["Cyan", "Magenta"])));
+    profile.set_tag("clro", TagData::Clro(vec![0, 0, 1]));
+    assert_eq!(colorant_errors(&profile.to_bytes()), 2);
+}
+
+#[test]
+fn response_curves_are_exported() {
+    let (_, parsed) = assert_round_trip(&all_types_profile());
+    let Some(TagData::Rcs2(response)) = parsed.tag("resp") else {
+        panic!("resp must be decoded");
+    };
+    assert_eq!(response.curves.len(), 2);
+    assert_eq!(response.curves[1].unit, str_to_sig("DN P"));
+    assert_eq!(response.curves[0].responses[1][2], (65535, 2.0));
+    assert_eq!(response.curves[0].max_colorant[1], [0.5, 0.25, 0.125]);
+
+    let csv = write_response_csv(response).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 1 + 2 * 3 + 2 * 2);
+    assert_eq!(
+        lines[0],
+        "unit,channel,device,measurement,max_X,max_Y,max_Z"
+    );
+    assert_eq!(lines[6], "StaT,2,100.0000,2.0000,0.5000,0.2500,0.1250");
+    assert!(lines[7].starts_with("DN P,1,0.0000,"));
+}
+
+#[test]
+fn generic_types_are_decoded_by_signature() {
+    let (_, parsed) = assert_round_trip(&all_types_profile());
+    assert!(matches!(
+        parsed.tag("calt"),
+        Some(TagData::DateTime([2026, 10, 19, 8, 5, 3]))
+    ));
+    assert!(matches!(parsed.tag("Pu16"), Some(TagData::UInt16(values)) if values == &[1, 65535]));
+    assert!(matches!(parsed.tag("Pu64"), Some(TagData::UInt64(values)) if values[0] == 1 << 40));
+    assert!(matches!(parsed.tag("Puf3"), Some(TagData::Uf32(values)) if values[0] == uf16(1.5)));
+    let Some(TagData::Data(data)) = parsed.tag("Pdat") else {
+        panic!("data must be decoded");
+    };
+    assert!(!data.is_ascii());
+    assert_eq!(data.data, [0xDE, 0xAD, 0xBE]);
+}
+
+#[test]
+fn dict_entries_are_decoded() {
+    let (bytes, _) = assert_round_trip(&all_types_profile());
+    let (_, offset, size) = tag_entries(&bytes)
+        .into_iter()
+        .find(|(sig, _, _)| *sig == str_to_sig("meta"))
+        .unwrap();
+    let records = read_dict_type(&bytes, offset, size).unwrap();
+
+    let names: Vec<&str> = records.iter().map(|record| record.name.as_str()).collect();
+    assert_eq!(names, ["key", "empty", "n"]);
+    assert_eq!(records[0].value.as_deref(), Some("val"));
+    assert_eq!(records[1].value.as_deref(), Some(""));
+    assert!(records[2].value.is_none());
+    assert_eq!(
+        records[0].display_name.as_ref().unwrap().text(None),
+        Some("Round trip profile")
+    );
+    assert_eq!(records[1].display_value.as_ref().unwrap().records.len(), 2);
+
+    let table = DictRecordsPrettyPrint(&records, None).to_string();
+    assert!(table
+        .lines()
+        .nth(1)
+        .unwrap()
+        .contains("key   | val    | Round trip profile"));
+
+    let json = json::parse(&write_dict_json(&records)).unwrap();
+    assert_eq!(json["key"]["value"], "val");
+    assert_eq!(json["key"]["display_name"]["en-US"], "Round trip profile");
+    assert!(json["n"]["value"].is_null());
+}
+
+#[test]
+fn huge_tag_counts_are_rejected() {
+    let mut icc_raw_data = srgb().unwrap().to_bytes();
+    icc_raw_data[128..132].copy_from_slice(&0x0FFFFFFFu32.to_be_bytes());
+    assert!(Profile::from_bytes(&icc_raw_data)
+        .err()
+        .unwrap()
+        .contains("out of file"));
+    assert!(Profile::from_bytes(&icc_raw_data[..130]).is_err());
+}
+
+#[test]
+fn malformed_lut_tags_are_rejected() {
+    let bytes = write_lutab_type(&lut_ab(0x6D414220, 3, 3));
+    assert!(read_lutab_type(&bytes, 0).is_ok());
+
+    // Truncated anywhere : an error, never a panic
+    for len in 0..bytes.len() {
+        let _ = read_lutab_type(&bytes[..len], 0);
+    }
+    assert!(read_lutab_type(&bytes[..20], 0).is_err());
+
+    // B curves offset beyond the data
+    let mut bad_offset = bytes.clone();
+    bad_offset[12..16].copy_from_slice(&0xFFFFFF00u32.to_be_bytes());
+    assert!(read_lutab_type(&bad_offset, 0).is_err());
+
+    // 255 grid points per channel announced, far more values than the tag holds
+    let mut bad_grid = bytes.clone();
+    let clut_offset = u32::from_be_bytes(bytes[24..28].try_into().unwrap()) as usize;
+    bad_grid[clut_offset..clut_offset + 3].fill(255);
+    assert!(read_lutab_type(&bad_grid, 0)
+        .err()
+        .unwrap()
+        .contains("out of data range"));
+
+    // Curve announcing 4G entries
+    let mut curve = write_curve_type(&table_curve(5));
+    curve[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert!(read_curve_type(&curve, 0).is_err());
+
+    // Undecodable tags are kept as raw bytes
+    let mut profile = srgb().unwrap();
+    profile.set_tag("A2B0", TagData::LutAB(lut_ab(0x6D414220, 3, 3)));
+    let mut icc_raw_data = profile.to_bytes();
+    let start = icc_raw_data
+        .windows(bad_grid.len())
+        .position(|window| window == bytes)
+        .unwrap();
+    icc_raw_data[start..start + bad_grid.len()].copy_from_slice(&bad_grid);
+    assert!(matches!(
+        Profile::from_bytes(&icc_raw_data).unwrap().tag("A2B0"),
+        Some(TagData::Unknown(..))
+    ));
+}
+
+#[test]
+fn malformed_mluc_tags_are_rejected() {
+    let bytes = write_mluc_type(&MultiLocalizedText::new("en-US", "Description"));
+    assert!(read_mluc_type(&bytes, 0, bytes.len()).is_ok());
+    for len in 0..bytes.len() {
+        let _ = read_mluc_type(&bytes[..len], 0, len);
+    }
+
+    // 4G records announced, for a single one stored
+    let mut huge = bytes.clone();
+    huge[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert!(read_mluc_type(&huge, 0, huge.len())
+        .err()
+        .unwrap()
+        .contains("overflow the tag size"));
+
+    let mut profile = srgb().unwrap();
+    profile.header.version = 0x04400000;
+    profile.set_tag(
+        "desc",
+        TagData::Mluc(MultiLocalizedText::new("en-US", "Description")),
+    );
+    let mut icc_raw_data = profile.to_bytes();
+    let start = icc_raw_data
+        .windows(bytes.len())
+        .position(|window| window == bytes)
+        .unwrap();
+    icc_raw_data[start..start + huge.len()].copy_from_slice(&huge);
+    assert!(matches!(
+        Profile::from_bytes(&icc_raw_data).unwrap().tag("desc"),
+        Some(TagData::Unknown(..))
+    ));
+}
+
+#[test]
+fn truncated_tags_are_rejected() {
+    let mut tags: Vec<TagData> = all_types_profile()
+        .tags
+        .into_iter()
+        .map(|(_, tag_data)| tag_data)
+        .collect();
+    tags.push(TagData::VcgtFormula([S15Fixed16Number::from_num(1); 9]));
+
+    // Cut anywhere, in the data or only in the declared size : an error, never a panic
+    for tag_data in tags.iter() {
+        let bytes = write_tag_data(tag_data);
+        for len in 8..bytes.len() {
+            let _ = read_tag_data(&bytes[..len], 0, len);
+            let _ = read_tag_data(&bytes, 0, len);
+        }
+    }
+
+    // 255 input channels : the CLUT size overflows
+    let all_types = all_types_profile();
+    for sig in ["A2B0", "B2A0"] {
+        let mut lut = write_tag_data(all_types.tag(sig).unwrap());
+        lut[8] = 255;
+        assert!(read_tag_data(&lut, 0, lut.len())
+            .err()
+            .unwrap()
+            .contains("overflows the tag size"));
+    }
+
+    let mut sf32 = write_tag_data(all_types.tag("chad").unwrap());
+    sf32.pop();
+    assert!(read_tag_data(&sf32, 0, sf32.len()).is_err());
+}
+
+#[test]
+fn colorant_tags_beyond_the_file_are_reported() {
+    let mut bytes = all_types_profile().to_bytes();
+    let tag_table = read_tag_table(&bytes).unwrap();
+    for sig in ["clrt", "clro"] {
+        let tag_num = tag_table
+            .iter()
+            .position(|(tag_sig, _, _)| *tag_sig == str_to_sig(sig))
+            .unwrap();
+        let entry = 132 + 12 * tag_num;
+        bytes[entry + 4..entry + 8].copy_from_slice(&0xFFFF0000u32.to_be_bytes());
+    }
+
+    let findings = validate_profile(&bytes);
+    assert!(findings
+        .iter()
+        .any(|finding| finding.message.contains("Tag 'clro'")
+            && finding.message.contains("outside the file")));
+}
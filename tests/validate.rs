@@ -0,0 +1,103 @@
+use icc_reader::analysis::validate::{validate_profile, Severity};
+use icc_reader::parsers::tags::read_tag_table;
+use icc_reader::standard_profiles::{srgb, STANDARD_PROFILES};
+use icc_reader::types::icc::MultiLocalizedText;
+use icc_reader::types::profile::TagData;
+use icc_reader::utils::bytes::str_to_sig;
+
+fn errors(icc_raw_data: &[u8]) -> Vec<String> {
+    validate_profile(icc_raw_data)
+        .into_iter()
+        .filter(|finding| matches!(finding.severity, Severity::Error))
+        .map(|finding| finding.message)
+        .collect()
+}
+
+#[test]
+fn standard_profiles_are_valid() {
+    for (name, build) in STANDARD_PROFILES.iter() {
+        let bytes = build().unwrap().to_bytes();
+        assert!(errors(&bytes).is_empty(), "{} : {:?}", name, errors(&bytes));
+    }
+}
+
+#[test]
+fn header_size_is_checked() {
+    let mut bytes = srgb().unwrap().to_bytes();
+    let size = bytes.len() as u32;
+    bytes[0..4].copy_from_slice(&(size + 100).to_be_bytes());
+    assert!(errors(&bytes)
+        .iter()
+        .any(|error| error.starts_with("Header size") && error.contains("larger than the file")));
+
+    // Trailing data is only a warning
+    bytes[0..4].copy_from_slice(&(size - 4).to_be_bytes());
+    assert!(errors(&bytes).is_empty());
+    assert!(validate_profile(&bytes)
+        .iter()
+        .any(|finding| finding.message.contains("larger than the header size")));
+
+    assert_eq!(errors(&bytes[..100]).len(), 1);
+}
+
+#[test]
+fn required_tags_are_checked() {
+    let without = |tag: &str| {
+        let mut profile = srgb().unwrap();
+        profile.tags.retain(|(sig, _)| *sig != str_to_sig(tag));
+        errors(&profile.to_bytes())
+    };
+
+    assert_eq!(without("wtpt"), vec!["Required tag 'wtpt' is missing"]);
+    let errors = without("gTRC");
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].starts_with("'mntr' profile is missing required tags : "));
+    assert!(errors[0].contains(" or gTRC or "));
+}
+
+#[test]
+fn tag_types_follow_the_version() {
+    // mluc is a version 4 type
+    let mut profile = srgb().unwrap();
+    profile.set_tag(
+        "desc",
+        TagData::Mluc(MultiLocalizedText::new("en-US", "sRGB")),
+    );
+    let v2_errors = errors(&profile.to_bytes());
+    assert_eq!(v2_errors.len(), 1, "{:?}", v2_errors);
+    assert!(v2_errors[0].starts_with("Tag 'desc' has type 'mluc'"));
+    assert!(v2_errors[0].ends_with("for a version 2 profile"));
+
+    // desc and text are version 2 types
+    let mut profile = srgb().unwrap();
+    profile.header.version = 0x04400000;
+    let v4_errors = errors(&profile.to_bytes());
+    assert!(v4_errors
+        .iter()
+        .any(|error| error.starts_with("Tag 'desc' has type 'desc'")));
+    assert!(v4_errors
+        .iter()
+        .any(|error| error.starts_with("Tag 'cprt' has type 'text'")));
+}
+
+#[test]
+fn tags_outside_the_file_are_reported() {
+    let mut bytes = srgb().unwrap().to_bytes();
+    let tag_num = read_tag_table(&bytes)
+        .unwrap()
+        .iter()
+        .position(|(sig, _, _)| *sig == str_to_sig("bXYZ"))
+        .unwrap();
+    let entry = 132 + 12 * tag_num;
+    let offset = bytes.len() as u32 - 8;
+    bytes[entry + 4..entry + 8].copy_from_slice(&offset.to_be_bytes());
+
+    let errors = errors(&bytes);
+    assert!(
+        errors
+            .iter()
+            .any(|error| error.starts_with("Tag 'bXYZ'") && error.contains("outside the file")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use icc_reader::analysis::volume::{compare_volumes, GamutHull, MAX_HULL_FACES};
+use icc_reader::standard_profiles::{display_p3, lab_d50, srgb};
+use icc_reader::writers::mesh::{write_hull_obj, write_hull_x3d};
+
+#[test]
+fn hull_of_a_cube() {
+    // Corners, edge middles and the center of a 10 ΔE cube
+    let points: Vec<[f64; 3]> = (0..27)
+        .map(|idx| {
+            [
+                (idx % 3) as f64 * 5.0,
+                (idx / 3 % 3) as f64 * 5.0,
+                (idx / 9) as f64 * 5.0,
+            ]
+        })
+        .collect();
+    let hull = GamutHull::from_points(&points).unwrap();
+    assert!((hull.volume() - 1000.0).abs() < 1e-6);
+    assert!(hull.contains(&[5.0, 5.0, 5.0]));
+    assert!(!hull.contains(&[5.0, 5.0, 10.5]));
+
+    // Closed mesh : every edge is shared by two faces, in opposite directions
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    for [a, b, c] in hull.faces.iter() {
+        for edge in [(*a, *b), (*b, *c), (*c, *a)] {
+            *edges.entry(edge).or_default() += 1;
+        }
+    }
+    assert!(edges
+        .iter()
+        .all(|((a, b), count)| *count == 1 && edges.get(&(*b, *a)) == Some(&1)));
+
+    assert!(GamutHull::from_points(&[[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [2.0, 2.0, 2.0]]).is_err());
+}
+
+#[test]
+fn degenerate_points_are_handled() {
+    let cube = |size: f64| -> Vec<[f64; 3]> {
+        (0..8)
+            .map(|idx| [idx & 1, idx >> 1 & 1, idx >> 2 & 1].map(|bit| bit as f64 * size))
+            .collect()
+    };
+
+    // The plane tolerance follows the extent of the colors
+    for size in [1e-4, 1.0, 1e5] {
+        let hull = GamutHull::from_points(&cube(size)).unwrap();
+        assert!((hull.volume() / size.powi(3) - 1.0).abs() < 1e-9);
+        assert!(hull.contains(&[size / 2.0; 3]));
+        assert!(!hull.contains(&[size / 2.0, size / 2.0, size * 1.01]));
+    }
+
+    let mut points = cube(10.0);
+    points.extend([
+        [f64::NAN, 0.0, 0.0],
+        [0.0, f64::INFINITY, 0.0],
+        [-1e3, f64::NAN, 0.0],
+    ]);
+    let hull = GamutHull::from_points(&points).unwrap();
+    assert_eq!(hull.vertices.len(), 8);
+    assert!((hull.volume() - 1000.0).abs() < 1e-6);
+    assert!(GamutHull::from_points(&[[f64::NAN; 3]; 8]).is_err());
+
+    // Every point of a sphere is a vertex of its hull
+    let count = MAX_HULL_FACES / 2 + 100;
+    let sphere: Vec<[f64; 3]> = (0..count)
+        .map(|idx| {
+            let z = 1.0 - 2.0 * (idx as f64 + 0.5) / count as f64;
+            let angle = idx as f64 * 2.399963;
+            let radius = (1.0 - z * z).sqrt();
+            [radius * angle.cos(), radius * angle.sin(), z]
+        })
+        .collect();
+    assert!(GamutHull::from_points(&sphere).is_err());
+}
+
+#[test]
+fn gamut_volumes_are_compared() {
+    let lab = lab_d50().unwrap().gamut_hull(1).unwrap();
+    // L* 0..100, a* and b* -128..127
+    assert!((lab.volume() / (100.0 * 255.0 * 255.0) - 1.0).abs() < 0.02);
+
+    let srgb_hull = srgb().unwrap().gamut_hull(1).unwrap();
+    let p3_hull = display_p3().unwrap().gamut_hull(1).unwrap();
+    let comparison = compare_volumes(&srgb_hull, &p3_hull);
+    assert!(comparison.destination > 1.4 * comparison.source);
+    assert!(comparison.intersection > 0.99 * comparison.source);
+    assert!(comparison.source_only() < 0.01 * comparison.source);
+    assert!(
+        (comparison.destination_only() - (comparison.destination - comparison.source)).abs()
+            < 0.01 * comparison.source
+    );
+}
+
+#[test]
+fn hulls_are_exported_as_meshes() {
+    let hull = srgb().unwrap().gamut_hull(1).unwrap();
+
+    let obj = write_hull_obj(&hull, "sRGB").unwrap();
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("v ")).count(),
+        hull.vertices.len()
+    );
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("f ")).count(),
+        hull.faces.len()
+    );
+    // White vertex : L* 100, sRGB white
+    let white = obj
+        .lines()
+        .find(|line| line.split(' ').nth(2) == Some("100.0000"))
+        .unwrap();
+    assert!(white.starts_with("v 0.000"));
+    assert!(white.ends_with(" 1.0000 1.0000 1.0000"));
+
+    let x3d = write_hull_x3d(&hull, "<sRGB>").unwrap();
+    assert!(x3d.contains("Gamut hull of &lt;sRGB>"));
+    assert!(x3d.contains("<IndexedFaceSet solid=\"false\""));
+}